@@ -23,6 +23,9 @@ use crate::definitions::*;
 // Import standard library features
 use std::time::Duration;
 
+// Import Chrono features
+use chrono::NaiveDateTime;
+
 /// A structure to store queued events in a backup-safe format
 ///
 #[derive(Copy, Clone, Serialize, Deserialize)]
@@ -50,3 +53,71 @@ pub struct MediaPlayback {
     pub media_cue: MediaCue,  // the media information that was cued
     pub time_since: Duration, // the minimum time since the media was cued
 }
+
+/// Implement time updates for the MediaPlayback
+impl MediaPlayback {
+    /// A method to add time to the time_since field
+    ///
+    pub fn update(&mut self, additional_time: Duration) {
+        self.time_since = self
+            .time_since
+            .checked_add(additional_time)
+            .unwrap_or(self.time_since); // default to the existing time on overflow
+    }
+}
+
+/// A structure to report the performance of the backup backend
+///
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupMetrics {
+    pub batches: u64,              // the number of batches written to the backend
+    pub updates: u64,              // the number of updates received by the backup
+    pub writes: u64,               // the number of writes after combining updates to the same key
+    pub last_latency: Duration,    // the time from the oldest update to the end of the last batch
+    pub max_latency: Duration,     // the longest latency of any batch
+    pub average_latency: Duration, // the average latency of all batches
+}
+
+/// Implement metric updates for the BackupMetrics
+impl BackupMetrics {
+    /// A method to record a completed batch
+    ///
+    pub fn record(&mut self, updates: u64, writes: u64, latency: Duration) {
+        // Update the counts
+        self.batches += 1;
+        self.updates += updates;
+        self.writes += writes;
+
+        // Update the latencies
+        self.last_latency = latency;
+        self.max_latency = self.max_latency.max(latency);
+        let batches = self.batches as f64;
+        self.average_latency =
+            self.average_latency.mul_f64((batches - 1.0) / batches) + latency.div_f64(batches);
+    }
+}
+
+/// A structure to hold a complete, named copy of the game state
+///
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub saved: NaiveDateTime,            // the time when the snapshot was saved
+    pub current_scene: ItemId,           // the current scene
+    pub statuses: Vec<(ItemId, ItemId)>, // the current state of each status
+    pub queued_events: Vec<QueuedEvent>, // the events in the queue
+    pub media_playlist: Vec<MediaPlayback>, // the most recent media cued on each channel
+    pub dmx_universes: Vec<(u32, DmxUniverse)>, // the most recent values for each dmx universe
+    #[serde(default)]
+    pub hints: Vec<(ItemId, u32)>, // the number of hints delivered in each scene
+}
+
+/// A structure to describe a snapshot for the user interface
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotInfo {
+    pub name: String,          // the name of the snapshot
+    pub saved: NaiveDateTime,  // the time when the snapshot was saved
+    pub current_scene: ItemId, // the current scene in the snapshot
+}
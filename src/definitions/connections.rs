@@ -65,10 +65,11 @@ pub enum ConnectionType {
     /// A variant to connect with a Mercury serial port. This implementation
     /// assumes the serial connection uses the Mercury event protocol.
     Mercury {
-        path: PathBuf,                              // the location of the serial port
+        path: PathBuf,                 // the location of the serial port
         alternate_paths: Vec<PathBuf>, // other locations where the serial port may appear
         baud: u32,                     // the baud rate of the serial port
         use_checksum: bool, // a flag indicating the system should use and verify 32bit checksums
+        #[serde(serialize_with = "serialize_sorted_option")]
         allowed_events: Option<FnvHashSet<ItemId>>, // if specified, the only events that can be sent to this connection
     },
 
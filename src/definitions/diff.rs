@@ -0,0 +1,112 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements structures to report the differences between two
+//! configurations.
+
+// Import crate definitions
+use crate::definitions::*;
+
+/// An enum to describe the part of the configuration that differs
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffSection {
+    Parameters, // the configuration parameters
+    Settings,   // the other settings of the configuration
+    Item,       // the description of an item
+    Event,      // the event of an item
+    Group,      // the group of an item
+    Scene,      // the scene of an item
+    Status,     // the status of an item
+}
+
+/// An enum to describe how the part of the configuration differs
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffKind {
+    Added,   // the part is only in the second configuration
+    Removed, // the part is only in the first configuration
+    Changed, // the part is in both configurations, but differs
+}
+
+/// A struct to describe a single difference between two configurations
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChange {
+    pub section: DiffSection, // the part of the configuration that differs
+    pub item: Option<ItemId>, // the item that differs, if any
+    pub kind: DiffKind,       // how the part differs
+    pub description: String, // the description of the item, or the names of the settings that differ
+}
+
+/// A struct to hold every difference between two configurations
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDiff {
+    pub from: String,               // the first configuration
+    pub to: String,                 // the second configuration
+    pub changes: Vec<ConfigChange>, // the differences, ordered by item and section
+}
+
+// Implement key features of the configuration diff
+impl ConfigDiff {
+    /// A method to check if the configurations differ
+    ///
+    pub fn has_changes(&self) -> bool {
+        !self.changes.is_empty()
+    }
+
+    /// A method to describe the differences as human-readable text, with one
+    /// line per difference and a summary at the end
+    ///
+    pub fn to_text(&self) -> String {
+        // Describe each difference
+        let mut text = String::new();
+        for change in self.changes.iter() {
+            let kind = match change.kind {
+                DiffKind::Added => "+",
+                DiffKind::Removed => "-",
+                DiffKind::Changed => "~",
+            };
+            match change.item {
+                Some(item) => {
+                    text += &format!(
+                        "{} {:?} [{}]: {}\n",
+                        kind, change.section, item, change.description
+                    )
+                }
+                None if change.description.is_empty() => {
+                    text += &format!("{} {:?}\n", kind, change.section)
+                }
+                None => text += &format!("{} {:?}: {}\n", kind, change.section, change.description),
+            }
+        }
+
+        // Add the summary
+        text += &format!(
+            "{} -> {}: {} difference(s)\n",
+            self.from,
+            self.to,
+            self.changes.len()
+        );
+        text
+    }
+}
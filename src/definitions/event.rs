@@ -199,22 +199,33 @@ impl From<DmxFade> for DmxFadeHelper {
 /// media when this media completes. This takes priority over the channel loop
 /// media field.
 ///
+/// If an event is specified in the on complete field, the event will be cued
+/// when the media player reports that this media has finished playing. If
+/// new media is cued on the same channel before then, the event is discarded.
+///
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MediaCue {
     pub uri: String,                // the location of the video or audio file to play
     pub channel: u32, // the channel of the video or audio. New media sent to the same channel will replace the old media, starting instantly
     pub loop_media: Option<String>, // the location of media to loop after this media is complete
+    #[serde(default)]
+    pub on_complete: Option<ItemId>, // the event to cue when this media is complete
 }
 
 // A helper struct to define a single media cue.
 // This version is serialized with camelCase to allow compatability with Apollo.
 //
+// If a complete callback is provided, Apollo posts the channel and uri of the
+// media to this address when the media finishes playing.
+//
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MediaCueHelper {
     pub uri: String,
     pub channel: u32,
     pub loop_media: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complete_callback: Option<String>,
 }
 
 // Implement conversion to MediaCueHelper
@@ -225,10 +236,40 @@ impl From<MediaCue> for MediaCueHelper {
             uri: media_cue.uri,
             channel: media_cue.channel,
             loop_media: media_cue.loop_media,
+            complete_callback: None,
         }
     }
 }
 
+/// A struct to define a single item in a media playlist
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaylistItem {
+    pub uri: String, // the location of the video or audio file to play
+    #[serde(default)]
+    pub event: Option<ItemId>, // the event to cue when this item begins to play
+}
+
+/// A struct to define a list of media to play in turn on a single channel
+///
+/// # Note
+///
+/// Each item follows the previous item when the media player reports that
+/// the previous item has finished playing. If shuffle is set, the items are
+/// played in a random order (reshuffled each time the playlist loops).
+///
+/// New media sent to the same channel will replace the playlist.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaPlaylist {
+    pub items: Vec<PlaylistItem>, // the media to play, in order
+    pub channel: u32,             // the channel of the video or audio
+    #[serde(default)]
+    pub shuffle: bool, // a flag to play the items in a random order
+    #[serde(default)]
+    pub loop_playlist: bool, // a flag to restart the playlist after the last item
+}
+
 /// An enum to define the adjustment directions
 ///
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -296,6 +337,7 @@ impl From<MediaAdjustment> for MediaAdjustmentHelper {
 /// An enum with various action options for each event.
 ///
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum EventAction {
     /// A variant to adjust media on one of the media channels.
     AdjustMedia {
@@ -325,13 +367,28 @@ pub enum EventAction {
         cue: MediaCue,
     },
 
+    /// A variant to cue a playlist of media on one of the media channels.
+    CueMediaPlaylist {
+        playlist: MediaPlaylist,
+    },
+
+    /// A variant to deliver the next hint for the current scene.
+    DeliverHint {},
+
+    /// A variant to end the current game session with the provided outcome.
+    EndSession {
+        outcome: String,
+    },
+
     /// A variant used to change current status of the target status.
+    #[serde(rename_all = "camelCase")]
     ModifyStatus {
         status_id: ItemId,
         new_state: ItemId,
     },
 
     /// A variant indicating a complete change in scene.
+    #[serde(rename_all = "camelCase")]
     NewScene {
         new_scene: ItemId,
     },
@@ -344,6 +401,7 @@ pub enum EventAction {
 
     /// A variant which selects an event based on the state of the indicated
     /// status.
+    #[serde(rename_all = "camelCase")]
     SelectEvent {
         status_id: ItemId,
         event_map: FnvHashMap<ItemId, ItemId>,
@@ -354,11 +412,15 @@ pub enum EventAction {
     SendData {
         data: DataType,
     },
+
+    /// A variant to start a new game session.
+    StartSession {},
 }
 
-/// An web-safe (JSON readable) enum with various action options for each event. FIXME change to camel case
+/// An web-safe (JSON readable) enum with various action options for each event.
 ///
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum WebEventAction {
     /// A variant to adjust media on one of the media channels.
     AdjustMedia {
@@ -388,13 +450,28 @@ pub enum WebEventAction {
         cue: MediaCue,
     },
 
+    /// A variant to cue a playlist of media on one of the media channels.
+    CueMediaPlaylist {
+        playlist: MediaPlaylist,
+    },
+
+    /// A variant to deliver the next hint for the current scene.
+    DeliverHint {},
+
+    /// A variant to end the current game session with the provided outcome.
+    EndSession {
+        outcome: String,
+    },
+
     /// A variant used to change current status of the target status.
+    #[serde(rename_all = "camelCase")]
     ModifyStatus {
         status_id: ItemId,
         new_state: ItemId,
     },
 
     /// A variant indicating a complete change in scene.
+    #[serde(rename_all = "camelCase")]
     NewScene {
         new_scene: ItemId,
     },
@@ -407,6 +484,7 @@ pub enum WebEventAction {
 
     /// A variant which selects an event based on the state of the indicated
     /// status.
+    #[serde(rename_all = "camelCase")]
     SelectEvent {
         status_id: ItemId,
         event_map: FnvHashMap<u32, ItemId>,
@@ -417,6 +495,9 @@ pub enum WebEventAction {
     SendData {
         data: DataType,
     },
+
+    /// A variant to start a new game session.
+    StartSession {},
 }
 
 // Implement conversions to and from WebEventAction
@@ -447,6 +528,11 @@ impl From<EventAction> for WebEventAction {
             EventAction::CueDmx { fade } => WebEventAction::CueDmx { fade },
             EventAction::CueEvent { event } => WebEventAction::CueEvent { event },
             EventAction::CueMedia { cue } => WebEventAction::CueMedia { cue },
+            EventAction::CueMediaPlaylist { playlist } => {
+                WebEventAction::CueMediaPlaylist { playlist }
+            }
+            EventAction::DeliverHint {} => WebEventAction::DeliverHint {},
+            EventAction::EndSession { outcome } => WebEventAction::EndSession { outcome },
             EventAction::ModifyStatus {
                 status_id,
                 new_state,
@@ -457,6 +543,7 @@ impl From<EventAction> for WebEventAction {
             EventAction::NewScene { new_scene } => WebEventAction::NewScene { new_scene },
             EventAction::SaveData { data } => WebEventAction::SaveData { data },
             EventAction::SendData { data } => WebEventAction::SendData { data },
+            EventAction::StartSession {} => WebEventAction::StartSession {},
         }
     }
 }
@@ -487,6 +574,11 @@ impl From<WebEventAction> for EventAction {
             WebEventAction::CueDmx { fade } => EventAction::CueDmx { fade },
             WebEventAction::CueEvent { event } => EventAction::CueEvent { event },
             WebEventAction::CueMedia { cue } => EventAction::CueMedia { cue },
+            WebEventAction::CueMediaPlaylist { playlist } => {
+                EventAction::CueMediaPlaylist { playlist }
+            }
+            WebEventAction::DeliverHint {} => EventAction::DeliverHint {},
+            WebEventAction::EndSession { outcome } => EventAction::EndSession { outcome },
             WebEventAction::ModifyStatus {
                 status_id,
                 new_state,
@@ -497,6 +589,7 @@ impl From<WebEventAction> for EventAction {
             WebEventAction::NewScene { new_scene } => EventAction::NewScene { new_scene },
             WebEventAction::SaveData { data } => EventAction::SaveData { data },
             WebEventAction::SendData { data } => EventAction::SendData { data },
+            WebEventAction::StartSession {} => EventAction::StartSession {},
         }
     }
 }
@@ -537,6 +630,6 @@ pub type Event = Vec<EventAction>;
 
 // Reexport the event action type variants
 pub use self::EventAction::{
-    AdjustMedia, CancelEvent, CueDmx, CueEvent, CueMedia, ModifyStatus, NewScene, SaveData,
-    SelectEvent, SendData,
+    AdjustMedia, CancelEvent, CueDmx, CueEvent, CueMedia, CueMediaPlaylist, DeliverHint,
+    EndSession, ModifyStatus, NewScene, SaveData, SelectEvent, SendData, StartSession,
 };
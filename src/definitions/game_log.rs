@@ -0,0 +1,80 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements structures for the structured game log.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import Chrono features
+use chrono::{NaiveDate, NaiveDateTime};
+
+/// A struct to hold one record of the game log. Each record is saved as a
+/// single line of JSON in the daily game log file.
+///
+/// # Notes
+///
+/// Each record describes one event, one status change, or one piece of saved
+/// game data. The fields that don't apply to the record are left empty.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameRecord {
+    pub time: NaiveDateTime,      // the time of the record
+    pub session: Option<String>,  // the id of the current game session, if running
+    pub scene: ItemPair,          // the current scene
+    pub event: Option<ItemPair>,  // the event that was processed, if any
+    pub status: Option<ItemPair>, // the status that changed, if any
+    pub state: Option<ItemPair>,  // the new state of the status, if any
+    pub data: Option<String>,     // the game data that was saved, if any
+}
+
+/// A struct to select records from the game log. Empty fields match every
+/// record.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameLogQuery {
+    pub start_date: Option<NaiveDate>, // the first day to include
+    pub end_date: Option<NaiveDate>,   // the last day to include
+    pub event_id: Option<ItemId>,      // the event to match
+    pub session: Option<String>,       // the game session to match
+}
+
+// Implement key features of the game log query
+impl GameLogQuery {
+    /// A method to check if the day falls within the date range
+    ///
+    pub fn includes_date(&self, date: &NaiveDate) -> bool {
+        self.start_date.is_none_or(|start| start <= *date)
+            && self.end_date.is_none_or(|end| *date <= end)
+    }
+
+    /// A method to check if the record matches the event and session
+    ///
+    pub fn matches(&self, record: &GameRecord) -> bool {
+        self.event_id.is_none_or(|event_id| {
+            record
+                .event
+                .as_ref()
+                .is_some_and(|event| event.get_id() == event_id)
+        }) && self
+            .session
+            .as_ref()
+            .is_none_or(|session| record.session.as_ref() == Some(session))
+    }
+}
@@ -28,8 +28,9 @@ use fnv::FnvHashSet;
 ///
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
 pub struct Group {
+    #[serde(serialize_with = "serialize_sorted")]
     pub items: FnvHashSet<ItemId>, // hash set of the items in this scene
-    pub is_hidden: bool,           // a flag to indicate whether the items in the group are visible
+    pub is_hidden: bool, // a flag to indicate whether the items in the group are visible
 }
 
 /// A structure to define the parameters of a group, web version
@@ -0,0 +1,51 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements structures for the hints available in each scene.
+
+// Import crate definitions
+use crate::definitions::*;
+
+/// A struct to define a single hint for the players
+///
+/// # Notes
+///
+/// The message is sent to the limited interface clients and the run
+/// interface. If media is specified, the media is also cued on its channel
+/// (for example, a video hint on the screen in the room).
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Hint {
+    pub message: String, // the text of the hint
+    #[serde(default)]
+    pub media: Option<MediaCue>, // media to cue with the hint, if any
+}
+
+/// A struct to define the penalty for each hint delivered to the players
+///
+/// # Notes
+///
+/// The status is changed to the new state once for each hint. For a counted
+/// status (e.g. a score), this adjusts the count by one for each hint.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HintPenalty {
+    pub status_id: ItemId, // the status to change for each hint
+    pub new_state: ItemId, // the state to send to the status for each hint
+}
@@ -25,6 +25,9 @@ use crate::definitions::*;
 use tokio::sync::mpsc;
 use warp::ws::Message;
 
+// Import Chrono features
+use chrono::NaiveDateTime;
+
 // Import FNV HashMap
 use fnv::FnvHashMap;
 
@@ -56,6 +59,30 @@ pub struct ConfigParameters {
     pub default_scene: ItemId,
 }
 
+/// A structure to hold the other settings of the configuration file
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSettings {
+    pub state_sync: Option<SyncParams>,
+    pub statistics: Option<StatisticsParams>,
+    pub hint_penalty: Option<HintPenalty>,
+    pub user_styles: StyleMap,
+    pub describe_items: bool,
+}
+
+/// A structure to hold a warning or error to display to the user
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    pub id: u64,               // the unique id of the notification
+    pub time: NaiveDateTime,   // the time of the notification
+    pub is_error: bool,        // a flag to indicate an error, rather than a warning
+    pub message: String,       // the text of the notification
+    pub is_acknowledged: bool, // a flag to indicate the user has acknowledged the notification
+}
+
 /// An enum type to provide updates to the web interface
 ///
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,7 +117,7 @@ pub enum InterfaceUpdate {
 
     /// A variant indicating that the system notifications should be updated.
     #[serde(rename_all = "camelCase")]
-    UpdateNotifications { notifications: Vec<String> },
+    UpdateNotifications { notifications: Vec<Notification> },
 
     /// A variant indicating that the event timeline should be updated.
     #[serde(rename_all = "camelCase")]
@@ -163,6 +190,13 @@ pub enum LimitedUpdate {
         current_status: CurrentStatus,
     },
 
+    /// A variant to display a hint to the players
+    #[serde(rename_all = "camelCase")]
+    DeliverHint {
+        number: u32,     // the number of the hint in the current scene, starting at one
+        message: String, // the text of the hint
+    },
+
     /// A variant indicating the current scene should be refreshed with
     /// the new scene.
     #[serde(rename_all = "camelCase")]
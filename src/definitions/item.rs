@@ -25,7 +25,10 @@ use std::fmt;
 use std::hash;
 
 // Import Serde macros
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+
+// Import FNV HashSet
+use fnv::FnvHashSet;
 
 /// Define the All Stop command (a.k.a. emergency stop)
 const ALL_STOP: u32 = 0;
@@ -159,6 +162,34 @@ impl fmt::Display for ItemId {
     }
 }
 
+/// A function to serialize a set of item ids in order, so that the set is
+/// written the same way every time
+///
+pub fn serialize_sorted<S>(ids: &FnvHashSet<ItemId>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut ids: Vec<&ItemId> = ids.iter().collect();
+    ids.sort();
+    ids.serialize(serializer)
+}
+
+/// A function to serialize an optional set of item ids in order, so that
+/// the set is written the same way every time
+///
+pub fn serialize_sorted_option<S>(
+    ids: &Option<FnvHashSet<ItemId>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match ids {
+        Some(ids) => serialize_sorted(ids, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
 /// This structure is a human-readable description and formatting parameters
 /// to be paired with an ItemId.
 ///
@@ -17,6 +17,9 @@
 
 //! This module implements structures shared from the media interface
 
+// Import crate definitions
+use crate::definitions::*;
+
 // Import Serde macros
 pub use serde::{Deserialize, Serialize};
 
@@ -196,19 +199,47 @@ pub type WindowMap = FnvHashMap<u32, WindowDefinition>;
 
 /// A struct to hold parameters for Apollo media player
 ///
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ApolloParams {
     pub spawn: bool, // a flag if Minerva should spawn and manage the Apollo process
     pub address: Option<String>, // the address where Apollo will listen for instructions, defaults to Apollo on localhost
 }
 
+/// An enum to select the backend that plays the media for a media player
+///
+/// # Note
+///
+/// The command line and null backends only play audio. Video frames, windows,
+/// and media adjustments are ignored. Seeking is not supported, so restored
+/// media restarts from the beginning.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MediaBackend {
+    /// A variant to play media with the Apollo media player
+    #[default]
+    Apollo,
+
+    /// A variant to play audio with a command line player, with one process
+    /// per channel. The file path of the media is appended to the arguments.
+    CommandLine {
+        command: String,        // the command to run the player (e.g. "mpg123")
+        arguments: Vec<String>, // any arguments to pass before the file path
+    },
+
+    /// A variant to discard all media, reporting it complete immediately
+    Null,
+}
+
 /// A scruct to specify all the details of a media player
 ///
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MediaPlayer {
     pub channel_map: ChannelMap, // the map of channel numbers to channel properties
     pub window_map: WindowMap,   // the map of window numbers to window properties
+    #[serde(default)]
     pub apollo_params: ApolloParams, // the parameters for Apollo media player
+    #[serde(default)]
+    pub backend: MediaBackend, // the backend to play the media, defaults to Apollo
 }
 
 // A helper struct to specify seeking media on a channel
@@ -219,3 +250,28 @@ pub struct SeekMediaHelper {
     pub channel: u32,  // the channel for the media
     pub position: u64, // the new position within the media stream in milliseconds
 }
+
+/// An enum to describe a problem found with media in the configuration
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MediaProblem {
+    MissingFile,    // the local file does not exist
+    UnreadableFile, // the local file exists, but cannot be opened
+    UnknownChannel, // the channel is not defined for any media player
+}
+
+/// A struct to describe a single problem with media in the configuration
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaIssue {
+    pub event_id: Option<ItemId>, // the event with the media, or None for channel loop media
+    pub channel: u32,             // the channel for the media
+    pub uri: String,              // the location of the media
+    pub problem: MediaProblem,    // the problem with the media
+}
+
+/// A type to store all the problems found with media in the configuration
+///
+pub type MediaReport = Vec<MediaIssue>;
@@ -21,7 +21,8 @@
 pub const DEFAULT_FILE: &str = "default.yaml"; // the default configuration filename
 pub const DEFAULT_LOGLEVEL: &str = "Info";
 pub const LOG_FOLDER: &str = "log/"; // the default log folder
-pub const GAME_LOG: &str = "game_log"; // the default logging filename
+pub const GAME_LOG: &str = "game_log"; // the default game log filename
+pub const ERROR_LOG: &str = "error_log"; // the default error log filename
 
 // Define web interface constants
 pub const DEFAULT_LIMITED_ADDRESS: &str = "127.0.0.1:64635";
@@ -36,20 +37,27 @@ mod test;
 mod background;
 mod backup;
 mod connections;
+mod diff;
 #[allow(dead_code)] // Allow dead code, reserved for future use
 mod dmx;
 mod event;
+mod game_log;
 mod group;
+mod hint;
 #[allow(dead_code)] // Allow dead code, reserved for future use
 mod index;
 mod interface;
 mod item;
 mod media;
 mod scene;
+mod session;
+mod statistics;
 mod status;
 #[allow(dead_code)] // Allow dead code, reserved for future use
 mod style;
+mod sync;
 mod system;
+mod validation;
 
 // Import Serde macros
 pub use serde::{Deserialize, Serialize};
@@ -58,17 +66,24 @@ pub use serde::{Deserialize, Serialize};
 pub use self::background::*;
 pub use self::backup::*;
 pub use self::connections::*;
+pub use self::diff::*;
 pub use self::dmx::*;
 pub use self::event::*;
+pub use self::game_log::*;
 pub use self::group::*;
+pub use self::hint::*;
 pub use self::index::*;
 pub use self::interface::*;
 pub use self::item::*;
 pub use self::media::*;
 pub use self::scene::*;
+pub use self::session::*;
+pub use self::statistics::*;
 pub use self::status::*;
 pub use self::style::*;
+pub use self::sync::*;
 pub use self::system::*;
+pub use self::validation::*;
 
 // Reexport the testing module and definitions
 #[cfg(test)]
@@ -32,18 +32,29 @@ pub type KeyMap = FnvHashMap<u32, ItemId>;
 ///
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
 pub struct Scene {
+    #[serde(serialize_with = "serialize_sorted")]
     pub items: FnvHashSet<ItemId>, // hash set of the items in this scene (excluding groups)
+    #[serde(serialize_with = "serialize_sorted")]
     pub groups: FnvHashSet<ItemId>, // hash set of the groups in this scene
-    pub key_map: Option<KeyMap>,   // an optional mapping of key codes to events
+    pub key_map: Option<KeyMap>, // an optional mapping of key codes to events
+    #[serde(default)]
+    pub hints: Vec<Hint>, // the hints for this scene, in the order they are delivered
 }
 
 /// A structure to define the parameters of a scene, web version
 ///
+/// # Notes
+///
+/// If the hints are not provided when modifying a scene, the existing hints
+/// of the scene are kept.
+///
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WebScene {
     pub items: FnvHashSet<ItemId>, // hash set of the items in this scene (including group ids)
     pub key_map: Option<KeyMap>,   // an optional mapping of key codes to events
+    #[serde(default)]
+    pub hints: Option<Vec<Hint>>, // the hints for this scene, if provided
 }
 
 // Implement conversion from Scene to WebScene
@@ -56,6 +67,7 @@ impl From<Scene> for WebScene {
         Self {
             items: scene.items,
             key_map: scene.key_map,
+            hints: Some(scene.hints),
         }
     }
 }
@@ -0,0 +1,82 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements structures to record each game session.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import Chrono features
+use chrono::NaiveDateTime;
+
+/// A struct to hold the complete record of a game session
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionRecord {
+    pub id: String,                 // the unique id of the session
+    pub team_name: Option<String>,  // the name of the team, if provided
+    pub player_count: Option<u32>,  // the number of players, if provided
+    pub start: NaiveDateTime,       // the time the session started
+    pub end: Option<NaiveDateTime>, // the time the session ended, if finished
+    pub outcome: Option<String>,    // the outcome of the session, if finished
+    #[serde(default)]
+    pub hint_count: u32, // the number of hints delivered during the session
+    pub data: Vec<SessionData>,     // the game data saved during the session
+}
+
+/// A struct to hold one piece of game data saved during a session
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionData {
+    pub time: NaiveDateTime, // the time the data was saved
+    pub data: String,        // the saved data
+}
+
+/// A struct to summarize a game session for display
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSummary {
+    pub id: String,                 // the unique id of the session
+    pub team_name: Option<String>,  // the name of the team, if provided
+    pub player_count: Option<u32>,  // the number of players, if provided
+    pub start: NaiveDateTime,       // the time the session started
+    pub end: Option<NaiveDateTime>, // the time the session ended, if finished
+    pub duration: Option<u64>,      // the length of the session in seconds, if finished
+    pub outcome: Option<String>,    // the outcome of the session, if finished
+    pub hint_count: u32,            // the number of hints delivered during the session
+}
+
+// Implement the conversion from a session record to a session summary
+impl From<&SessionRecord> for SessionSummary {
+    fn from(record: &SessionRecord) -> Self {
+        Self {
+            id: record.id.clone(),
+            team_name: record.team_name.clone(),
+            player_count: record.player_count,
+            start: record.start,
+            end: record.end,
+            duration: record
+                .end
+                .map(|end| (end - record.start).num_seconds().max(0) as u64),
+            outcome: record.outcome.clone(),
+            hint_count: record.hint_count,
+        }
+    }
+}
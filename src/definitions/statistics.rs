@@ -0,0 +1,158 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements structures for the room statistics computed from
+//! the game sessions and the game log.
+
+// Import crate definitions
+use crate::definitions::*;
+
+/// A struct to define which events are used for the room statistics
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatisticsParams {
+    #[serde(default)]
+    pub milestones: Vec<ItemId>, // the events that mark progress through the game, in order
+    #[serde(default)]
+    pub hints: Vec<ItemId>, // the events that deliver a hint to the players
+}
+
+/// A struct to hold the time to reach one milestone during a session
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneTime {
+    pub milestone: ItemPair,  // the milestone event
+    pub seconds: Option<u64>, // the seconds from the start of the session, if reached
+}
+
+/// A struct to hold the statistics for a single game session
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatistics {
+    pub id: String,                     // the unique id of the session
+    pub team_name: Option<String>,      // the name of the team, if provided
+    pub player_count: Option<u32>,      // the number of players, if provided
+    pub duration: Option<u64>,          // the length of the session in seconds, if finished
+    pub outcome: Option<String>,        // the outcome of the session, if finished
+    pub hint_count: u32,                // the number of hints delivered
+    pub milestones: Vec<MilestoneTime>, // the time to reach each milestone
+}
+
+/// A struct to summarize one milestone across every session
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneSummary {
+    pub milestone: ItemPair,          // the milestone event
+    pub reached_count: u32,           // the number of sessions that reached the milestone
+    pub average_seconds: Option<u64>, // the average time to reach the milestone, if reached
+    pub fastest_seconds: Option<u64>, // the fastest time to reach the milestone, if reached
+}
+
+/// A struct to hold the number of times an event was triggered
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventCount {
+    pub event: ItemPair, // the event
+    pub count: u32,      // the number of times it was triggered
+}
+
+/// A struct to hold the statistics for the room across every selected session
+///
+/// # Notes
+///
+/// A session is complete when it was ended with any outcome other than
+/// abandoned. The average duration only includes the completed sessions.
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomStatistics {
+    pub session_count: u32,                // the number of sessions
+    pub completed_count: u32,              // the number of completed sessions
+    pub completion_rate: f64,              // the fraction of sessions that were completed
+    pub average_duration: Option<u64>,     // the average length of a completed session in seconds
+    pub average_hints: f64,                // the average number of hints per session
+    pub milestones: Vec<MilestoneSummary>, // the summary of each milestone
+    pub top_events: Vec<EventCount>,       // the most triggered events, most frequent first
+    pub sessions: Vec<SessionStatistics>,  // the statistics for each session
+}
+
+// Implement key features of the room statistics
+impl RoomStatistics {
+    /// A method to export the statistics for each session as comma-separated
+    /// values, with one row per session
+    ///
+    pub fn to_csv(&self) -> String {
+        // Compose the header from the fixed columns and the milestones
+        let mut header = vec![
+            "Session".to_string(),
+            "Team Name".into(),
+            "Player Count".into(),
+            "Duration (s)".into(),
+            "Outcome".into(),
+            "Hints".into(),
+        ];
+        for summary in self.milestones.iter() {
+            header.push(format!("{} (s)", summary.milestone.description));
+        }
+        let mut lines = vec![RoomStatistics::csv_row(&header)];
+
+        // Add a row for each session
+        for session in self.sessions.iter() {
+            let mut row = vec![
+                session.id.clone(),
+                session.team_name.clone().unwrap_or_default(),
+                RoomStatistics::csv_optional(session.player_count),
+                RoomStatistics::csv_optional(session.duration),
+                session.outcome.clone().unwrap_or_default(),
+                session.hint_count.to_string(),
+            ];
+            for time in session.milestones.iter() {
+                row.push(RoomStatistics::csv_optional(time.seconds));
+            }
+            lines.push(RoomStatistics::csv_row(&row));
+        }
+
+        // Join the lines
+        lines.join("\n") + "\n"
+    }
+
+    /// A helper function to compose one row, quoting fields when necessary
+    ///
+    fn csv_row(fields: &[String]) -> String {
+        fields
+            .iter()
+            .map(|field| {
+                if field.contains([',', '"', '\n']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.clone()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    /// A helper function to show an optional value, or nothing if missing
+    ///
+    fn csv_optional<T: ToString>(value: Option<T>) -> String {
+        value.map(|value| value.to_string()).unwrap_or_default()
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements structures to share statuses and scenes between
+//! instances of Minerva.
+
+// Import crate definitions
+use crate::definitions::*;
+
+/// A struct to define the statuses and scenes shared with other instances
+///
+/// # Notes
+///
+/// Shared statuses use the same status id on every instance. Any instance
+/// that lists the status in its own configuration will follow the latest
+/// state. Instances that do not have the status (such as a lobby) will only
+/// display it.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncParams {
+    pub address: String, // the local address to receive updates from other instances
+    pub peers: Vec<String>, // the addresses of the other instances
+    #[serde(default)]
+    pub statuses: Vec<ItemId>, // the statuses to share with other instances
+    #[serde(default)]
+    pub share_scene: bool, // a flag to share the current scene with other instances
+    #[serde(default)]
+    pub reactions: Vec<SyncReaction>, // the local events to trigger from remote changes
+}
+
+/// A struct to trigger a local event when a scene or state is reported by
+/// another instance
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncReaction {
+    pub item_id: ItemId,  // the remote scene or state to react to
+    pub event_id: ItemId, // the local event to trigger
+    #[serde(default)]
+    pub identifier: Option<u32>, // the instance to react to, or any instance if None
+}
+
+/// An enum to identify each value shared between instances
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncKey {
+    /// A variant for the current scene of one instance
+    Scene { identifier: u32 },
+
+    /// A variant for a shared status, common to all instances
+    Status { status_id: ItemId },
+}
+
+/// A struct to order the changes to a shared value. Versions are compared by
+/// their logical clock first and then by the identifier of the instance that
+/// made the change, so every instance picks the same latest value.
+///
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct SyncVersion {
+    pub clock: u64,      // the logical clock of the change
+    pub identifier: u32, // the instance that made the change
+}
+
+/// A struct to hold the latest value of a shared scene or status
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncEntry {
+    pub key: SyncKey,         // the shared scene or status
+    pub description: String,  // the description of the scene or status, for display
+    pub value: ItemPair,      // the current scene or state
+    pub version: SyncVersion, // the version of the change
+}
+
+/// A struct to hold a group of entries sent between instances
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncMessage {
+    pub identifier: u32,         // the instance that sent the message
+    pub entries: Vec<SyncEntry>, // the entries known to that instance
+}
@@ -60,6 +60,26 @@ pub enum InternalUpdate {
         data1: u32,
         data2: u32,
     },
+
+    /// A variant to notify the system that media has finished playing on
+    /// the indicated channel
+    MediaComplete { channel: u32, uri: String },
+
+    /// A variant to notify the standby that the primary was lost and this
+    /// instance should take over
+    FailoverTakeover,
+
+    /// A variant to pass the shared statuses and scenes received from another
+    /// instance
+    SyncUpdate(SyncMessage),
+
+    /// A variant to notify the system that the watched configuration file
+    /// has changed
+    ConfigChanged(PathBuf),
+
+    /// A variant to notify the system that it is time to autosave any
+    /// changes to the configuration
+    Autosave,
 }
 
 /// The stucture and methods to send internal updates to the system interface.
@@ -123,6 +143,51 @@ impl InternalSend {
             .await
             .unwrap_or(());
     }
+
+    // A method to notify the system that media has finished playing.
+    //
+    pub async fn send_media_complete(&self, channel: u32, uri: String) {
+        self.internal_send
+            .send(InternalUpdate::MediaComplete { channel, uri })
+            .await
+            .unwrap_or(());
+    }
+
+    // A method to notify the standby that it should take over.
+    //
+    pub async fn send_failover_takeover(&self) {
+        self.internal_send
+            .send(InternalUpdate::FailoverTakeover)
+            .await
+            .unwrap_or(());
+    }
+
+    // A method to pass the shared statuses and scenes from another instance.
+    //
+    pub async fn send_sync_update(&self, message: SyncMessage) {
+        self.internal_send
+            .send(InternalUpdate::SyncUpdate(message))
+            .await
+            .unwrap_or(());
+    }
+
+    // A method to notify the system that the configuration file has changed.
+    //
+    pub async fn send_config_changed(&self, path: PathBuf) {
+        self.internal_send
+            .send(InternalUpdate::ConfigChanged(path))
+            .await
+            .unwrap_or(());
+    }
+
+    // A method to notify the system that it is time to autosave.
+    //
+    pub async fn send_autosave(&self) {
+        self.internal_send
+            .send(InternalUpdate::Autosave)
+            .await
+            .unwrap_or(());
+    }
 }
 
 /// The stucture and methods to send WebRequests to the system interface
@@ -194,6 +259,10 @@ pub enum Modification {
     #[serde(rename_all = "camelCase")]
     ModifyParameters { parameters: ConfigParameters },
 
+    /// A modification to change the other settings of the configuration
+    #[serde(rename_all = "camelCase")]
+    ModifySettings { settings: ConfigSettings },
+
     /// A modification to add a status, modify an existing one, or delete it
     /// (if None provided)
     #[serde(rename_all = "camelCase")]
@@ -235,18 +304,27 @@ pub enum DetailType {
     /// NOTE: Consider moving this to the index module (see AllItems)
     AllScenes,
 
+    /// A variant for the latency metrics of the backup
+    BackupMetrics,
+
     /// A variant for the event associated with an item
     Event { item_id: ItemId },
 
     /// A variant for the list of all events in a group
     Group { item_id: ItemId },
 
+    /// A variant for a report of any problems with the media in the configuration
+    MediaReport,
+
     /// A variant for the status associated with an item
     Status { item_id: ItemId },
 
     /// A variant for the list of all events in a scene
     Scene { item_id: ItemId },
 
+    /// A variant for the statuses and scenes shared by all instances
+    SyncState,
+
     /// A variant for the item type
     Type { item_id: ItemId },
 }
@@ -285,6 +363,11 @@ pub enum UserRequest {
     /// If there is no active configuration, this request will throw an error.
     ConfigParameters,
 
+    /// A variant that retrieves the other settings of the current
+    /// configuration, including the state sync, statistics, hint penalty, etc.
+    /// If there is no active configuration, this request will throw an error.
+    ConfigSettings,
+
     /// A variant that cues a new event with the given item id. The event
     /// will trigger after the specified delay has passed.
     CueEvent { event_delay: EventDelay },
@@ -292,12 +375,57 @@ pub enum UserRequest {
     /// A variant to provide the current scene and status
     CurrentSceneAndStatus,
 
+    /// A variant to delete the named snapshot of the game state.
+    DeleteSnapshot { name: String },
+
+    /// A variant to deliver the next hint for the current scene.
+    DeliverHint,
+
     /// A variant to provide details as requested by the web interface.
     Detail { detail_type: DetailType },
 
+    /// A variant to provide the differences between the current
+    /// configuration and the provided configuration file.
+    DiffConfig { filepath: PathBuf },
+
     /// A variant to modify the underlying configuration.
     Edit { modifications: Vec<Modification> },
 
+    /// A variant to end the current game session with the provided outcome.
+    EndSession { outcome: String },
+
+    /// A variant to export the items and events of the current configuration
+    /// to a cue list in the provided CSV file.
+    ExportCues { filepath: PathBuf },
+
+    /// A variant to provide the records of the game log that match the query.
+    GameLog { query: GameLogQuery },
+
+    /// A variant to import the cue list in the provided CSV file into the
+    /// current configuration.
+    ImportCues { filepath: PathBuf },
+
+    /// A variant to provide a summary of the saved game sessions.
+    ListSessions,
+
+    /// A variant to provide the room statistics for the sessions within the
+    /// date range of the query.
+    Statistics { query: GameLogQuery },
+
+    /// A variant to provide a list of the saved snapshots of the game state.
+    ListSnapshots,
+
+    /// A variant to notify the system that media has finished playing on
+    /// the indicated channel.
+    MediaComplete { channel: u32, uri: String },
+
+    /// A variant to merge the selected differences from the provided
+    /// configuration file into the current configuration.
+    MergeConfig {
+        filepath: PathBuf,
+        changes: Vec<ConfigChange>,
+    },
+
     /// A variant to change the remaining delay for an existing event in the
     /// queue.
     EventChange {
@@ -306,18 +434,46 @@ pub enum UserRequest {
         new_delay: Option<Duration>, // new delay relative to the original start time, or None to cancel the event
     },
 
+    /// A variant to load the most recent autosave of the current
+    /// configuration in place of the current configuration.
+    RecoverAutosave,
+
+    /// A variant to repeat the most recently undone change to the
+    /// configuration.
+    Redo,
+
+    /// A variant to restore the game state from the named snapshot.
+    RestoreSnapshot { name: String },
+
     /// A variant that provides a new configuration file to save the current
     /// configuration.
     SaveConfig { filepath: PathBuf },
 
+    /// A variant to save the current game state as a named snapshot,
+    /// replacing any existing snapshot with the same name.
+    SaveSnapshot { name: String },
+
     /// A variant to change the current scene.
     SceneChange { scene: ItemId },
 
+    /// A variant to set the team name and player count of the current game
+    /// session, or of the next session if none is running.
+    SessionDetails {
+        team_name: Option<String>,
+        player_count: Option<u32>,
+    },
+
     /// A special variant to close the program, unload all the data, and attempt to shut down the computer
     Shutdown,
 
+    /// A variant to start a new game session.
+    StartSession,
+
     /// A variant to change the state of the indicated status.
     StatusChange { status: ItemId, state: ItemId },
+
+    /// A variant to reverse the most recent change to the configuration.
+    Undo,
 }
 
 /// A struct to cover all web replies
@@ -334,6 +490,18 @@ pub struct WebReply {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum WebReplyData {
+    // A variant that contains the backup metrics
+    #[serde(rename_all = "camelCase")]
+    BackupMetrics(Option<BackupMetrics>),
+
+    // A variant that contains the statuses and scenes shared by all instances
+    #[serde(rename_all = "camelCase")]
+    SyncState(Vec<SyncEntry>),
+
+    // A variant that contains the differences between two configurations
+    #[serde(rename_all = "camelCase")]
+    ConfigDiff(ConfigDiff),
+
     // A variant that contains current scene and status
     #[serde(rename_all = "camelCase")]
     CurrentSceneAndStatus((ItemId, CurrentStatus)),
@@ -342,10 +510,18 @@ pub enum WebReplyData {
     #[serde(rename_all = "camelCase")]
     Event(Option<WebEvent>),
 
+    // A variant that contains records from the game log
+    #[serde(rename_all = "camelCase")]
+    GameLog(Vec<GameRecord>),
+
     // A variant that contains item detail
     #[serde(rename_all = "camelCase")]
     Item(ItemPair),
 
+    // A variant that contains the warning and error notifications
+    #[serde(rename_all = "camelCase")]
+    Notifications(Vec<Notification>),
+
     // A variant that contains an item list
     #[serde(rename_all = "camelCase")]
     Items(Vec<ItemId>),
@@ -358,6 +534,14 @@ pub enum WebReplyData {
     #[serde(rename_all = "camelCase")]
     Message(String),
 
+    // A variant that contains the problems with a batch of modifications
+    #[serde(rename_all = "camelCase")]
+    ModificationErrors(Vec<ModificationError>),
+
+    // A variant that contains a media report
+    #[serde(rename_all = "camelCase")]
+    MediaReport(MediaReport),
+
     // A variant that contains group detail
     #[serde(rename_all = "camelCase")]
     Group(Option<WebGroup>),
@@ -366,6 +550,10 @@ pub enum WebReplyData {
     #[serde(rename_all = "camelCase")]
     Parameters(ConfigParameters),
 
+    // A variant that contains the other configuration settings
+    #[serde(rename_all = "camelCase")]
+    Settings(ConfigSettings),
+
     // A variant that contains a file path
     #[serde(rename_all = "camelCase")]
     Path {
@@ -377,6 +565,18 @@ pub enum WebReplyData {
     #[serde(rename_all = "camelCase")]
     Scene(Option<WebScene>),
 
+    // A variant that contains a list of snapshots
+    #[serde(rename_all = "camelCase")]
+    Snapshots(Vec<SnapshotInfo>),
+
+    // A variant that contains a summary of the game sessions
+    #[serde(rename_all = "camelCase")]
+    Sessions(Vec<SessionSummary>),
+
+    // A variant that contains the room statistics
+    #[serde(rename_all = "camelCase")]
+    Statistics(RoomStatistics),
+
     // A variant that contains status detail
     #[serde(rename_all = "camelCase")]
     Status(Option<Status>),
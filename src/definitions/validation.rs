@@ -0,0 +1,135 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements structures to report the problems found when
+//! validating a configuration.
+
+// Import crate definitions
+use crate::definitions::*;
+
+/// An enum to describe how serious a configuration problem is
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    Error,   // the configuration will not run correctly
+    Warning, // the configuration will run, but may not behave as intended
+}
+
+/// A struct to describe a single problem with the configuration
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    pub severity: Severity,   // how serious the problem is
+    pub item: Option<ItemId>, // the item with the problem, if any
+    pub message: String,      // a description of the problem
+}
+
+/// A struct to describe a problem caused by one modification in a batch of
+/// modifications to the configuration
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModificationError {
+    pub index: usize,         // the position of the modification in the batch
+    pub item: Option<ItemId>, // the item with the problem, if any
+    pub message: String,      // a description of the problem
+}
+
+/// A struct to hold every problem found with a configuration
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    pub file: String,                 // the configuration file that was checked
+    pub issues: Vec<ValidationIssue>, // the problems with the configuration
+}
+
+// Implement key features of the validation report
+impl ValidationReport {
+    /// A function to create a new, empty report for the file
+    ///
+    pub fn new(file: &str) -> Self {
+        Self {
+            file: file.into(),
+            issues: Vec::new(),
+        }
+    }
+
+    /// A method to add an error to the report
+    ///
+    pub fn error(&mut self, item: Option<ItemId>, message: String) {
+        self.issues.push(ValidationIssue {
+            severity: Severity::Error,
+            item,
+            message,
+        });
+    }
+
+    /// A method to add a warning to the report
+    ///
+    pub fn warning(&mut self, item: Option<ItemId>, message: String) {
+        self.issues.push(ValidationIssue {
+            severity: Severity::Warning,
+            item,
+            message,
+        });
+    }
+
+    /// A method to count the issues with the provided severity
+    ///
+    pub fn count(&self, severity: Severity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .count()
+    }
+
+    /// A method to check if the report contains any errors
+    ///
+    pub fn has_errors(&self) -> bool {
+        self.count(Severity::Error) > 0
+    }
+
+    /// A method to describe the report as human-readable text, with one line
+    /// per issue and a summary at the end
+    ///
+    pub fn to_text(&self) -> String {
+        // Describe each issue
+        let mut text = String::new();
+        for issue in self.issues.iter() {
+            let severity = match issue.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            match issue.item {
+                Some(item) => text += &format!("{} [{}]: {}\n", severity, item, issue.message),
+                None => text += &format!("{}: {}\n", severity, issue.message),
+            }
+        }
+
+        // Add the summary
+        text += &format!(
+            "{}: {} error(s), {} warning(s)\n",
+            self.file,
+            self.count(Severity::Error),
+            self.count(Severity::Warning)
+        );
+        text
+    }
+}
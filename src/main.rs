@@ -24,6 +24,7 @@
 #[macro_use]
 mod definitions;
 mod item_index;
+mod notification_log;
 mod style_sheet;
 mod system_interface;
 mod web_interface;
@@ -33,8 +34,12 @@ use crate::definitions::*;
 
 // Import other structures into this module
 use self::item_index::ItemIndex;
+use self::notification_log::NotificationLog;
 use self::style_sheet::StyleSheet;
-use self::system_interface::SystemInterface;
+use self::system_interface::{
+    AutosaveSettings, FailoverMode, SystemInterface, diff_files, export_cues_file,
+    import_cues_file, merge_files, validate_config,
+};
 use self::web_interface::WebInterface;
 
 // Import anyhow features
@@ -47,8 +52,11 @@ use tracing_appender;
 use tracing_subscriber::filter::{LevelFilter, filter_fn};
 use tracing_subscriber::prelude::*;
 
+// Import standard library features
+use std::time::Duration;
+
 // Import clap features
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 
 // Import single instance features
 use single_instance::SingleInstance;
@@ -60,6 +68,10 @@ use system_shutdown::shutdown;
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Arguments {
+    /// An optional command to run instead of the main program
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Relative path to a configuration file
     #[arg(short, long, default_value = DEFAULT_FILE)]
     config: String,
@@ -68,6 +80,22 @@ struct Arguments {
     #[arg(short = 'm', long, default_value = "false")]
     allow_multiple: bool,
 
+    /// Flag to reload the configuration file when it changes
+    #[arg(short = 'w', long, default_value = "false")]
+    watch_config: bool,
+
+    /// Directory to autosave changes to the configuration (no autosave if not set)
+    #[arg(long, default_value = None)]
+    autosave_dir: Option<String>,
+
+    /// Number of autosave files to keep (at least one)
+    #[arg(long, default_value = "10", value_parser = clap::value_parser!(u64).range(1..))]
+    autosave_keep: u64,
+
+    /// Time between autosaves (in seconds), if there are changes to save
+    #[arg(long, default_value = "60")]
+    autosave_interval: u64,
+
     /// Run address for the web interface
     #[arg(long, default_value = DEFAULT_RUN_ADDRESS)]
     run_addr: String,
@@ -99,6 +127,101 @@ struct Arguments {
     /// Flag to set the log level
     #[arg(short, long, default_value = DEFAULT_LOGLEVEL)]
     log_level: String,
+
+    /// Address of a standby instance to send heartbeats to (runs as the primary)
+    #[arg(long, default_value = None, conflicts_with = "standby_addr", requires = "failover_secret")]
+    heartbeat_addr: Option<String>,
+
+    /// Address to listen for heartbeats from the primary (runs as the standby)
+    #[arg(long, default_value = None, requires = "failover_secret")]
+    standby_addr: Option<String>,
+
+    /// Time without a heartbeat (in milliseconds) before the standby takes over
+    /// (the standby waits for the first heartbeat from the primary)
+    #[arg(long, default_value = "3000")]
+    failover_timeout: u64,
+
+    /// Secret shared by the primary and the standby to sign the heartbeats
+    #[arg(long, default_value = None)]
+    failover_secret: Option<String>,
+}
+
+/// The commands available in place of the main program
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check a configuration file for problems without running it
+    Validate {
+        /// Path to the configuration file to check
+        file: String,
+
+        /// Format of the report
+        #[arg(short, long, value_enum, default_value = "text")]
+        format: ReportFormat,
+    },
+
+    /// Compare two configuration files item by item
+    Diff {
+        /// Path to the first configuration file
+        from: String,
+
+        /// Path to the second configuration file
+        to: String,
+
+        /// Format of the report
+        #[arg(short, long, value_enum, default_value = "text")]
+        format: ReportFormat,
+    },
+
+    /// Merge changes from one configuration file into another
+    Merge {
+        /// Path to the configuration file to merge the changes into
+        base: String,
+
+        /// Path to the configuration file with the changes
+        other: String,
+
+        /// Path to write the merged configuration file
+        #[arg(short, long)]
+        output: String,
+
+        /// Id of an item to merge the changes of (may be repeated, merges every change if no items or parameters are selected)
+        #[arg(short, long = "item")]
+        items: Vec<u32>,
+
+        /// Flag to merge the changes to the configuration parameters and other settings
+        #[arg(short, long, default_value = "false")]
+        parameters: bool,
+    },
+
+    /// Import a cue list (CSV) into a configuration file
+    ImportCues {
+        /// Path to the configuration file to import the cues into
+        config: String,
+
+        /// Path to the cue list
+        cues: String,
+
+        /// Path to write the resulting configuration file
+        #[arg(short, long)]
+        output: String,
+    },
+
+    /// Export the items and events of a configuration file to a cue list (CSV)
+    ExportCues {
+        /// Path to the configuration file to export
+        config: String,
+
+        /// Path to write the cue list
+        #[arg(short, long)]
+        output: String,
+    },
+}
+
+/// The formats available for reports
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ReportFormat {
+    Text,
+    Json,
 }
 
 /// The Minerva structure to contain the program launching and overall
@@ -110,7 +233,10 @@ struct Minerva;
 impl Minerva {
     /// A function to setup the logging configuration
     ///
-    fn setup_logging(log_string: String) -> tracing_appender::non_blocking::WorkerGuard {
+    fn setup_logging(
+        log_string: String,
+        notification_log: NotificationLog,
+    ) -> tracing_appender::non_blocking::WorkerGuard {
         // Try to convert the string to a log level
         let log_level = match log_string.as_str() {
             "Trace" => LevelFilter::TRACE,
@@ -133,45 +259,167 @@ impl Minerva {
             .with_target(false)
             .with_filter(log_level);
 
-        // Create a user interface layer FIXME collect warning and error messages to display to the user
-        //let buffer = Mutex::new(Writer::new(Arc::new(Vec::new()))); //Arc::new()
-        //let buf_writer = buffer.clone().make_writer();
-        //let user_layer = tracing_subscriber::fmt::layer().with_writer(buffer).with_ansi(false).with_target(false).with_filter(log_level);
-
-        // Create the log file
-        let file_appender = tracing_appender::rolling::daily(LOG_FOLDER, GAME_LOG);
+        // Create the error log file
+        let file_appender = tracing_appender::rolling::daily(LOG_FOLDER, ERROR_LOG);
         let (non_blocking, file_guard) = tracing_appender::non_blocking(file_appender);
 
-        // Create the log file filter
-        let file_filter = {
-            filter_fn(|metadata| metadata.target() == GAME_LOG || metadata.level() == &Level::ERROR)
-        };
+        // Create the error log file filter
+        let file_filter = { filter_fn(|metadata| metadata.level() == &Level::ERROR) };
 
-        // Create the log file layer
+        // Create the error log file layer
         let file_layer = tracing_subscriber::fmt::layer()
             .with_writer(non_blocking)
             .with_ansi(false)
             .with_target(false)
             .with_filter(file_filter);
 
-        // Initialize tracing
-        tracing_subscriber::registry()
+        // Initialize tracing, with the tokio console if enabled
+        let registry = tracing_subscriber::registry()
             .with(stdout_layer)
             .with(file_layer)
-            .init();
+            .with(notification_log);
+        #[cfg(feature = "tokio_console")]
+        registry.with(console_subscriber::spawn()).init();
+        #[cfg(not(feature = "tokio_console"))]
+        registry.init();
 
         // Return and file guard
         file_guard
     }
 
+    /// A function to validate a configuration file and print the report
+    ///
+    /// This function returns true if the configuration has no errors.
+    ///
+    fn validate(file: &str, format: ReportFormat) -> bool {
+        // Try to read the configuration and validate it
+        let report = match std::fs::read_to_string(file) {
+            Ok(config_string) => validate_config(file, &config_string),
+
+            // Otherwise, report that the file could not be read
+            Err(error) => {
+                let mut report = ValidationReport::new(file);
+                report.error(
+                    None,
+                    format!("Unable to read configuration file: {}", error),
+                );
+                report
+            }
+        };
+
+        // Print the report in the requested format
+        match format {
+            ReportFormat::Text => print!("{}", report.to_text()),
+            ReportFormat::Json => match serde_json::to_string_pretty(&report) {
+                Ok(json) => println!("{}", json),
+                Err(error) => eprintln!("Unable to format report: {}", error),
+            },
+        }
+
+        // Indicate whether the configuration is valid
+        !report.has_errors()
+    }
+
+    /// A function to compare two configuration files and print the differences
+    ///
+    /// This function returns true if the configurations are the same.
+    ///
+    fn diff(from: &str, to: &str, format: ReportFormat) -> bool {
+        // Try to compare the configurations
+        let diff = match diff_files(from, to) {
+            Ok(diff) => diff,
+            Err(error) => {
+                eprintln!("Unable to compare configurations: {}", error);
+                std::process::exit(2);
+            }
+        };
+
+        // Print the differences in the requested format
+        match format {
+            ReportFormat::Text => print!("{}", diff.to_text()),
+            ReportFormat::Json => match serde_json::to_string_pretty(&diff) {
+                Ok(json) => println!("{}", json),
+                Err(error) => eprintln!("Unable to format report: {}", error),
+            },
+        }
+
+        // Indicate whether the configurations are the same
+        !diff.has_changes()
+    }
+
+    /// A function to merge the selected changes from one configuration file
+    /// into another and write the result
+    ///
+    /// This function returns true if the merge was successful.
+    ///
+    fn merge(base: &str, other: &str, output: &str, items: Vec<u32>, parameters: bool) -> bool {
+        // Select every change if nothing was selected
+        let select_all = items.is_empty() && !parameters;
+        let select = |change: &ConfigChange| match change.item {
+            _ if select_all => true,
+            Some(item) => items.contains(&item.id()),
+            None => parameters,
+        };
+
+        // Try to merge the configurations
+        match merge_files(base, other, output, select) {
+            Ok(changes) => {
+                println!("Merged {} change(s) into {}.", changes.len(), output);
+                true
+            }
+            Err(error) => {
+                eprintln!("Unable to merge configurations: {}", error);
+                false
+            }
+        }
+    }
+
+    /// A function to import a cue list into a configuration file and write
+    /// the result
+    ///
+    /// This function returns true if the import was successful.
+    ///
+    fn import_cues(config: &str, cues: &str, output: &str) -> bool {
+        match import_cues_file(config, cues, output) {
+            Ok(count) => {
+                println!("Imported {} cue(s) into {}.", count, output);
+                true
+            }
+            Err(error) => {
+                eprintln!("Unable to import cues: {}", error);
+                false
+            }
+        }
+    }
+
+    /// A function to export the items and events of a configuration file to
+    /// a cue list
+    ///
+    /// This function returns true if the export was successful.
+    ///
+    fn export_cues(config: &str, output: &str) -> bool {
+        match export_cues_file(config, output) {
+            Ok(count) => {
+                println!("Exported {} cue(s) to {}.", count, output);
+                true
+            }
+            Err(error) => {
+                eprintln!("Unable to export cues: {}", error);
+                false
+            }
+        }
+    }
+
     /// A function to build the main program and the user interface
     ///
     /// This function returns true if the user requested the computer to shut down.
     ///
     async fn run(arguments: Arguments) -> bool {
+        // Create the notification log to collect warnings and errors for the user
+        let (notification_log, notification_receive) = NotificationLog::new();
+
         // Initialize logging (guard is held until the end of run())
-        #[cfg(not(feature = "tokio_console"))]
-        let _guard = Minerva::setup_logging(arguments.log_level);
+        let _guard = Minerva::setup_logging(arguments.log_level, notification_log.clone());
 
         // Create the item index to process item description requests
         let (mut item_index, index_access) = ItemIndex::new();
@@ -195,6 +443,35 @@ impl Minerva {
         // Create the limited send
         let (limited_send, limited_recv) = LimitedSend::new();
 
+        // Send the warnings and errors to the user interface in a new thread
+        tokio::spawn(
+            notification_log
+                .clone()
+                .forward_updates(notification_receive, interface_send.clone()),
+        );
+
+        // Select the failover mode, if specified
+        let secret = arguments.failover_secret.unwrap_or_default();
+        let failover_mode = match (arguments.heartbeat_addr, arguments.standby_addr) {
+            (Some(standby_address), _) => Some(FailoverMode::Primary {
+                standby_address,
+                secret,
+            }),
+            (_, Some(listen_address)) => Some(FailoverMode::Standby {
+                listen_address,
+                timeout: Duration::from_millis(arguments.failover_timeout),
+                secret,
+            }),
+            _ => None,
+        };
+
+        // Collect the autosave settings, if specified
+        let autosave_settings = arguments.autosave_dir.map(|directory| AutosaveSettings {
+            directory: directory.into(),
+            keep: arguments.autosave_keep as usize,
+            period: Duration::from_secs(arguments.autosave_interval),
+        });
+
         // Launch the system interface to monitor and handle events
         let (system_interface, web_send) = SystemInterface::new(
             index_access.clone(),
@@ -202,6 +479,10 @@ impl Minerva {
             interface_send.clone(),
             limited_send.clone(),
             arguments.config,
+            failover_mode,
+            arguments.watch_config,
+            autosave_settings,
+            arguments.run_addr.clone(),
         )
         .await;
 
@@ -209,6 +490,7 @@ impl Minerva {
         WebInterface::launch(
             index_access,
             style_access,
+            notification_log,
             web_send,
             interface_recv,
             limited_recv,
@@ -234,9 +516,47 @@ async fn main() {
     // Get the commandline arguments
     let arguments = Arguments::parse();
 
-    // Start the console subscriber
-    #[cfg(feature = "tokio_console")]
-    console_subscriber::init();
+    // Run the command instead, if specified
+    if let Some(command) = arguments.command {
+        match command {
+            Command::Validate { file, format } => {
+                if !Minerva::validate(&file, format) {
+                    std::process::exit(1);
+                }
+            }
+            Command::Diff { from, to, format } => {
+                if !Minerva::diff(&from, &to, format) {
+                    std::process::exit(1);
+                }
+            }
+            Command::Merge {
+                base,
+                other,
+                output,
+                items,
+                parameters,
+            } => {
+                if !Minerva::merge(&base, &other, &output, items, parameters) {
+                    std::process::exit(1);
+                }
+            }
+            Command::ImportCues {
+                config,
+                cues,
+                output,
+            } => {
+                if !Minerva::import_cues(&config, &cues, &output) {
+                    std::process::exit(1);
+                }
+            }
+            Command::ExportCues { config, output } => {
+                if !Minerva::export_cues(&config, &output) {
+                    std::process::exit(1);
+                }
+            }
+        }
+        return;
+    }
 
     // Create a single instance marker
     let is_shutdown;
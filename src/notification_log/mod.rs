@@ -0,0 +1,232 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to collect the warnings and errors from the program and display
+//! them to the user. The notification log is a tracing layer that keeps the
+//! most recent warnings and errors in a ring buffer and sends the
+//! unacknowledged notifications to the user interface whenever they change.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import standard library features
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+// Import Chrono features
+use chrono::Local;
+
+// Import Tokio features
+use tokio::sync::mpsc;
+
+// Import tracing features
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// The maximum number of notifications to keep
+const MAX_NOTIFICATIONS: usize = 200;
+
+/// A structure to hold the notifications and the next notification id
+///
+struct NotificationBuffer {
+    notifications: VecDeque<Notification>, // the most recent notifications, oldest first
+    next_id: u64,                          // the id of the next notification
+}
+
+/// A structure to collect warnings and errors and provide access to them
+/// across threads
+///
+#[derive(Clone)]
+pub struct NotificationLog {
+    buffer: Arc<Mutex<NotificationBuffer>>, // the shared notifications
+    update_send: mpsc::UnboundedSender<()>, // a line to notify that the notifications changed
+}
+
+// Implement key features for the notification log
+impl NotificationLog {
+    /// A function to create a new notification log
+    ///
+    /// The function returns the notification log and a line that receives
+    /// a message whenever the notifications change. Pass this line to
+    /// forward_updates() to send the changes to the user interface.
+    ///
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<()>) {
+        // Create the update line
+        let (update_send, update_receive) = mpsc::unbounded_channel();
+
+        // Return the new notification log and receiving line
+        (
+            Self {
+                buffer: Arc::new(Mutex::new(NotificationBuffer {
+                    notifications: VecDeque::new(),
+                    next_id: 1,
+                })),
+                update_send,
+            },
+            update_receive,
+        )
+    }
+
+    /// A method to add a new notification, removing the oldest notification
+    /// if the log is full
+    ///
+    pub fn add(&self, is_error: bool, message: String) {
+        // Add the notification to the buffer
+        if let Ok(mut buffer) = self.buffer.lock() {
+            let id = buffer.next_id;
+            buffer.next_id += 1;
+            buffer.notifications.push_back(Notification {
+                id,
+                time: Local::now().naive_local(),
+                is_error,
+                message,
+                is_acknowledged: false,
+            });
+
+            // Remove the oldest notification, if full
+            if buffer.notifications.len() > MAX_NOTIFICATIONS {
+                buffer.notifications.pop_front();
+            }
+        }
+
+        // Notify that the notifications changed
+        self.update_send.send(()).unwrap_or(());
+    }
+
+    /// A method to acknowledge the notifications with the provided ids, or
+    /// every notification if no ids are provided
+    ///
+    pub fn acknowledge(&self, ids: Option<Vec<u64>>) {
+        // Mark the matching notifications
+        if let Ok(mut buffer) = self.buffer.lock() {
+            for notification in buffer.notifications.iter_mut() {
+                if ids
+                    .as_ref()
+                    .is_none_or(|ids| ids.contains(&notification.id))
+                {
+                    notification.is_acknowledged = true;
+                }
+            }
+        }
+
+        // Notify that the notifications changed
+        self.update_send.send(()).unwrap_or(());
+    }
+
+    /// A method to return a copy of every notification, oldest first
+    ///
+    pub fn get_all(&self) -> Vec<Notification> {
+        match self.buffer.lock() {
+            Ok(buffer) => buffer.notifications.iter().cloned().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// A method to return a copy of the unacknowledged notifications, oldest
+    /// first
+    ///
+    pub fn get_active(&self) -> Vec<Notification> {
+        let mut notifications = self.get_all();
+        notifications.retain(|notification| !notification.is_acknowledged);
+        notifications
+    }
+
+    /// A method to send the unacknowledged notifications to the user interface
+    /// each time the notifications change. This method runs indefinitely.
+    ///
+    pub async fn forward_updates(
+        self,
+        mut update_receive: mpsc::UnboundedReceiver<()>,
+        interface_send: InterfaceSend,
+    ) {
+        // Wait for each change
+        while update_receive.recv().await.is_some() {
+            // Skip any changes that arrived in the meantime
+            while update_receive.try_recv().is_ok() {}
+
+            // Send the current notifications
+            interface_send
+                .send(InterfaceUpdate::UpdateNotifications {
+                    notifications: self.get_active(),
+                })
+                .await;
+        }
+    }
+}
+
+/// A helper structure to collect the message of a tracing event
+///
+struct MessageVisitor {
+    message: String, // the message of the event
+}
+
+// Implement the visitor to collect only the message field
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        }
+    }
+}
+
+// Implement the tracing layer for the notification log
+impl<S: Subscriber> Layer<S> for NotificationLog {
+    /// This method collects the warnings and errors from this program
+    ///
+    fn on_event(&self, event: &Event<'_>, _context: Context<'_, S>) {
+        // Ignore anything less than a warning and messages from other crates
+        let metadata = event.metadata();
+        if *metadata.level() > Level::WARN || !metadata.target().starts_with("minerva") {
+            return;
+        }
+
+        // Collect the message and add the notification
+        let mut visitor = MessageVisitor {
+            message: String::new(),
+        };
+        event.record(&mut visitor);
+        self.add(*metadata.level() == Level::ERROR, visitor.message);
+    }
+}
+
+// Tests of the notification log module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test adding, limiting, and acknowledging notifications
+    #[test]
+    fn acknowledge_notifications() {
+        // Fill the notification log past the limit
+        let (log, _update_receive) = NotificationLog::new();
+        for count in 0..(MAX_NOTIFICATIONS + 5) {
+            log.add(count % 2 == 0, format!("Problem {}", count));
+        }
+        let all = log.get_all();
+        assert_eq!(MAX_NOTIFICATIONS, all.len());
+        assert_eq!("Problem 5", all[0].message);
+
+        // Acknowledge one notification, then the rest
+        log.acknowledge(Some(vec![all[0].id]));
+        assert_eq!(MAX_NOTIFICATIONS - 1, log.get_active().len());
+        log.acknowledge(None);
+        assert!(log.get_active().is_empty());
+        assert_eq!(MAX_NOTIFICATIONS, log.get_all().len());
+    }
+}
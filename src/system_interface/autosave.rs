@@ -0,0 +1,282 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to periodically save the configuration in memory to a backup
+//! directory after it has been changed from the edit interface.
+//!
+//! Each autosave is written to a new file, named after the configuration
+//! file and the time of the save. Only the most recent autosaves are kept.
+//! If an autosave is newer than the configuration file when it is loaded,
+//! the autosave can be recovered.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import standard library features
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Import Tokio features
+use tokio::sync::mpsc;
+use tokio::time::{MissedTickBehavior, interval};
+
+// Import chrono features
+use chrono::Local;
+
+// Import anyhow features
+use anyhow::Result;
+
+// Import tracing features
+use tracing::{info, warn};
+
+/// The text between the configuration name and the time in an autosave file
+const AUTOSAVE_MARKER: &str = ".autosave.";
+
+/// The extension of an autosave file
+const AUTOSAVE_EXTENSION: &str = ".yaml";
+
+/// A struct to hold the settings for the autosave
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AutosaveSettings {
+    pub directory: PathBuf, // the directory for the autosave files
+    pub keep: usize,        // the number of autosave files to keep
+    pub period: Duration,   // the time between checks for changes to save
+}
+
+/// A structure to track changes to the configuration and to manage the
+/// autosave files
+///
+/// # Notes
+///
+/// When the autosave is dropped, the background thread is closed.
+///
+pub struct Autosave {
+    settings: AutosaveSettings, // the settings for the autosave
+    is_changed: bool,           // a flag to indicate the configuration has unsaved changes
+    _close_sender: mpsc::Sender<()>, // a line to notify the background thread to close
+                                // the line is never used, but is poisoned when dropped
+}
+
+// Implement key functionality for the autosave
+impl Autosave {
+    /// A function to create a new autosave with the provided settings. The
+    /// system interface is notified at each period to save any changes.
+    ///
+    pub fn new(settings: AutosaveSettings, internal_send: InternalSend) -> Self {
+        // Spawn the background thread
+        let (_close_sender, close_receiver) = mpsc::channel(1); // don't need space for any messages
+        tokio::spawn(Autosave::run_loop(
+            settings.period,
+            internal_send,
+            close_receiver,
+        ));
+        info!("Autosaving to directory: {}.", settings.directory.display());
+
+        // Return the new autosave
+        Self {
+            settings,
+            is_changed: false,
+            _close_sender,
+        }
+    }
+
+    /// A method to note that the configuration has changed since it was last
+    /// saved
+    ///
+    pub fn mark_changed(&mut self) {
+        self.is_changed = true;
+    }
+
+    /// A method to note that the configuration has been saved (or replaced)
+    ///
+    pub fn mark_saved(&mut self) {
+        self.is_changed = false;
+    }
+
+    /// A method to return whether the configuration has changed since it was
+    /// last saved, and to clear the flag
+    ///
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.is_changed)
+    }
+
+    /// A method to return the location for a new autosave of the provided
+    /// configuration file
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error if the autosave directory could not
+    /// be created.
+    ///
+    pub fn next_path(&self, config_path: &Path) -> Result<PathBuf> {
+        // Make sure the directory exists
+        fs::create_dir_all(&self.settings.directory)?;
+
+        // Name the file after the configuration and the current time
+        Ok(self.settings.directory.join(format!(
+            "{}{}{}{}",
+            Autosave::stem(config_path),
+            AUTOSAVE_MARKER,
+            Local::now().format("%Y%m%d-%H%M%S-%3f"),
+            AUTOSAVE_EXTENSION
+        )))
+    }
+
+    /// A method to remove the oldest autosaves of the provided configuration
+    /// file, keeping only the most recent ones
+    ///
+    pub fn rotate(&self, config_path: &Path) {
+        // Remove the files beyond the number to keep
+        let autosaves = self.list(config_path);
+        let extra = autosaves.len().saturating_sub(self.settings.keep);
+        for path in autosaves.iter().take(extra) {
+            if let Err(error) = fs::remove_file(path) {
+                warn!(
+                    "Unable to remove old autosave {}: {}.",
+                    path.display(),
+                    error
+                );
+            }
+        }
+    }
+
+    /// A method to return the most recent autosave of the provided
+    /// configuration file, if it is newer than the configuration file
+    ///
+    pub fn find_recovery(&self, config_path: &Path) -> Option<PathBuf> {
+        // Find the most recent autosave
+        let latest = self.list(config_path).pop()?;
+
+        // Compare the modified times, if the configuration file exists
+        let modified = |path: &Path| fs::metadata(path).and_then(|data| data.modified()).ok();
+        match (modified(&latest), modified(config_path)) {
+            (Some(autosave), Some(config)) if autosave <= config => None,
+            _ => Some(latest),
+        }
+    }
+
+    /// A helper method to list the autosaves of the provided configuration
+    /// file, oldest first
+    ///
+    fn list(&self, config_path: &Path) -> Vec<PathBuf> {
+        // Read the directory, if it exists
+        let Ok(entries) = fs::read_dir(&self.settings.directory) else {
+            return Vec::new();
+        };
+
+        // Collect the files for this configuration (the time sorts in order)
+        let prefix = format!("{}{}", Autosave::stem(config_path), AUTOSAVE_MARKER);
+        let mut autosaves: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .map(|name| name.starts_with(&prefix) && name.ends_with(AUTOSAVE_EXTENSION))
+                    .unwrap_or(false)
+            })
+            .collect();
+        autosaves.sort();
+        autosaves
+    }
+
+    /// A helper function to return the name of the configuration file,
+    /// without the extension
+    ///
+    fn stem(config_path: &Path) -> String {
+        config_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "config".into())
+    }
+
+    /// A helper function to notify the system interface at each period until
+    /// closed
+    ///
+    async fn run_loop(
+        period: Duration,
+        internal_send: InternalSend,
+        mut close_receiver: mpsc::Receiver<()>,
+    ) {
+        // Skip the first tick, which completes immediately
+        let mut check = interval(period);
+        check.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        check.tick().await;
+
+        // Notify the system interface at each period
+        loop {
+            tokio::select! {
+                // Request an autosave
+                _ = check.tick() => internal_send.send_autosave().await,
+
+                // Close the thread when the autosave is dropped
+                _ = close_receiver.recv() => return,
+            }
+        }
+    }
+}
+
+// Tests of the autosave module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that old autosaves are removed and newer ones are recovered
+    #[tokio::test]
+    async fn rotate_and_recover() {
+        // Create an empty directory and a configuration file
+        let directory = std::env::temp_dir().join("minerva_autosave_test");
+        fs::remove_dir_all(&directory).unwrap_or(());
+        fs::create_dir_all(&directory).unwrap();
+        let config_path = directory.join("show.yaml");
+        fs::write(&config_path, "version: 1\n").unwrap();
+        let (internal_send, _internal_receive) = InternalSend::new();
+        let autosave = Autosave::new(
+            AutosaveSettings {
+                directory: directory.join("autosave"),
+                keep: 2,
+                period: Duration::from_secs(60),
+            },
+            internal_send,
+        );
+
+        // Check that there is nothing to recover
+        assert_eq!(None, autosave.find_recovery(&config_path));
+
+        // Write three autosaves and keep the most recent two
+        let mut paths = Vec::new();
+        for _ in 0..3 {
+            let path = autosave.next_path(&config_path).unwrap();
+            fs::write(&path, "version: 2\n").unwrap();
+            paths.push(path);
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        autosave.rotate(&config_path);
+        assert_eq!(paths[1..].to_vec(), autosave.list(&config_path));
+
+        // Check that the most recent autosave is recovered
+        assert_eq!(Some(paths[2].clone()), autosave.find_recovery(&config_path));
+
+        // Check that an autosave older than the configuration is not recovered
+        fs::write(&config_path, "version: 3\n").unwrap();
+        assert_eq!(None, autosave.find_recovery(&config_path));
+
+        // Clean up the directory
+        fs::remove_dir_all(&directory).unwrap_or(());
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to watch the loaded configuration file (and any included files)
+//! and notify the system interface when the configuration has changed.
+//!
+//! The files are checked periodically and only a change to the contents of
+//! a file (not just the modified time) is reported.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import standard library features
+use std::path::PathBuf;
+use std::time::Duration;
+
+// Import Tokio features
+use tokio::fs;
+use tokio::sync::mpsc;
+use tokio::time::interval;
+
+// Import tracing features
+use tracing::info;
+
+/// The time between checks of the configuration file
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A structure to watch the configuration file for changes
+///
+/// # Notes
+///
+/// When the config watcher is dropped, the background thread is closed.
+///
+pub struct ConfigWatcher {
+    _close_sender: mpsc::Sender<()>, // a line to notify the background thread to close
+                                     // the line is never used, but is poisoned when dropped
+}
+
+// Implement key functionality for the config watcher
+impl ConfigWatcher {
+    /// A function to start watching the provided configuration file and
+    /// included files. The current contents of the files are used as the
+    /// starting point, so only later changes are reported.
+    ///
+    pub async fn new(path: PathBuf, includes: Vec<PathBuf>, internal_send: InternalSend) -> Self {
+        // Read the current contents of the files
+        let mut files = vec![path.clone()];
+        files.extend(includes);
+        let contents = ConfigWatcher::read_all(&files).await;
+        info!("Watching configuration file: {}.", path.display());
+
+        // Spawn the background thread
+        let (_close_sender, close_receiver) = mpsc::channel(1); // don't need space for any messages
+        tokio::spawn(ConfigWatcher::run_loop(
+            path,
+            files,
+            contents,
+            internal_send,
+            close_receiver,
+        ));
+
+        // Return the new config watcher
+        Self { _close_sender }
+    }
+
+    /// A helper function to read the contents of each file, if available
+    ///
+    async fn read_all(files: &[PathBuf]) -> Vec<Option<String>> {
+        let mut contents = Vec::new();
+        for file in files.iter() {
+            contents.push(fs::read_to_string(file).await.ok());
+        }
+        contents
+    }
+
+    /// A helper function to check the files at each interval until closed
+    ///
+    async fn run_loop(
+        path: PathBuf,
+        files: Vec<PathBuf>,
+        mut contents: Vec<Option<String>>,
+        internal_send: InternalSend,
+        mut close_receiver: mpsc::Receiver<()>,
+    ) {
+        // Check the files at each interval
+        let mut check = interval(CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                // Check the files for changes
+                _ = check.tick() => {
+                    // Ignore the files while any are missing (e.g. while an editor replaces one)
+                    let new_contents = ConfigWatcher::read_all(&files).await;
+                    if new_contents.iter().any(|content| content.is_none()) {
+                        continue;
+                    }
+
+                    // Notify the system interface if the contents changed
+                    if contents != new_contents {
+                        contents = new_contents;
+                        internal_send.send_config_changed(path.clone()).await;
+                    }
+                }
+
+                // Close the thread when the watcher is dropped
+                _ = close_receiver.recv() => return,
+            }
+        }
+    }
+}
+
+// Tests of the config watcher module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that a change to the file is reported once
+    #[tokio::test]
+    async fn watch_config() {
+        // Import libraries for testing
+        use tokio::time::timeout;
+
+        // Create the file and start watching it
+        let path = std::env::temp_dir().join("minerva_watch_config.yaml");
+        fs::write(&path, "version: 1\n").await.unwrap();
+        let (internal_send, mut internal_receive) = InternalSend::new();
+        let _watcher = ConfigWatcher::new(path.clone(), Vec::new(), internal_send).await;
+
+        // Check that the unchanged file is not reported
+        let wait = CHECK_INTERVAL + Duration::from_millis(500);
+        assert!(timeout(wait, internal_receive.recv()).await.is_err());
+
+        // Change the file and check that the change is reported
+        fs::write(&path, "version: 2\n").await.unwrap();
+        match timeout(wait, internal_receive.recv()).await {
+            Ok(Some(InternalUpdate::ConfigChanged(changed))) => assert_eq!(path, changed),
+            _ => panic!("Configuration change was not reported."),
+        }
+
+        // Clean up the file
+        fs::remove_file(&path).await.unwrap_or(());
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to record the changes made from the edit interface so that they
+//! can be undone and redone.
+//!
+//! Each batch of modifications is recorded as the batch of modifications
+//! that reverses it. Undoing a batch applies the reversing batch and records
+//! its own reverse so that the change can be redone.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import other definitions
+use super::event_handler::EventHandler;
+
+/// The most batches of modifications to remember
+const MAX_HISTORY: usize = 100;
+
+/// A structure to hold the batches of modifications that can be undone and
+/// redone
+///
+/// # Notes
+///
+/// The history is cleared when the configuration is saved or a new
+/// configuration is loaded.
+///
+#[derive(Debug, Default)]
+pub struct EditHistory {
+    undo: Vec<Vec<Modification>>, // the batches that reverse each change, most recent last
+    redo: Vec<Vec<Modification>>, // the batches that repeat each undone change, most recent last
+    is_unsaved: bool,             // a flag to indicate changes since the last save or load
+}
+
+// Implement key features of the edit history
+impl EditHistory {
+    /// A function to create a new, empty edit history
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A method to record the batch that reverses a new change. Any undone
+    /// changes can no longer be redone.
+    ///
+    pub fn record(&mut self, inverse: Vec<Modification>) {
+        // Record the batch, forgetting the oldest if necessary
+        self.undo.push(inverse);
+        if self.undo.len() > MAX_HISTORY {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+        self.is_unsaved = true;
+    }
+
+    /// A method to take the batch that reverses the most recent change, if
+    /// there is one
+    ///
+    pub fn take_undo(&mut self) -> Option<Vec<Modification>> {
+        self.undo.pop()
+    }
+
+    /// A method to take the batch that repeats the most recently undone
+    /// change, if there is one
+    ///
+    pub fn take_redo(&mut self) -> Option<Vec<Modification>> {
+        self.redo.pop()
+    }
+
+    /// A method to record the batch that repeats a change that was just
+    /// undone
+    ///
+    pub fn record_undone(&mut self, inverse: Vec<Modification>) {
+        self.redo.push(inverse);
+        self.is_unsaved = true;
+    }
+
+    /// A method to record the batch that reverses a change that was just
+    /// redone, without forgetting the other undone changes
+    ///
+    pub fn record_redone(&mut self, inverse: Vec<Modification>) {
+        self.undo.push(inverse);
+        self.is_unsaved = true;
+    }
+
+    /// A method to check if any change has been made since the configuration
+    /// was last saved or loaded
+    ///
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.is_unsaved
+    }
+
+    /// A method to forget every change
+    ///
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.is_unsaved = false;
+    }
+}
+
+/// A function to compose the modifications that reverse the provided
+/// modification, based on the current configuration. This function must be
+/// called before the modification is applied.
+///
+pub async fn inverse_of(
+    handler: &mut EventHandler,
+    index_access: &IndexAccess,
+    modification: &Modification,
+) -> Vec<Modification> {
+    match modification {
+        // Restore the previous description, or remove a new item
+        Modification::ModifyItem { item_pair } => {
+            let item_id = item_pair.get_id();
+            if index_access.is_listed(&item_id).await {
+                vec![Modification::ModifyItem {
+                    item_pair: index_access.get_pair(&item_id).await,
+                }]
+            } else {
+                vec![Modification::RemoveItem { item_id }]
+            }
+        }
+
+        // Restore the previous event, group, status, or scene
+        Modification::ModifyEvent { item_id, .. } => vec![previous_event(handler, *item_id)],
+        Modification::ModifyGroup { item_id, .. } => vec![previous_group(handler, *item_id)],
+        Modification::ModifyStatus { item_id, .. } => vec![previous_status(handler, *item_id)],
+        Modification::ModifyScene { item_id, .. } => vec![previous_scene(handler, *item_id)],
+
+        // Restore the previous parameters
+        Modification::ModifyParameters { .. } => vec![Modification::ModifyParameters {
+            parameters: ConfigParameters {
+                identifier: handler.get_identifier(),
+                server_location: handler.get_server_location(),
+                dmx_controllers: handler.get_dmx_controllers(),
+                media_players: handler.get_media_players(),
+                system_connections: handler.get_connections(),
+                background_process: handler.get_background_process(),
+                default_scene: handler.get_default_scene(),
+            },
+        }],
+
+        // Restore the previous settings
+        Modification::ModifySettings { .. } => vec![Modification::ModifySettings {
+            settings: handler.get_settings().await,
+        }],
+
+        // Restore the item, its definitions, and every scene and group that contained it
+        Modification::RemoveItem { item_id } => {
+            // Restore the item, if it was described
+            let mut inverse = Vec::new();
+            if index_access.is_listed(item_id).await {
+                inverse.push(Modification::ModifyItem {
+                    item_pair: index_access.get_pair(item_id).await,
+                });
+            }
+
+            // Restore the definitions (groups before the scenes that use them)
+            inverse.push(previous_event(handler, *item_id));
+            inverse.push(previous_status(handler, *item_id));
+            inverse.push(previous_group(handler, *item_id));
+            inverse.push(previous_scene(handler, *item_id));
+
+            // Restore every group that contained the item
+            for group_id in handler.get_groups() {
+                if group_id != *item_id
+                    && handler
+                        .get_group(&group_id)
+                        .is_some_and(|group| group.items.contains(item_id))
+                {
+                    inverse.push(previous_group(handler, group_id));
+                }
+            }
+
+            // Restore every scene that contained the item
+            for scene_id in handler.get_scenes() {
+                if scene_id != *item_id
+                    && handler
+                        .get_scene(&scene_id)
+                        .is_some_and(|scene| scene.items.contains(item_id))
+                {
+                    inverse.push(previous_scene(handler, scene_id));
+                }
+            }
+            inverse
+        }
+    }
+}
+
+/// A helper function to compose the modification that restores an event
+///
+fn previous_event(handler: &mut EventHandler, item_id: ItemId) -> Modification {
+    Modification::ModifyEvent {
+        item_id,
+        event: handler.get_event(&item_id).map(|event| event.into()),
+    }
+}
+
+/// A helper function to compose the modification that restores a group
+///
+fn previous_group(handler: &EventHandler, item_id: ItemId) -> Modification {
+    Modification::ModifyGroup {
+        item_id,
+        group: handler.get_group(&item_id).map(|group| group.into()),
+    }
+}
+
+/// A helper function to compose the modification that restores a status
+///
+fn previous_status(handler: &mut EventHandler, item_id: ItemId) -> Modification {
+    Modification::ModifyStatus {
+        item_id,
+        status: handler.get_status(&item_id),
+    }
+}
+
+/// A helper function to compose the modification that restores a scene
+///
+fn previous_scene(handler: &EventHandler, item_id: ItemId) -> Modification {
+    Modification::ModifyScene {
+        item_id,
+        scene: handler.get_scene(&item_id).map(|scene| scene.into()),
+    }
+}
+
+// Tests of the edit history module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test recording, undoing, and redoing changes
+    #[test]
+    fn undo_and_redo() {
+        // Create a helper to make a recognizable batch
+        let batch = |id| {
+            vec![Modification::RemoveItem {
+                item_id: ItemId::new_unchecked(id),
+            }]
+        };
+        let id_of = |batch: Option<Vec<Modification>>| match batch.as_deref() {
+            Some([Modification::RemoveItem { item_id }]) => Some(item_id.id()),
+            _ => None,
+        };
+
+        // Record two changes and undo the second
+        let mut history = EditHistory::new();
+        history.record(batch(1));
+        history.record(batch(2));
+        assert_eq!(Some(2), id_of(history.take_undo()));
+        history.record_undone(batch(20));
+
+        // Redo the second change
+        assert_eq!(Some(20), id_of(history.take_redo()));
+        history.record_redone(batch(2));
+        assert_eq!(None, id_of(history.take_redo()));
+
+        // Check that a new change clears the redo history
+        assert_eq!(Some(2), id_of(history.take_undo()));
+        history.record_undone(batch(20));
+        history.record(batch(3));
+        assert_eq!(None, id_of(history.take_redo()));
+        assert_eq!(Some(3), id_of(history.take_undo()));
+        assert_eq!(Some(1), id_of(history.take_undo()));
+        assert_eq!(None, id_of(history.take_undo()));
+
+        // Check that the oldest changes are forgotten
+        for id in 0..(MAX_HISTORY as u32 + 10) {
+            history.record(batch(id + 1));
+        }
+        let mut count = 0;
+        while history.take_undo().is_some() {
+            count += 1;
+        }
+        assert_eq!(MAX_HISTORY, count);
+
+        // Check that clearing the history forgets every change
+        history.record(batch(1));
+        history.clear();
+        assert!(history.take_undo().is_none());
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to play audio files on this device without Apollo media player.
+//! Each channel runs a separate command line player process.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import standard library features
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+// Import tokio elements
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::time::sleep;
+
+// Import FNV HashMap
+use fnv::FnvHashMap;
+
+// Import tracing features
+use tracing::error;
+
+/// The shortest time the loop media can play before the next loop is delayed
+const MIN_LOOP_TIME: Duration = Duration::from_secs(1);
+
+/// The longest delay before the loop media is played again
+const MAX_LOOP_DELAY: Duration = Duration::from_secs(30);
+
+/// A structure to play audio with a command line player or discard it
+///
+pub struct AudioPlayer {
+    command: Option<(String, Vec<String>)>, // the player command and arguments, or None to discard the audio
+    channels: FnvHashMap<u32, mpsc::Sender<()>>, // a line to stop the player thread for each channel
+    channel_loops: FnvHashMap<u32, String>,      // the default loop media for each channel
+    internal_send: InternalSend,                 // a line to notify the system when media completes
+}
+
+// Implement key functionality for the Audio Player structure
+impl AudioPlayer {
+    /// A function to create a new audio player. If no command is provided,
+    /// all audio is discarded.
+    ///
+    pub fn new(
+        command: Option<(String, Vec<String>)>,
+        channel_map: &ChannelMap,
+        internal_send: InternalSend,
+    ) -> Self {
+        // Collect the loop media for each channel
+        let channel_loops = channel_map
+            .iter()
+            .filter_map(|(channel, media_channel)| {
+                media_channel
+                    .loop_media
+                    .clone()
+                    .map(|media| (*channel, media))
+            })
+            .collect();
+
+        // Return the new audio player
+        Self {
+            command,
+            channels: FnvHashMap::default(),
+            channel_loops,
+            internal_send,
+        }
+    }
+
+    /// A method to play a new media cue, replacing any media on the same
+    /// channel. When the media completes, the system is notified and the
+    /// loop media (if any) is played until it is replaced. The loop media of
+    /// the cue takes priority over the loop media of the channel.
+    ///
+    /// # Notes
+    ///
+    /// If the player fails, the error is logged and the channel stops
+    /// playing. If the loop media finishes too quickly, each loop is delayed
+    /// a little longer than the last.
+    ///
+    /// Without a command, the media is reported complete immediately unless
+    /// the loop media would play (in which case the channel never completes).
+    ///
+    pub fn play(&mut self, cue: MediaCue) {
+        // Stop any media currently playing on the channel
+        self.stop(cue.channel);
+
+        // Create a line to stop the player thread
+        let (stop_send, mut stop_receive) = mpsc::channel(1);
+        self.channels.insert(cue.channel, stop_send);
+
+        // Select the loop media for the cue
+        let loop_media = cue
+            .loop_media
+            .or_else(|| self.channel_loops.get(&cue.channel).cloned());
+
+        // If there is no command, notify that the media completed immediately (unless looping)
+        let internal_send = self.internal_send.clone();
+        let (command, arguments) = match self.command.clone() {
+            Some(command) => command,
+            None => {
+                if loop_media.is_none() {
+                    tokio::spawn(async move {
+                        internal_send
+                            .send_media_complete(cue.channel, cue.uri)
+                            .await;
+                    });
+                }
+                return;
+            }
+        };
+
+        // Spawn a thread to run the player processes for this channel
+        tokio::spawn(async move {
+            // Play the media, then the loop media until stopped
+            let mut uri = cue.uri;
+            let mut loop_delay = Duration::from_secs(0);
+            loop {
+                // Note the start of the media
+                let start = Instant::now();
+
+                // Start the player process
+                let mut child = match Command::new(&command)
+                    .args(&arguments)
+                    .arg(uri.strip_prefix("file://").unwrap_or(&uri))
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .kill_on_drop(true)
+                    .spawn()
+                {
+                    Ok(child) => child,
+                    Err(error) => {
+                        error!("Unable to start audio player: {}.", error);
+                        return;
+                    }
+                };
+
+                // Wait for the media to finish or the channel to be stopped
+                tokio::select! {
+                    // The media finished
+                    result = child.wait() => {
+                        // Stop the channel if the player failed
+                        match result {
+                            Ok(status) if status.success() => (),
+                            Ok(status) => {
+                                error!("Unable to play audio {}: player {}.", uri, status);
+                                return;
+                            }
+                            Err(error) => {
+                                error!("Unable to play audio {}: {}.", uri, error);
+                                return;
+                            }
+                        }
+
+                        // Notify the system that the media is complete
+                        internal_send.send_media_complete(cue.channel, uri.clone()).await;
+                    }
+
+                    // The channel was stopped (the sender was dropped)
+                    _ = stop_receive.recv() => {
+                        // Stop the player process
+                        child.kill().await.unwrap_or(());
+                        return;
+                    }
+                }
+
+                // Continue with the loop media, if specified
+                match loop_media {
+                    Some(ref media) => uri = media.clone(),
+                    None => return,
+                }
+
+                // Delay the next loop if the media finished too quickly
+                if start.elapsed() < MIN_LOOP_TIME {
+                    loop_delay = (loop_delay * 2).clamp(MIN_LOOP_TIME, MAX_LOOP_DELAY);
+                    tokio::select! {
+                        _ = sleep(loop_delay) => (),
+                        _ = stop_receive.recv() => return,
+                    }
+                } else {
+                    loop_delay = Duration::from_secs(0);
+                }
+            }
+        });
+    }
+
+    /// A method to check if the audio player discards all audio
+    ///
+    pub fn is_discarding(&self) -> bool {
+        self.command.is_none()
+    }
+
+    /// A method to stop any media playing on the channel
+    ///
+    pub fn stop(&mut self, channel: u32) {
+        // Drop the stop line to close the thread
+        self.channels.remove(&channel);
+    }
+}
+
+// Tests of the audio player module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that discarded audio is reported as complete
+    #[tokio::test]
+    async fn null_player() {
+        // Create the audio player without a command
+        let (internal_send, mut internal_receive) = InternalSend::new();
+        let mut audio_player = AudioPlayer::new(None, &ChannelMap::default(), internal_send);
+
+        // Play the media and check for the completion
+        audio_player.play(MediaCue {
+            uri: "file:///test.mp3".into(),
+            channel: 2,
+            loop_media: None,
+            on_complete: None,
+        });
+        assert_eq!(
+            Some(InternalUpdate::MediaComplete {
+                channel: 2,
+                uri: "file:///test.mp3".into(),
+            }),
+            internal_receive.recv().await
+        );
+
+        // Play looping media and check that it never completes
+        audio_player.play(MediaCue {
+            uri: "file:///test.mp3".into(),
+            channel: 2,
+            loop_media: Some("file:///loop.mp3".into()),
+            on_complete: None,
+        });
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), internal_receive.recv())
+                .await
+                .is_err()
+        );
+    }
+}
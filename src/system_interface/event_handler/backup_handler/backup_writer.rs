@@ -0,0 +1,318 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements a dedicated writer thread for the backup backend.
+//! Writes are sent to the thread without waiting so that a slow backend never
+//! delays the event loop. Any writes that accumulate while the backend is
+//! busy are combined into a single batch, and only the latest value for each
+//! key is written.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import other definitions
+use super::BackupBackend;
+
+// Import standard library features
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// Import tokio elements
+use tokio::sync::{mpsc, oneshot};
+
+// Import tracing features
+use tracing::{error, warn};
+
+/// The batch latency above which to warn that the backup is falling behind
+const SLOW_BATCH: Duration = Duration::from_millis(250);
+
+/// An enum with the operations sent to the writer thread
+///
+enum BackupCommand {
+    /// A variant to save a value with the provided key
+    Set {
+        key: String,
+        value: String,
+        time: Instant,
+    },
+
+    /// A variant to remove the provided key
+    Delete { key: String, time: Instant },
+
+    /// A variant to read the value of the provided key
+    Get {
+        key: String,
+        reply: oneshot::Sender<Option<String>>,
+    },
+}
+
+/// A structure to hold the writes waiting to be sent to the backend
+///
+struct Batch {
+    writes: BTreeMap<String, Option<String>>, // the latest value for each key, or None to delete the key
+    updates: u64,                             // the number of updates received for this batch
+    oldest: Option<Instant>,                  // the time of the oldest update in the batch
+}
+
+// Implement key features for the batch
+impl Batch {
+    /// A function to create a new, empty batch
+    ///
+    fn new() -> Self {
+        Self {
+            writes: BTreeMap::new(),
+            updates: 0,
+            oldest: None,
+        }
+    }
+
+    /// A method to add a write to the batch, replacing any earlier write to
+    /// the same key
+    ///
+    fn add(&mut self, key: String, value: Option<String>, time: Instant) {
+        self.writes.insert(key, value);
+        self.updates += 1;
+        self.oldest.get_or_insert(time);
+    }
+
+    /// A method to write the batch to the backend and update the metrics
+    ///
+    fn flush(&mut self, backend: &mut Box<dyn BackupBackend>, metrics: &Arc<Mutex<BackupMetrics>>) {
+        // Ignore an empty batch
+        let oldest = match self.oldest.take() {
+            Some(oldest) => oldest,
+            None => return,
+        };
+
+        // Write every value to the backend at once
+        if let Err(error) = backend.commit(&self.writes) {
+            error!("Unable to backup onto backup server: {}.", error);
+        }
+
+        // Warn if the backup is falling behind
+        let latency = oldest.elapsed();
+        if latency > SLOW_BATCH {
+            warn!(
+                "Backup is falling behind: {} updates took {} ms.",
+                self.updates,
+                latency.as_millis()
+            );
+        }
+
+        // Update the metrics
+        if let Ok(mut metrics) = metrics.lock() {
+            metrics.record(self.updates, self.writes.len() as u64, latency);
+        }
+
+        // Clear the batch
+        self.writes.clear();
+        self.updates = 0;
+    }
+}
+
+/// A structure to send writes to the backup backend on a dedicated thread
+///
+/// # Notes
+///
+/// When the writer is dropped, all pending writes are completed before the
+/// thread is closed.
+///
+pub struct BackupWriter {
+    send: Option<mpsc::UnboundedSender<BackupCommand>>, // the line to send operations to the writer thread
+    thread: Option<JoinHandle<()>>,                     // the writer thread
+    metrics: Arc<Mutex<BackupMetrics>>,                 // the latency metrics for the backup
+}
+
+// Implement key features for the backup writer
+impl BackupWriter {
+    /// A function to create a new backup writer and start the writer thread
+    /// with the provided backend.
+    ///
+    pub fn new(mut backend: Box<dyn BackupBackend>) -> Self {
+        // Create the line to the writer thread and the metrics
+        let (send, mut receive) = mpsc::unbounded_channel();
+        let metrics = Arc::new(Mutex::new(BackupMetrics::default()));
+        let thread_metrics = metrics.clone();
+
+        // Spawn the writer thread
+        let thread = thread::spawn(move || {
+            // Wait for the next operation, closing when the line is dropped
+            let mut batch = Batch::new();
+            while let Some(command) = receive.blocking_recv() {
+                // Collect this operation and any others that are waiting
+                let mut next = Some(command);
+                while let Some(command) = next {
+                    match command {
+                        // Add saved values and deletions to the batch
+                        BackupCommand::Set { key, value, time } => {
+                            batch.add(key, Some(value), time)
+                        }
+                        BackupCommand::Delete { key, time } => batch.add(key, None, time),
+
+                        // Complete any pending writes before reading a value
+                        BackupCommand::Get { key, reply } => {
+                            batch.flush(&mut backend, &thread_metrics);
+                            reply.send(backend.get(&key)).unwrap_or(());
+                        }
+                    }
+
+                    // Check for another operation
+                    next = receive.try_recv().ok();
+                }
+
+                // Write the batch to the backend
+                batch.flush(&mut backend, &thread_metrics);
+            }
+        });
+
+        // Return the new writer
+        Self {
+            send: Some(send),
+            thread: Some(thread),
+            metrics,
+        }
+    }
+
+    /// A method to save a value with the provided key, without waiting for
+    /// the write to complete
+    ///
+    pub fn set(&self, key: String, value: String) {
+        self.send_command(BackupCommand::Set {
+            key,
+            value,
+            time: Instant::now(),
+        });
+    }
+
+    /// A method to remove the provided key, without waiting for the write to
+    /// complete
+    ///
+    pub fn delete(&self, key: String) {
+        self.send_command(BackupCommand::Delete {
+            key,
+            time: Instant::now(),
+        });
+    }
+
+    /// A method to read the value of the provided key after all pending writes
+    /// have completed
+    ///
+    pub async fn get(&self, key: String) -> Option<String> {
+        // Send the request to the writer thread
+        let (reply, receive) = oneshot::channel();
+        self.send_command(BackupCommand::Get { key, reply });
+
+        // Wait for the reply
+        receive.await.unwrap_or(None)
+    }
+
+    /// A method to return a copy of the current backup metrics
+    ///
+    pub fn metrics(&self) -> BackupMetrics {
+        match self.metrics.lock() {
+            Ok(metrics) => metrics.clone(),
+            Err(_) => BackupMetrics::default(),
+        }
+    }
+
+    /// A helper method to send an operation to the writer thread
+    ///
+    fn send_command(&self, command: BackupCommand) {
+        if let Some(ref send) = self.send
+            && send.send(command).is_err()
+        {
+            error!("Unable to reach the backup writer.");
+        }
+    }
+}
+
+// Implement the drop trait for the backup writer
+impl Drop for BackupWriter {
+    /// This method closes the writer thread after all pending writes are
+    /// complete.
+    ///
+    fn drop(&mut self) {
+        // Drop the line to close the thread
+        self.send.take();
+
+        // Wait for the thread to finish
+        if let Some(thread) = self.thread.take() {
+            thread.join().unwrap_or(());
+        }
+    }
+}
+
+// Tests of the backup writer module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A backend that counts the writes it receives
+    struct CountBackend {
+        data: BTreeMap<String, String>,
+        writes: Arc<Mutex<u64>>,
+    }
+
+    // Implement the backup backend for the counting backend
+    impl BackupBackend for CountBackend {
+        fn set(&mut self, key: &str, value: &str) -> anyhow::Result<()> {
+            // Slow the backend so that writes accumulate
+            thread::sleep(Duration::from_millis(20));
+            *self.writes.lock().unwrap() += 1;
+            self.data.insert(key.into(), value.into());
+            Ok(())
+        }
+
+        fn get(&mut self, key: &str) -> Option<String> {
+            self.data.get(key).cloned()
+        }
+
+        fn delete(&mut self, key: &str) {
+            self.data.remove(key);
+        }
+    }
+
+    // Test that rapid writes are coalesced and reads see the latest value
+    #[tokio::test]
+    async fn coalesce_writes() {
+        // Create the writer with the counting backend
+        let writes = Arc::new(Mutex::new(0));
+        let writer = BackupWriter::new(Box::new(CountBackend {
+            data: BTreeMap::new(),
+            writes: writes.clone(),
+        }));
+
+        // Send many updates to the same key
+        for count in 0..50 {
+            writer.set("minerva:current".into(), format!("{}", count));
+        }
+
+        // Check that the latest value was saved with fewer writes
+        assert_eq!(
+            Some("49".to_string()),
+            writer.get("minerva:current".into()).await
+        );
+        assert!(*writes.lock().unwrap() < 50);
+
+        // Check that every update was counted in the metrics
+        let metrics = writer.metrics();
+        assert_eq!(50, metrics.updates);
+        assert!(metrics.writes < 50);
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements the backup backend for a local file. The complete
+//! backup is rewritten after each batch of changes to a temporary file which
+//! then replaces the backup file, so that a crash never leaves a partial
+//! backup behind. The
+//! temporary file and the directory are synced to the disk so that the new
+//! backup also survives a power loss.
+
+// Import other definitions
+use super::BackupBackend;
+
+// Import standard library features
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+// Import anyhow features
+use anyhow::Result;
+
+/// A structure to hold the backup data and the location of the backup file
+///
+pub struct FileBackend {
+    path: PathBuf,                  // the location of the backup file
+    data: BTreeMap<String, String>, // the current backup data
+}
+
+// Implement key features for the file backend
+impl FileBackend {
+    /// A function to open the backup file at the provided location. If the
+    /// file already exists, the existing backup is loaded.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if the existing backup file cannot
+    /// be read or parsed.
+    ///
+    pub fn new(path: PathBuf) -> Result<Self> {
+        // Load the existing backup, if it exists
+        let mut data = BTreeMap::new();
+        if path.exists() {
+            data = serde_yaml::from_str(&fs::read_to_string(&path)?)?;
+        }
+
+        // Return the new backend
+        Ok(Self { path, data })
+    }
+
+    /// A helper method to write the backup data to the file
+    ///
+    fn write(&self) -> Result<()> {
+        // Write the data to a temporary file and wait for it to reach the disk
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let mut file = File::create(&temporary)?;
+        file.write_all(serde_yaml::to_string(&self.data)?.as_bytes())?;
+        file.sync_all()?;
+
+        // Replace the backup file with the temporary file
+        fs::rename(&temporary, &self.path)?;
+
+        // Sync the directory so that the replacement reaches the disk
+        #[cfg(unix)]
+        {
+            let directory = match self.path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => std::path::Path::new("."),
+            };
+            File::open(directory)?.sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// A helper method to write the backup data to the file, or to remove
+    /// the file entirely if there is no data left
+    ///
+    fn update(&self) -> Result<()> {
+        if self.data.is_empty() {
+            fs::remove_file(&self.path).unwrap_or(());
+            Ok(())
+        } else {
+            self.write()
+        }
+    }
+}
+
+// Implement the backup backend for a local file
+impl BackupBackend for FileBackend {
+    /// The method to save a value to the file
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        self.data.insert(key.into(), value.into());
+        self.write()
+    }
+
+    /// The method to read a value from the file
+    fn get(&mut self, key: &str) -> Option<String> {
+        self.data.get(key).cloned()
+    }
+
+    /// The method to remove a value from the file
+    fn delete(&mut self, key: &str) {
+        // Remove the value and update the file
+        if self.data.remove(key).is_some() {
+            self.update().unwrap_or(());
+        }
+    }
+
+    /// The method to save and remove several values with one write to the file
+    fn commit(&mut self, writes: &BTreeMap<String, Option<String>>) -> Result<()> {
+        // Update every value
+        for (key, value) in writes.iter() {
+            match value {
+                Some(value) => self.data.insert(key.clone(), value.clone()),
+                None => self.data.remove(key),
+            };
+        }
+
+        // Write the file once
+        self.update()
+    }
+}
+
+// Tests of the file backend module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test saving and reloading a backup file
+    #[test]
+    fn file_backup() {
+        // Create a backup file in the temporary directory
+        let path = std::env::temp_dir().join("minerva_file_backup_test.yaml");
+        fs::remove_file(&path).unwrap_or(());
+        let mut backend = FileBackend::new(path.clone()).unwrap();
+
+        // Save several values
+        backend.set("minerva:current", "10").unwrap();
+        backend.set("minerva:11", "12").unwrap();
+        backend.delete("minerva:11");
+
+        // Reload the backup from the file and check the values
+        let mut reloaded = FileBackend::new(path.clone()).unwrap();
+        assert_eq!(Some("10".to_string()), reloaded.get("minerva:current"));
+        assert_eq!(None, reloaded.get("minerva:11"));
+
+        // Save and remove several values at once and check the file
+        let writes = BTreeMap::from([
+            ("minerva:current".to_string(), Some("20".to_string())),
+            ("minerva:13".to_string(), Some("14".to_string())),
+            ("minerva:15".to_string(), None),
+        ]);
+        reloaded.commit(&writes).unwrap();
+        let mut reloaded = FileBackend::new(path.clone()).unwrap();
+        assert_eq!(Some("20".to_string()), reloaded.get("minerva:current"));
+        assert_eq!(Some("14".to_string()), reloaded.get("minerva:13"));
+
+        // Remove the last values and check that the file is removed
+        reloaded
+            .commit(&BTreeMap::from([
+                ("minerva:current".to_string(), None),
+                ("minerva:13".to_string(), None),
+            ]))
+            .unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements a lease in the backup server, so that only one
+//! instance of a failover pair controls the game at a time. The instance that
+//! holds the lease renews it regularly. Another instance can only take the
+//! lease once it has expired.
+//!
+//! The lease is only available with a Redis server, as a local backup file
+//! cannot be shared between instances.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import other definitions
+use super::redis_backend::RedisBackend;
+
+// Import standard library features
+use std::time::Duration;
+
+// Import tracing features
+use tracing::error;
+
+// Import anyhow features
+use anyhow::Result;
+
+/// A structure to hold the connection to the lease in the backup server
+///
+pub struct BackupLease {
+    backend: RedisBackend, // the connection to the Redis server
+    key: String,           // the key of the lease
+    owner: String,         // the random token that identifies this instance as the owner
+}
+
+// Implement key features for the backup lease
+impl BackupLease {
+    /// A function to connect to the lease for the provided identifier. The
+    /// lease is not taken until it is acquired.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if the location is a local backup
+    /// file or if it is unable to connect to the Redis server.
+    ///
+    pub fn new(identifier: Identifier, server_location: &str) -> Result<Self> {
+        // Check that the location is a Redis server
+        if server_location.starts_with("file://") {
+            return Err(anyhow!("A backup file cannot hold a failover lease"));
+        }
+
+        // Connect to the Redis server
+        Ok(Self {
+            backend: RedisBackend::new(server_location)?,
+            key: format!("minerva:{}:lease", identifier),
+            owner: format!("{:016x}", rand::random::<u64>()),
+        })
+    }
+
+    /// A method to take or renew the lease for the provided duration.
+    /// Returns true if this instance holds the lease.
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error and return false if it is unable to
+    /// reach the Redis server.
+    ///
+    pub fn acquire(&mut self, duration: Duration) -> bool {
+        match self.backend.acquire_lease(&self.key, &self.owner, duration) {
+            Ok(is_held) => is_held,
+            Err(error) => {
+                error!("Unable to reach the failover lease: {}.", error);
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,1062 @@
+// Copyright (c) 2019-21 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements the connection to a backup backend (a Redis server
+//! or a local file) to maintain a backup of the system state. This handler
+//! syncs the system status, current scene, queue, media playback, and
+//! delivered hints to the backend. This module does nothing if a backend is not connected.
+//!
+//! All writes are sent to the backend on a dedicated thread so that a slow
+//! backend does not delay the rest of the system.
+//!
+//! The handler also keeps a local copy of the game state so that operators
+//! can save and restore named snapshots of the game (e.g. "before finale").
+//! Snapshots are kept in the backend, when connected, and are not removed
+//! when the handler is closed.
+//!
+//! A Redis server can also hold the lease for a failover pair, so that only
+//! one instance controls the game at a time.
+//!
+//! WARNING: This module assumes no authorized systems/operators are compromised.
+
+// Define private submodules
+mod backup_writer;
+mod file_backend;
+mod lease;
+mod redis_backend;
+
+// Reexport the backup lease
+pub use self::lease::BackupLease;
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import other definitions
+use self::backup_writer::BackupWriter;
+use self::file_backend::FileBackend;
+use self::redis_backend::RedisBackend;
+
+// Import standard library features
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+// Import Chrono features
+use chrono::Local;
+
+// Import tracing features
+use tracing::{error, info, warn};
+
+// Import anyhow features
+use anyhow::Result;
+
+// Import FNV HashSet and HashMap
+use fnv::{FnvHashMap, FnvHashSet};
+
+/// A helper structure to hold the last update
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct LastUpdates {
+    queue_update: Duration, // the time since the last update for the queue backup
+    media_update: Duration, // the time since the last update for the media backup
+}
+
+/// A trait to store and retrieve backup data by key. Each implementation
+/// provides a different location to store the backup.
+///
+trait BackupBackend: Send {
+    /// A method to save a value with the provided key
+    fn set(&mut self, key: &str, value: &str) -> Result<()>;
+
+    /// A method to read the value of the provided key, if it exists
+    fn get(&mut self, key: &str) -> Option<String>;
+
+    /// A method to remove the provided key, ignoring any errors
+    fn delete(&mut self, key: &str);
+
+    /// A method to save (or remove, if None) several values at once. By
+    /// default, each value is saved separately.
+    fn commit(&mut self, writes: &BTreeMap<String, Option<String>>) -> Result<()> {
+        for (key, value) in writes.iter() {
+            match value {
+                Some(value) => self.set(key, value)?,
+                None => self.delete(key),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A structure which holds a reference to the backup writer (if it exists)
+/// and syncronizes local data to and from the backend.
+///
+/// # Notes
+///
+/// When created, the backup handler will attempt to connect to the requested
+/// backend. A server location that starts with "file://" is treated as the
+/// path to a local backup file. Any other location is treated as the address
+/// of a Redis server. If the backup handler cannot make the connection, the
+/// backup handler will raise an error and continue without a backup.
+///
+pub struct BackupHandler {
+    identifier: Identifier, // the identifier for this instance of the controller, if specified
+    server_location: Option<String>, // the location of the backup backend, if specified
+    connection: Option<BackupWriter>, // the writer for the backup backend, if it exists
+    last_queue_update: Instant, // the time of the last update for the queue backup
+    last_media_update: Instant, // the time of the last update for the media backup
+    current_scene: Option<ItemId>, // the current scene, once backed up
+    backup_items: FnvHashSet<ItemId>, // items currently backed up in the system
+    queued_events: Vec<QueuedEvent>, // the most recent event queue
+    media_playlist: FnvHashMap<u32, MediaPlayback>, // the most recent media cued on each channel
+    dmx_universes: FnvHashMap<u32, DmxUniverse>, // the most recent values for each dmx universe
+    hints: Vec<(ItemId, u32)>, // the number of hints delivered in each scene
+    snapshots: BTreeMap<String, GameSnapshot>, // the named snapshots of the game state
+}
+
+// Implement key features for the status handler
+impl BackupHandler {
+    /// A function to create and return a new backup handler.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if it is unable to connect to the
+    /// backup backend provided.
+    ///
+    /// Like all BackupHandler functions and methods, this function will fail
+    /// gracefully by notifying of any errors on the update line and returning
+    /// None.
+    ///
+    pub async fn new(identifier: Identifier, server_location: Option<String>) -> Self {
+        // If a server location was specified, try to connect to the backend
+        let connection = BackupHandler::connect(server_location.as_ref());
+
+        // Try to load any existing snapshots
+        let mut snapshots = BTreeMap::new();
+        if let Some(ref connection) = connection {
+            // If something was received
+            if let Some(snapshot_string) = connection
+                .get(format!("minerva:{}:snapshots", identifier))
+                .await
+            {
+                // Try to parse the snapshots
+                match serde_yaml::from_str(snapshot_string.as_str()) {
+                    Ok(existing) => snapshots = existing,
+                    Err(error) => error!("Unable to parse existing snapshots: {}.", error),
+                }
+            }
+        }
+
+        // Return the new backup handler (without a connection if the location wasn't specified or failed)
+        Self {
+            identifier,
+            server_location,
+            connection,
+            last_queue_update: Instant::now(),
+            last_media_update: Instant::now(),
+            current_scene: None,
+            backup_items: FnvHashSet::default(),
+            queued_events: Vec::new(),
+            media_playlist: FnvHashMap::default(),
+            dmx_universes: FnvHashMap::default(),
+            hints: Vec::new(),
+            snapshots,
+        }
+    }
+
+    /// A helper function to connect to the backup backend and start the
+    /// writer for the backend
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if it is unable to connect to the
+    /// backup backend provided, and return None.
+    ///
+    fn connect(server_location: Option<&String>) -> Option<BackupWriter> {
+        // If a server location was specified, try to connect to the backend
+        let mut connection: Option<Box<dyn BackupBackend>> = None;
+        if let Some(location) = server_location {
+            // If the location is a local file
+            if let Some(path) = location.strip_prefix("file://") {
+                // Try to open the backup file
+                match FileBackend::new(PathBuf::from(path)) {
+                    Ok(backend) => connection = Some(Box::new(backend)),
+                    Err(error) => error!("Unable to open backup file {}: {}.", location, error),
+                }
+
+            // Otherwise, try to connect to the Redis server
+            } else {
+                match RedisBackend::new(location) {
+                    Ok(backend) => connection = Some(Box::new(backend)),
+                    Err(_) => error!("Unable to connect to backup server: {}.", location),
+                }
+            }
+        }
+
+        // Start the writer for the backend, if connected
+        connection.map(BackupWriter::new)
+    }
+
+    /// A method to close the connection to the backup backend without
+    /// removing the backup, so that another backup handler can take over
+    /// the backup (e.g. when the configuration is reloaded).
+    ///
+    /// # Notes
+    ///
+    /// This method waits for any pending writes to complete. Once closed,
+    /// changes are no longer backed up until the connection is reopened.
+    ///
+    pub fn close(&mut self) {
+        // Wait for the writer to complete any pending writes
+        drop(self.connection.take());
+    }
+
+    /// A method to reopen the connection to the backup backend after it was
+    /// closed (e.g. if another backup handler failed to take over the
+    /// backup).
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if it is unable to connect to the
+    /// backup backend.
+    ///
+    /// Like all BackupHandler functions and methods, this function will fail
+    /// gracefully by notifying of any errors on the update line.
+    ///
+    pub fn reopen(&mut self) {
+        if self.connection.is_none() {
+            self.connection = BackupHandler::connect(self.server_location.as_ref());
+        }
+    }
+
+    /// A method to backup the current scene of the system
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if it is unable to connect to the
+    /// backup backend.
+    ///
+    /// Like all BackupHandler functions and methods, this function will fail
+    /// gracefully by notifying of any errors on the update line.
+    ///
+    pub async fn backup_current_scene(&mut self, current_scene: &ItemId) {
+        // Save a local copy of the current scene
+        self.current_scene = Some(*current_scene);
+
+        // If the backup connection exists
+        if let Some(ref connection) = self.connection {
+            // Copy the current scene to the server
+            connection.set(
+                format!("minerva:{}:current", self.identifier),
+                format!("{}", current_scene.id()),
+            );
+
+            // Backup the update times
+            self.backup_last_update();
+        }
+    }
+
+    /// A method to backup the number of hints delivered in each scene
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if it is unable to connect to the
+    /// backup backend.
+    ///
+    /// Like all BackupHandler functions and methods, this function will fail
+    /// gracefully by notifying of any errors on the update line.
+    ///
+    pub async fn backup_hints(&mut self, hints: Vec<(ItemId, u32)>) {
+        // Save a local copy of the hints
+        self.hints = hints;
+
+        // If the backup connection exists
+        if let Some(ref connection) = self.connection {
+            // Try to serialize the hints
+            let hints_string = match serde_yaml::to_string(&self.hints) {
+                Ok(string) => string,
+                Err(error) => {
+                    error!("Unable to parse delivered hints: {}.", error);
+                    return;
+                }
+            };
+
+            // Copy the hints to the server
+            connection.set(format!("minerva:{}:hints", self.identifier), hints_string);
+        }
+    }
+
+    /// A method to backup a status state on the backup server based on the
+    /// provided status id and new state.
+    ///
+    /// # Note
+    ///
+    /// As the backup handler does not hold a copy of the status map, this
+    /// method does not verify the validity of the new state in any way.
+    /// It is expected that the calling module will perform this check.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if it is unable to connect to the
+    /// backup backend.
+    ///
+    /// Like all BackupHandler functions and methods, this function will fail
+    /// gracefully by notifying of any errors on the update line.
+    ///
+    pub async fn backup_status(&mut self, status_id: &ItemId, new_state: &ItemId) {
+        // If the backup connection exists
+        if let Some(ref connection) = self.connection {
+            // Copy the state to the server
+            connection.set(
+                format!("minerva:{}:{}", self.identifier, status_id),
+                format!("{}", new_state.id()),
+            );
+
+            // Add the id to the backup items
+            self.backup_items.insert(*status_id);
+
+            // Backup the update times
+            self.backup_last_update();
+        }
+    }
+
+    /// A method to backup the event queue on the backup server based on the
+    /// provided coming events
+    ///
+    /// # Note
+    ///
+    /// As the backup handler does not hold a copy of the configuration, this
+    /// method does not verify the validity of the event queue in any way.
+    /// It is expected that the calling module will perform this check.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if it is unable to connect to the
+    /// backup backend.
+    ///
+    /// Like all BackupHandler functions and methods, this function will fail
+    /// gracefully by notifying of any errors on the update line.
+    ///
+    pub async fn backup_events(&mut self, coming_events: Vec<ComingEvent>) {
+        // Covert the coming events to queued events
+        let mut queued_events = Vec::new();
+        for event in coming_events {
+            // Convert each event to a queued event
+            if let Some(remaining) = event.remaining() {
+                queued_events.push(QueuedEvent {
+                    remaining,
+                    event_id: event.id(),
+                });
+            }
+        }
+
+        // Save a local copy of the queue and the new update time
+        self.queued_events = queued_events;
+        self.last_queue_update = Instant::now();
+
+        // If the backup connection exists
+        if let Some(ref connection) = self.connection {
+            // Try to serialize the coming events
+            let event_string = match serde_yaml::to_string(&self.queued_events) {
+                Ok(string) => string,
+                Err(error) => {
+                    error!("Unable to parse coming events: {}.", error);
+                    return;
+                }
+            };
+
+            // Copy the event to the server
+            connection.set(format!("minerva:{}:queue", self.identifier), event_string);
+
+            // Backup the update times
+            self.backup_last_update();
+        }
+    }
+
+    /// A method to backup a new media cue on the backup server. The cue
+    /// replaces any previous media on the same channel.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if it is unable to connect to the
+    /// backup backend.
+    ///
+    /// Like all BackupHandler functions and methods, this function will fail
+    /// gracefully by notifying of any errors on the update line.
+    ///
+    pub async fn backup_media(&mut self, media_cue: MediaCue) {
+        self.backup_playback(MediaPlayback {
+            media_cue,
+            time_since: Duration::from_secs(0),
+        })
+        .await;
+    }
+
+    /// A method to backup media that is already playing on the backup
+    /// server. The media replaces any previous media on the same channel.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if it is unable to connect to the
+    /// backup backend.
+    ///
+    /// Like all BackupHandler functions and methods, this function will fail
+    /// gracefully by notifying of any errors on the update line.
+    ///
+    pub async fn backup_playback(&mut self, new_playback: MediaPlayback) {
+        // Update the timing for the existing media
+        let elapsed = self.last_media_update.elapsed();
+        for playback in self.media_playlist.values_mut() {
+            playback.update(elapsed);
+        }
+
+        // Add the new media to the playlist
+        self.media_playlist
+            .insert(new_playback.media_cue.channel, new_playback);
+
+        // Save the new update time and copy the playlist to the server
+        self.last_media_update = Instant::now();
+        self.backup_media_playlist();
+    }
+
+    /// A method to remove completed media from the backup, unless the media
+    /// loops. The media is only removed if it is still the most recent media
+    /// on the channel.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if it is unable to connect to the
+    /// backup backend.
+    ///
+    /// Like all BackupHandler functions and methods, this function will fail
+    /// gracefully by notifying of any errors on the update line.
+    ///
+    pub async fn media_complete(&mut self, channel: u32, uri: &str, is_looping: bool) {
+        // Check that the media is still playing and doesn't loop
+        if is_looping
+            || !self.media_playlist.get(&channel).is_some_and(|playback| {
+                playback.media_cue.uri == uri && playback.media_cue.loop_media.is_none()
+            })
+        {
+            return;
+        }
+
+        // Update the timing for the remaining media
+        let elapsed = self.last_media_update.elapsed();
+        for playback in self.media_playlist.values_mut() {
+            playback.update(elapsed);
+        }
+
+        // Remove the media and copy the playlist to the server
+        self.media_playlist.remove(&channel);
+        self.last_media_update = Instant::now();
+        self.backup_media_playlist();
+    }
+
+    /// A helper method to copy the media playlist to the backup server
+    ///
+    fn backup_media_playlist(&mut self) {
+        // If the backup connection exists
+        if let Some(ref connection) = self.connection {
+            // Try to serialize the media playlist
+            let playlist: Vec<MediaPlayback> = self.media_playlist.values().cloned().collect();
+            let media_string = match serde_yaml::to_string(&playlist) {
+                Ok(string) => string,
+                Err(error) => {
+                    error!("Unable to parse media playlist: {}.", error);
+                    return;
+                }
+            };
+
+            // Copy the media playlist to the server
+            connection.set(format!("minerva:{}:media", self.identifier), media_string);
+
+            // Backup the update times
+            self.backup_last_update();
+        }
+    }
+
+    /// A method to record a dmx fade for future snapshots. The final value of
+    /// the fade replaces any previous value of the channel.
+    ///
+    /// # Note
+    ///
+    /// The dmx controller maintains its own backup of the dmx values, so the
+    /// values are not sent to the backup backend.
+    ///
+    pub fn backup_dmx(&mut self, fade: &DmxFade) {
+        // Update the value in the selected universe
+        self.dmx_universes
+            .entry(fade.universe.unwrap_or(0))
+            .or_insert_with(DmxUniverse::new)
+            .set(fade.channel, fade.value);
+    }
+
+    /// A method to save the current game state as a snapshot with the
+    /// provided name, replacing any existing snapshot with the same name.
+    ///
+    /// # Note
+    ///
+    /// As the backup handler does not hold a copy of the status map, the
+    /// current state of every status must be provided by the calling module.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if the current scene has not yet
+    /// been backed up.
+    ///
+    /// Like all BackupHandler functions and methods, this function will fail
+    /// gracefully by notifying of any errors on the update line.
+    ///
+    pub fn save_snapshot(&mut self, name: String, statuses: Vec<(ItemId, ItemId)>) -> Result<()> {
+        // Compose and save the snapshot
+        let snapshot = self.current_snapshot(statuses)?;
+        self.snapshots.insert(name, snapshot);
+
+        // Copy the snapshots to the server
+        self.backup_snapshots();
+        Ok(())
+    }
+
+    /// A method to compose a snapshot of the current game state, without
+    /// saving it.
+    ///
+    /// # Note
+    ///
+    /// As the backup handler does not hold a copy of the status map, the
+    /// current state of every status must be provided by the calling module.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if the current scene has not yet
+    /// been backed up.
+    ///
+    pub fn current_snapshot(&self, statuses: Vec<(ItemId, ItemId)>) -> Result<GameSnapshot> {
+        // Make sure there is a current scene
+        let current_scene = self
+            .current_scene
+            .ok_or(anyhow!("No current scene to save."))?;
+
+        // Update the timing for the event queue
+        let elapsed = self.last_queue_update.elapsed();
+        let mut queued_events = self.queued_events.clone();
+        for event in queued_events.iter_mut() {
+            event.update(elapsed);
+        }
+
+        // Update the timing for the media playlist
+        let elapsed = self.last_media_update.elapsed();
+        let mut media_playlist: Vec<MediaPlayback> =
+            self.media_playlist.values().cloned().collect();
+        for playback in media_playlist.iter_mut() {
+            playback.update(elapsed);
+        }
+
+        // Compose the snapshot
+        Ok(GameSnapshot {
+            saved: Local::now().naive_local(),
+            current_scene,
+            statuses,
+            queued_events,
+            media_playlist,
+            dmx_universes: self
+                .dmx_universes
+                .iter()
+                .map(|(universe, values)| (*universe, values.clone()))
+                .collect(),
+            hints: self.hints.clone(),
+        })
+    }
+
+    /// A method to return a copy of the snapshot with the provided name, if
+    /// it exists
+    ///
+    pub fn get_snapshot(&self, name: &str) -> Option<GameSnapshot> {
+        self.snapshots.get(name).cloned()
+    }
+
+    /// A method to return a description of each snapshot, sorted by name
+    ///
+    pub fn list_snapshots(&self) -> Vec<SnapshotInfo> {
+        self.snapshots
+            .iter()
+            .map(|(name, snapshot)| SnapshotInfo {
+                name: name.clone(),
+                saved: snapshot.saved,
+                current_scene: snapshot.current_scene,
+            })
+            .collect()
+    }
+
+    /// A method to delete the snapshot with the provided name
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if the snapshot does not exist.
+    ///
+    pub fn delete_snapshot(&mut self, name: &str) -> Result<()> {
+        // Remove the snapshot
+        self.snapshots
+            .remove(name)
+            .ok_or(anyhow!("Snapshot not found: {}.", name))?;
+
+        // Copy the snapshots to the server
+        self.backup_snapshots();
+        Ok(())
+    }
+
+    /// A method to replace the recorded dmx values with those from a snapshot
+    ///
+    pub fn restore_dmx(&mut self, dmx_universes: Vec<(u32, DmxUniverse)>) {
+        self.dmx_universes = dmx_universes.into_iter().collect();
+    }
+
+    /// A method to reload an existing backup from the backup server. If the
+    /// data exists, this function returns the existing backup data.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if it is unable to connect to the
+    /// backup backend.
+    ///
+    /// Like all BackupHandler functions and methods, this function will fail
+    /// gracefully by notifying of any errors on the update line and returning
+    /// None.
+    ///
+    pub async fn reload_backup(
+        &mut self,
+        mut status_ids: Vec<ItemId>,
+    ) -> Option<(
+        ItemId,
+        Vec<(ItemId, ItemId)>,
+        Vec<QueuedEvent>,
+        Vec<MediaPlayback>,
+        Vec<(ItemId, u32)>,
+    )> {
+        // If the backup connection exists
+        if let Some(ref connection) = self.connection {
+            // Check to see if there is an existing scene
+            let result = connection
+                .get(format!("minerva:{}:current", self.identifier))
+                .await;
+
+            // If the current scene exists
+            if let Some(current_str) = result {
+                // Warn that existing data was found
+                warn!("Detected lingering backup data. Reloading ...");
+
+                // Try to read the last update times
+                let mut last_updates = LastUpdates {
+                    queue_update: Duration::from_secs(0),
+                    media_update: Duration::from_secs(0),
+                };
+                let result = connection
+                    .get(format!("minerva:{}:lastupdate", self.identifier))
+                    .await;
+
+                // If something was received
+                if let Some(update_string) = result {
+                    // Try to parse the data
+                    if let Ok(updates) = serde_yaml::from_str(update_string.as_str()) {
+                        last_updates = updates;
+                    }
+                }
+
+                // Try to read the exising event queue
+                let mut queued_events: Vec<QueuedEvent> = Vec::new();
+                let result = connection
+                    .get(format!("minerva:{}:queue", self.identifier))
+                    .await;
+
+                // If something was received
+                if let Some(queue_string) = result {
+                    // Try to parse the queue
+                    if let Ok(events) = serde_yaml::from_str(queue_string.as_str()) {
+                        queued_events = events;
+                    }
+                }
+
+                // Try to read the existing media playlist
+                let mut media_playlist: Vec<MediaPlayback> = Vec::new();
+                let result = connection
+                    .get(format!("minerva:{}:media", self.identifier))
+                    .await;
+
+                // If something was received
+                if let Some(media_string) = result {
+                    // Try to parse the playlist
+                    if let Ok(playlist) = serde_yaml::from_str(media_string.as_str()) {
+                        media_playlist = playlist;
+                    }
+                }
+
+                // Try to read the delivered hints
+                let mut hints: Vec<(ItemId, u32)> = Vec::new();
+                let result = connection
+                    .get(format!("minerva:{}:hints", self.identifier))
+                    .await;
+
+                // If something was received
+                if let Some(hints_string) = result {
+                    // Try to parse the hints
+                    if let Ok(delivered) = serde_yaml::from_str(hints_string.as_str()) {
+                        hints = delivered;
+                    }
+                }
+                self.hints = hints.clone();
+
+                // Update the timing for the media playlist
+                info!(
+                    "Adjusting media playback by {}.{:0>3}.",
+                    last_updates.media_update.as_secs(),
+                    (last_updates.media_update.as_millis() % 1000)
+                );
+                for playback in media_playlist.iter_mut() {
+                    playback.update(last_updates.media_update);
+                }
+
+                // Keep a copy of the playlist for future backups
+                self.last_media_update = Instant::now();
+                self.media_playlist = media_playlist
+                    .iter()
+                    .map(|playback| (playback.media_cue.channel, playback.clone()))
+                    .collect();
+
+                // Update the timing for the event queue
+                info!(
+                    "Adjusting event queue by {}.{:0>3}.",
+                    last_updates.queue_update.as_secs(),
+                    (last_updates.queue_update.as_millis() % 1000)
+                );
+                if !queued_events.is_empty() {
+                    for event in queued_events.iter_mut() {
+                        event.update(last_updates.queue_update);
+                    }
+                }
+
+                // Keep a copy of the queue for future snapshots
+                self.last_queue_update = Instant::now();
+                self.queued_events = queued_events.clone();
+
+                // Compile a list of valid status pairs
+                let mut status_pairs: Vec<(ItemId, ItemId)> = Vec::new();
+                for status_id in status_ids.drain(..) {
+                    // Try to read an existing status from the backup
+                    let result = connection
+                        .get(format!("minerva:{}:{}", self.identifier, status_id))
+                        .await;
+
+                    // If something was received
+                    if let Some(state_str) = result {
+                        // Try to parse the current state id
+                        if let Ok(state_id) = state_str.parse::<u32>() {
+                            // Try to compose the id into an item
+                            if let Some(new_state) = ItemId::new(state_id) {
+                                // Add the status id and new state to the status pairs
+                                status_pairs.push((status_id, new_state));
+                            }
+                        }
+                    }
+                }
+
+                // Try to parse the current scene id
+                if let Ok(current_id) = current_str.parse::<u32>() {
+                    // Try to compose the id into an item
+                    if let Some(current_scene) = ItemId::new(current_id) {
+                        // Keep a copy of the current scene
+                        self.current_scene = Some(current_scene);
+
+                        // Return the current scene, status pairs, queue, media, and hints
+                        return Some((
+                            current_scene,
+                            status_pairs,
+                            queued_events,
+                            media_playlist,
+                            hints,
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Silently return nothing if the connection does not exist or there was not a current scene
+        None
+    }
+
+    /// A helper function to backup the last update times for the queue and media
+    ///
+    fn backup_last_update(&self) {
+        // Create the last updates structure
+        let last_updates = LastUpdates {
+            queue_update: self.last_queue_update.elapsed(),
+            media_update: self.last_media_update.elapsed(),
+        };
+
+        // Try to serialize the update times
+        let update_string = match serde_yaml::to_string(&last_updates) {
+            Ok(string) => string,
+            Err(error) => {
+                error!("Unable to parse update times {}.", error);
+                return;
+            }
+        };
+
+        // Copy the data to the server
+        if let Some(ref connection) = self.connection {
+            connection.set(
+                format!("minerva:{}:lastupdate", self.identifier),
+                update_string,
+            );
+        }
+    }
+
+    /// A helper function to backup the named snapshots
+    ///
+    fn backup_snapshots(&self) {
+        // If the backup connection exists
+        if let Some(ref connection) = self.connection {
+            // Try to serialize the snapshots
+            let snapshot_string = match serde_yaml::to_string(&self.snapshots) {
+                Ok(string) => string,
+                Err(error) => {
+                    error!("Unable to parse snapshots: {}.", error);
+                    return;
+                }
+            };
+
+            // Copy the snapshots to the server
+            connection.set(
+                format!("minerva:{}:snapshots", self.identifier),
+                snapshot_string,
+            );
+        }
+    }
+
+    /// A method to return the latency metrics for the backup, if a backup
+    /// backend is connected
+    ///
+    pub fn get_metrics(&self) -> Option<BackupMetrics> {
+        self.connection
+            .as_ref()
+            .map(|connection| connection.metrics())
+    }
+}
+
+// Implement the drop trait for the backup handler struct.
+impl Drop for BackupHandler {
+    /// This method removes all the the existing statuses from the status server.
+    ///
+    /// # Errors
+    ///
+    /// This method will ignore any errors as it is called only when the backup
+    /// connection is being closed.
+    ///
+    fn drop(&mut self) {
+        // If the backup connection exists
+        if let Some(connection) = self.connection.take() {
+            // Try to delete the current scene if it exists
+            connection.delete(format!("minerva:{}:current", self.identifier));
+
+            // Try to delete the last update backup if it exists
+            connection.delete(format!("minerva:{}:lastupdate", self.identifier));
+
+            // Try to delete the queue if it exists
+            connection.delete(format!("minerva:{}:queue", self.identifier));
+
+            // Try to delete the media playlist if it exists
+            connection.delete(format!("minerva:{}:media", self.identifier));
+
+            // Try to delete the delivered hints if they exist
+            connection.delete(format!("minerva:{}:hints", self.identifier));
+
+            // Try to delete all the items that were backed up
+            for item in self.backup_items.drain() {
+                connection.delete(format!("minerva:{}:{}", self.identifier, item));
+            }
+
+            // Wait for the writer to complete the deletions
+            drop(connection);
+        }
+    }
+}
+
+// Tests of the status module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test the backup module
+    #[tokio::test]
+    async fn backup_game() {
+        // Import libraries for testing
+        use crate::definitions::Identifier;
+
+        // Create the backup handler
+        let mut backup_handler = BackupHandler::new(
+            Identifier { id: None },
+            Some("redis://127.0.0.1:6379".into()),
+        )
+        .await;
+
+        // Make sure there is no existing backup
+        if backup_handler.reload_backup(Vec::new()).await.is_some() {
+            panic!("Backup already existed before beginning of the test.");
+        }
+
+        // Create the current scene and status pairs
+        let current_scene = ItemId::new_unchecked(10);
+        let status1 = ItemId::new_unchecked(11);
+        let state1 = ItemId::new_unchecked(12);
+        let status2 = ItemId::new_unchecked(13);
+        let state2 = ItemId::new_unchecked(14);
+
+        // Create the media cue
+        let media_cue = MediaCue {
+            uri: "file:///test.mp4".into(),
+            channel: 1,
+            loop_media: None,
+            on_complete: None,
+        };
+
+        // Backup the current scene, statuses, media (unable to easily test coming events)
+        backup_handler.backup_current_scene(&current_scene).await;
+        backup_handler.backup_status(&status1, &state1).await;
+        backup_handler.backup_status(&status2, &state2).await;
+        backup_handler.backup_media(media_cue.clone()).await;
+        backup_handler.backup_hints(vec![(current_scene, 2)]).await;
+
+        // Reload the backup
+        if let Some((reload_scene, statuses, _queue, media, hints)) =
+            backup_handler.reload_backup(vec![status1, status2]).await
+        {
+            assert_eq!(current_scene, reload_scene);
+            assert_eq!(vec!((status1, state1), (status2, state2)), statuses);
+            assert_eq!(1, media.len());
+            assert_eq!(media_cue, media[0].media_cue);
+            assert_eq!(vec!((current_scene, 2)), hints);
+
+        // If the backup doesn't exist, throw the error
+        } else {
+            panic!("Backup was not reloaded.");
+        }
+    }
+
+    // Test saving, restoring, and deleting snapshots without a backend
+    #[tokio::test]
+    async fn game_snapshots() {
+        // Create the backup handler without a backend
+        let mut backup_handler = BackupHandler::new(Identifier { id: None }, None).await;
+
+        // Make sure a snapshot cannot be saved before there is a scene
+        assert!(
+            backup_handler
+                .save_snapshot("empty".into(), Vec::new())
+                .is_err()
+        );
+
+        // Backup the current scene and a dmx fade
+        let current_scene = ItemId::new_unchecked(10);
+        let status = ItemId::new_unchecked(11);
+        let state = ItemId::new_unchecked(12);
+        backup_handler.backup_current_scene(&current_scene).await;
+        backup_handler.backup_dmx(&DmxFade {
+            universe: None,
+            channel: 5,
+            value: 200,
+            duration: None,
+        });
+
+        // Backup media on two channels and complete both (only one loops)
+        for (channel, loop_media) in [(1, None), (2, Some("loop.mp3".to_string()))] {
+            backup_handler
+                .backup_media(MediaCue {
+                    uri: "media.mp3".into(),
+                    channel,
+                    loop_media,
+                    on_complete: None,
+                })
+                .await;
+            backup_handler
+                .media_complete(channel, "media.mp3", false)
+                .await;
+        }
+
+        // Backup the hints delivered in the current scene
+        backup_handler.backup_hints(vec![(current_scene, 1)]).await;
+
+        // Save the snapshot and change the scene
+        backup_handler
+            .save_snapshot("before finale".into(), vec![(status, state)])
+            .unwrap();
+        backup_handler
+            .backup_current_scene(&ItemId::new_unchecked(13))
+            .await;
+
+        // Check the saved snapshot
+        let snapshot = backup_handler.get_snapshot("before finale").unwrap();
+        assert_eq!(current_scene, snapshot.current_scene);
+        assert_eq!(vec!((status, state)), snapshot.statuses);
+        assert_eq!(200, snapshot.dmx_universes[0].1.get(5));
+        assert_eq!(1, snapshot.media_playlist.len());
+        assert_eq!(2, snapshot.media_playlist[0].media_cue.channel);
+        assert_eq!(vec!((current_scene, 1)), snapshot.hints);
+        assert_eq!(1, backup_handler.list_snapshots().len());
+
+        // Delete the snapshot
+        backup_handler.delete_snapshot("before finale").unwrap();
+        assert!(backup_handler.delete_snapshot("before finale").is_err());
+        assert!(backup_handler.list_snapshots().is_empty());
+    }
+
+    // Test that a new backup handler can take over the backup file from a
+    // closed backup handler (as when the configuration is reloaded)
+    #[tokio::test]
+    async fn take_over_backup() {
+        // Create the first backup handler and backup the game
+        let path = std::env::temp_dir().join("minerva_take_over_backup.yaml");
+        std::fs::remove_file(&path).unwrap_or(());
+        let location = Some(format!("file://{}", path.display()));
+        let mut first = BackupHandler::new(Identifier { id: None }, location.clone()).await;
+        let current_scene = ItemId::new_unchecked(10);
+        let status = ItemId::new_unchecked(11);
+        let state = ItemId::new_unchecked(12);
+        first.backup_current_scene(&current_scene).await;
+        first.backup_status(&status, &state).await;
+
+        // Close the first handler and resume the game in a second handler
+        first.close();
+        let mut second = BackupHandler::new(Identifier { id: None }, location.clone()).await;
+        second.backup_current_scene(&current_scene).await;
+        second.backup_status(&status, &state).await;
+
+        // Check that dropping the first handler keeps the backup
+        drop(first);
+        second.close();
+        let mut third = BackupHandler::new(Identifier { id: None }, location).await;
+        let (reload_scene, statuses, ..) = third.reload_backup(vec![status]).await.unwrap();
+        assert_eq!(current_scene, reload_scene);
+        assert_eq!(vec!((status, state)), statuses);
+
+        // Check that the second handler can reopen the backup
+        third.close();
+        second.reopen();
+        assert!(second.reload_backup(vec![status]).await.is_some());
+
+        // Clean up the backup file
+        drop(second);
+        std::fs::remove_file(&path).unwrap_or(());
+    }
+}
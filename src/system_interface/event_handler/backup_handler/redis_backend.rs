@@ -0,0 +1,136 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements the backup backend for a Redis server.
+
+// Import other definitions
+use super::BackupBackend;
+
+// Import tracing features
+use tracing::error;
+
+// Import redis client library
+use redis::{Commands, ConnectionLike, RedisResult};
+
+// Import standard library features
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+// Import anyhow features
+use anyhow::Result;
+
+/// A script to take or renew a lease, if it is free or already held by the
+/// owner (the owner is the first argument and the duration in milliseconds is
+/// the second argument)
+const LEASE_SCRIPT: &str = r"
+local owner = redis.call('GET', KEYS[1])
+if owner == false or owner == ARGV[1] then
+    redis.call('SET', KEYS[1], ARGV[1], 'PX', ARGV[2])
+    return 1
+end
+return 0
+";
+
+/// A structure to hold the connection to the Redis server
+///
+pub struct RedisBackend {
+    connection: redis::Connection, // the Redis connection
+}
+
+// Implement key features for the Redis backend
+impl RedisBackend {
+    /// A function to connect to the Redis server at the provided location
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if it is unable to connect to the
+    /// Redis server.
+    ///
+    pub fn new(location: &str) -> Result<Self> {
+        // Try to connect to the Redis server
+        let client = redis::Client::open(location)?;
+        let mut connection = client.get_connection()?;
+
+        // Set the snapshot settings
+        let result: RedisResult<redis::Value> = connection.req_command(
+            redis::Cmd::new()
+                .arg("CONFIG")
+                .arg("SET")
+                .arg("save")
+                .arg("60 1"),
+        );
+
+        // Unpack the result from the operation
+        if result.is_err() {
+            // Warn that it wasn't possible to update the snapshot settings
+            error!("Unable to set Redis snapshot settings.");
+        }
+
+        // Return the new backend
+        Ok(Self { connection })
+    }
+
+    /// A method to take or renew the lease with the provided key for the
+    /// provided duration. Returns true if the owner holds the lease.
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error if it is unable to reach the Redis
+    /// server.
+    ///
+    pub fn acquire_lease(&mut self, key: &str, owner: &str, duration: Duration) -> Result<bool> {
+        let is_held: bool = redis::Script::new(LEASE_SCRIPT)
+            .key(key)
+            .arg(owner)
+            .arg(duration.as_millis() as u64)
+            .invoke(&mut self.connection)?;
+        Ok(is_held)
+    }
+}
+
+// Implement the backup backend for Redis
+impl BackupBackend for RedisBackend {
+    /// The method to save a value to the server
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let _: bool = self.connection.set(key, value)?;
+        Ok(())
+    }
+
+    /// The method to read a value from the server
+    fn get(&mut self, key: &str) -> Option<String> {
+        self.connection.get(key).ok()
+    }
+
+    /// The method to remove a value from the server
+    fn delete(&mut self, key: &str) {
+        let _: RedisResult<bool> = self.connection.del(key);
+    }
+
+    /// The method to save and remove several values in one transaction
+    fn commit(&mut self, writes: &BTreeMap<String, Option<String>>) -> Result<()> {
+        let mut pipeline = redis::pipe();
+        pipeline.atomic();
+        for (key, value) in writes.iter() {
+            match value {
+                Some(value) => pipeline.set(key, value).ignore(),
+                None => pipeline.del(key).ignore(),
+            };
+        }
+        let _: () = pipeline.query(&mut self.connection)?;
+        Ok(())
+    }
+}
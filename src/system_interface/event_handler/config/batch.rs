@@ -0,0 +1,269 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to check a batch of modifications against the configuration that
+//! would result from applying the whole batch.
+//!
+//! The batch is applied to a draft copy of the configuration and the draft is
+//! validated once. Only errors that are not already present in the current
+//! configuration are reported, and each error is attributed to the
+//! modification that introduced it. Errors that are introduced and then fixed
+//! later in the same batch are not reported. Problems with the media files
+//! are left to the validation report and never reject a batch.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import the relevant structures into the correct namespace
+use super::YamlConfig;
+use super::validation::config_errors;
+
+// Import FNV HashMap and HashSet
+use fnv::{FnvHashMap, FnvHashSet};
+
+/// A function to check a batch of modifications against a draft of the
+/// current configuration. The function returns every new error in the
+/// resulting configuration, or an empty list if the batch is valid.
+///
+/// # Notes
+///
+/// Each new error is attributed by searching for the shortest part of the
+/// batch that introduces it, so the draft is only validated again when
+/// there are errors to report.
+///
+pub(super) fn check_batch(
+    base: YamlConfig,
+    modifications: &[Modification],
+) -> Vec<ModificationError> {
+    // Note the errors that are already in the configuration
+    let existing = config_errors(&base);
+
+    // Apply the whole batch and note the new errors in the result
+    let mut draft = base.clone();
+    for modification in modifications.iter() {
+        apply_to_draft(&mut draft, modification);
+    }
+    let resulting: Vec<ValidationIssue> = config_errors(&draft)
+        .into_iter()
+        .filter(|issue| !existing.contains(issue))
+        .collect();
+
+    // Attribute each new error to a modification
+    let mut steps = FnvHashMap::default();
+    let mut errors = Vec::new();
+    for issue in resulting {
+        // Find the first modification after which the error is present
+        let (mut without, mut with) = (0, modifications.len());
+        while with - without > 1 {
+            let middle = (without + with) / 2;
+            let step = steps.entry(middle).or_insert_with(|| {
+                let mut draft = base.clone();
+                for modification in modifications[..middle].iter() {
+                    apply_to_draft(&mut draft, modification);
+                }
+                config_errors(&draft)
+            });
+            if step.contains(&issue) {
+                with = middle;
+            } else {
+                without = middle;
+            }
+        }
+
+        // Add the error to the list
+        errors.push(ModificationError {
+            index: with - 1,
+            item: issue.item,
+            message: issue.message,
+        });
+    }
+    errors
+}
+
+/// A helper function to apply one modification to the draft configuration,
+/// matching the changes made to the active configuration
+///
+pub(super) fn apply_to_draft(draft: &mut YamlConfig, modification: &Modification) {
+    match modification {
+        // Add or replace the item description, keeping any event
+        Modification::ModifyItem { item_pair } => {
+            let event = draft.event_set.remove(item_pair).flatten();
+            draft.event_set.insert(item_pair.clone(), event);
+        }
+
+        // Add, replace, or remove the event
+        Modification::ModifyEvent { item_id, event } => {
+            let item_pair = ItemPair::new_default(item_id.id());
+            match draft.event_set.get_mut(&item_pair) {
+                Some(existing) => *existing = event.clone().map(|event| event.into()),
+                None => {
+                    if let Some(event) = event {
+                        draft
+                            .event_set
+                            .insert(item_pair, Some(event.clone().into()));
+                    }
+                }
+            }
+        }
+
+        // Add, replace, or remove the group
+        Modification::ModifyGroup { item_id, group } => match group {
+            Some(group) => {
+                draft.group_map.insert(*item_id, group.clone().into());
+            }
+            None => {
+                draft.group_map.remove(item_id);
+            }
+        },
+
+        // Replace the configuration parameters
+        Modification::ModifyParameters { parameters } => {
+            draft.identifier = parameters.identifier;
+            draft.server_location = parameters.server_location.clone();
+            draft.dmx_controllers = parameters.dmx_controllers.clone();
+            draft.media_players = parameters.media_players.clone();
+            draft.system_connections = parameters.system_connections.clone();
+            draft.background_process = parameters.background_process.clone();
+            draft.default_scene = parameters.default_scene;
+        }
+
+        // Replace the other settings
+        Modification::ModifySettings { settings } => {
+            draft.state_sync = settings.state_sync.clone();
+            draft.statistics = settings.statistics.clone();
+            draft.hint_penalty = settings.hint_penalty.clone();
+            draft.user_styles = settings.user_styles.clone();
+            draft.describe_items = settings.describe_items;
+        }
+
+        // Add, replace, or remove the status
+        Modification::ModifyStatus { item_id, status } => match status {
+            Some(status) => {
+                draft.status_map.insert(*item_id, status.clone());
+            }
+            None => {
+                draft.status_map.remove(item_id);
+            }
+        },
+
+        // Add, replace, or remove the scene
+        Modification::ModifyScene { item_id, scene } => match scene {
+            Some(scene) => {
+                // Extract the groups from the item list
+                let mut items = FnvHashSet::default();
+                let mut groups = FnvHashSet::default();
+                for id in scene.items.iter() {
+                    if draft.group_map.contains_key(id) {
+                        groups.insert(*id);
+                    } else {
+                        items.insert(*id);
+                    }
+                }
+
+                // Keep the existing hints, if none were provided
+                let hints = match scene.hints {
+                    Some(ref hints) => hints.clone(),
+                    None => draft
+                        .scene_map
+                        .get(item_id)
+                        .map(|scene| scene.hints.clone())
+                        .unwrap_or_default(),
+                };
+
+                // Replace the scene
+                draft.scene_map.insert(
+                    *item_id,
+                    Scene {
+                        items,
+                        groups,
+                        key_map: scene.key_map.clone(),
+                        hints,
+                    },
+                );
+            }
+            None => {
+                draft.scene_map.remove(item_id);
+            }
+        },
+
+        // Remove the item, its definitions, and its place in any scene or group
+        Modification::RemoveItem { item_id } => {
+            draft.event_set.remove(&ItemPair::new_default(item_id.id()));
+            draft.group_map.remove(item_id);
+            draft.status_map.remove(item_id);
+            draft.scene_map.remove(item_id);
+            for scene in draft.scene_map.values_mut() {
+                scene.items.remove(item_id);
+            }
+            for group in draft.group_map.values_mut() {
+                group.items.remove(item_id);
+            }
+        }
+    }
+}
+
+// Tests of the batch module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system_interface::event_handler::config::test_config;
+
+    // Test that a batch is checked as a whole and errors are attributed
+    #[test]
+    fn check_modification_batch() {
+        // Create a small, valid configuration
+        let draft = || {
+            test_config(
+                "default_scene: {id: 100}\nscene_map:\n  ? id: 100\n  : items: [{id: 10}]\n    groups: []\n    key_map: ~\nevent_set:\n  ? id: 100\n    description: Scene\n  : ~\n  ? id: 10\n    description: Start\n  : []\n",
+            )
+        };
+        let scene = |ids: &[u32]| Modification::ModifyScene {
+            item_id: ItemId::new_unchecked(100),
+            scene: Some(WebScene {
+                items: ids.iter().map(|id| ItemId::new_unchecked(*id)).collect(),
+                key_map: None,
+                hints: None,
+            }),
+        };
+        let event = |id| Modification::ModifyEvent {
+            item_id: ItemId::new_unchecked(id),
+            event: Some(Event::new().into()),
+        };
+
+        // Check that a valid batch has no errors
+        assert!(check_batch(draft(), &[event(11), scene(&[10, 11])]).is_empty());
+
+        // Check that a batch is valid if a later modification fixes an earlier one
+        assert!(check_batch(draft(), &[scene(&[10, 11]), event(11)]).is_empty());
+
+        // Check that an invalid modification is reported with its position
+        let errors = check_batch(draft(), &[event(11), scene(&[10, 12])]);
+        assert_eq!(1, errors.len());
+        assert_eq!(1, errors[0].index);
+        assert_eq!(Some(ItemId::new_unchecked(12)), errors[0].item);
+
+        // Check that removing an item in use is reported
+        let errors = check_batch(
+            draft(),
+            &[Modification::RemoveItem {
+                item_id: ItemId::new_unchecked(100),
+            }],
+        );
+        assert_eq!(0, errors[0].index);
+        assert_eq!("Default scene is not defined", errors[0].message);
+    }
+}
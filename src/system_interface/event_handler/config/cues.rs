@@ -0,0 +1,600 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to import and export cue lists as CSV files, with one item per
+//! row and the columns named in the first row.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import the relevant structures into the correct namespace
+use super::YamlConfig;
+use super::diff::{read_yaml_config, write_modified};
+
+// Import standard library features
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+// Import FNV HashMap
+use fnv::FnvHashMap;
+
+// Import anyhow features
+use anyhow::Result;
+
+/// The columns of a cue list, in the order they are exported
+const COLUMNS: [&str; 9] = [
+    "id",
+    "description",
+    "scene",
+    "delay",
+    "media_uri",
+    "media_channel",
+    "dmx_channel",
+    "dmx_value",
+    "next_event",
+];
+
+/// The media channel for a media cue, if none is specified
+const DEFAULT_MEDIA_CHANNEL: u32 = 1;
+
+/// A helper struct to hold one row of a cue list
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Cue {
+    id: u32,                    // the id of the item
+    description: String,        // the description of the item, if any
+    scene: Option<u32>,         // the scene to add the item to, if any
+    delay: Option<Duration>,    // the delay before the next event, if any
+    media_uri: Option<String>,  // the media to play, if any
+    media_channel: Option<u32>, // the channel to play the media on, if specified
+    dmx: Option<(u32, u8)>,     // the dmx channel and value to set, if any
+    next_event: Option<u32>,    // the event to cue after the delay, if any
+}
+
+/// A function to import the cue list from the provided CSV file into the
+/// configuration file and write the result to the output file. The function
+/// returns the number of cues imported.
+///
+/// # Notes
+///
+/// Any included files are merged into the output file.
+///
+/// # Errors
+///
+/// This function will raise an error if either file could not be read, if
+/// any row of the cue list is not valid, if the resulting configuration is
+/// not valid, or if the output file could not be written. The output file is
+/// not written if there are any errors.
+///
+pub fn import_cues_file(config: &str, cues: &str, output: &str) -> Result<usize> {
+    // Read the configuration and the cue list
+    let config_path = Path::new(config);
+    let yaml_config = read_yaml_config(config_path)?;
+    let text =
+        fs::read_to_string(cues).map_err(|error| anyhow!("Unable to read {}: {}", cues, error))?;
+
+    // Import the cues and write the result
+    let (count, modifications) = cue_modifications(&yaml_config, &text)?;
+    write_modified(config_path, &modifications, output)?;
+    Ok(count)
+}
+
+/// A function to export the items and events of the configuration file to a
+/// cue list in the output file. The function returns the number of cues
+/// exported.
+///
+/// # Errors
+///
+/// This function will raise an error if the configuration could not be read
+/// or if the output file could not be written.
+///
+pub fn export_cues_file(config: &str, output: &str) -> Result<usize> {
+    let yaml_config = read_yaml_config(Path::new(config))?;
+    let (count, text) = cues_csv(&yaml_config);
+    fs::write(output, text).map_err(|error| anyhow!("Unable to write {}: {}", output, error))?;
+    Ok(count)
+}
+
+/// A function to return the modifications that import the provided cue list
+/// into the configuration, along with the number of cues
+///
+/// # Notes
+///
+/// The first media cue, dmx fade, and next event of an existing event are
+/// replaced by those in the cue list, and any other actions of the event are
+/// kept. An item is added to its scene, but is not removed from any other
+/// scene.
+///
+/// # Errors
+///
+/// This function will raise an error that lists every row of the cue list
+/// that is not valid.
+///
+pub(super) fn cue_modifications(
+    config: &YamlConfig,
+    text: &str,
+) -> Result<(usize, Vec<Modification>)> {
+    // Read every cue, noting any errors
+    let rows = parse_csv(text);
+    let Some((header, rows)) = rows.split_first() else {
+        return Err(anyhow!("The cue list is empty."));
+    };
+    let columns = column_map(header)?;
+    let mut cues = Vec::new();
+    let mut rows_of: FnvHashMap<u32, usize> = FnvHashMap::default();
+    let mut errors = Vec::new();
+    for (index, row) in rows.iter().enumerate() {
+        // Ignore empty rows
+        if row.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+
+        // Add the cue or note the error (the header is row 1)
+        match parse_cue(&columns, row) {
+            Ok(cue) => match rows_of.get(&cue.id) {
+                Some(first) => errors.push(format!(
+                    "Row {}: Item {} is already listed in row {}",
+                    index + 2,
+                    cue.id,
+                    first
+                )),
+                None => {
+                    rows_of.insert(cue.id, index + 2);
+                    cues.push(cue);
+                }
+            },
+            Err(error) => errors.push(format!("Row {}: {}", index + 2, error)),
+        }
+    }
+
+    // Create the modifications for each cue
+    let mut items = Vec::new();
+    let mut events = Vec::new();
+    let mut scenes: FnvHashMap<ItemId, Scene> = FnvHashMap::default();
+    for cue in cues.iter() {
+        let item_id = ItemId::new_unchecked(cue.id);
+        let existing = config
+            .event_set
+            .get_key_value(&ItemPair::new_default(cue.id));
+
+        // Add or rename the item, if a new description is provided
+        match (cue.description.is_empty(), existing) {
+            (true, Some(_)) => (),
+            (false, Some((item_pair, _))) if item_pair.description == cue.description => (),
+            (true, None) => errors.push(format!("Item {}: A new item needs a description", cue.id)),
+            (false, _) => match ItemPair::new(cue.id, &cue.description) {
+                Some(item_pair) => items.push(Modification::ModifyItem { item_pair }),
+                None => errors.push(format!("Item {}: Not a valid item id", cue.id)),
+            },
+        }
+
+        // Update the cue actions of the event, if anything has changed
+        let previous = existing.and_then(|(_, event)| event.clone());
+        let mut event = previous.clone().unwrap_or_default();
+        update_event(&mut event, cue, &columns);
+        if previous.unwrap_or_default() != event {
+            events.push(Modification::ModifyEvent {
+                item_id,
+                event: Some(event.into()),
+            });
+        }
+
+        // Add the item to the scene
+        if let Some(scene_id) = cue.scene {
+            let scene_id = ItemId::new_unchecked(scene_id);
+            match config.scene_map.get(&scene_id) {
+                // Add the item, unless it's already in the scene
+                Some(scene) => {
+                    if !scene.items.contains(&item_id) && !scene.groups.contains(&item_id) {
+                        scenes
+                            .entry(scene_id)
+                            .or_insert_with(|| scene.clone())
+                            .items
+                            .insert(item_id);
+                    }
+                }
+                None => errors.push(format!(
+                    "Item {}: Scene {} is not defined",
+                    cue.id, scene_id
+                )),
+            }
+        }
+    }
+
+    // Report any errors
+    if !errors.is_empty() {
+        return Err(anyhow!("Invalid cue list:\n{}", errors.join("\n")));
+    }
+
+    // Define the items before they are used
+    let mut modifications = items;
+    modifications.extend(events);
+    let mut scenes: Vec<(ItemId, Scene)> = scenes.into_iter().collect();
+    scenes.sort_by_key(|(scene_id, _)| *scene_id);
+    modifications.extend(
+        scenes
+            .into_iter()
+            .map(|(item_id, scene)| Modification::ModifyScene {
+                item_id,
+                scene: Some(scene.into()),
+            }),
+    );
+    Ok((cues.len(), modifications))
+}
+
+/// A function to return every item and event of the configuration as a cue
+/// list, along with the number of cues
+///
+/// # Notes
+///
+/// Only the first media cue, dmx fade, and next event of each event are
+/// included. Each item is listed with the lowest scene that includes it.
+///
+pub(super) fn cues_csv(config: &YamlConfig) -> (usize, String) {
+    // Write the header
+    let mut text = COLUMNS.join(",") + "\n";
+
+    // Write each item, ordered by id
+    let mut pairs: Vec<(&ItemPair, &Option<Event>)> = config.event_set.iter().collect();
+    pairs.sort_by_key(|(item_pair, _)| item_pair.id());
+    for (item_pair, event) in pairs.iter() {
+        // Find the lowest scene with the item
+        let item_id = item_pair.get_id();
+        let scene = config
+            .scene_map
+            .iter()
+            .filter(|(_, scene)| scene.items.contains(&item_id) || scene.groups.contains(&item_id))
+            .map(|(scene_id, _)| *scene_id)
+            .min();
+
+        // Find the first of each cue action
+        let actions = event.as_deref().unwrap_or_default();
+        let media = actions.iter().find_map(|action| match action {
+            CueMedia { cue } => Some(cue),
+            _ => None,
+        });
+        let fade = actions.iter().find_map(|action| match action {
+            CueDmx { fade } => Some(fade),
+            _ => None,
+        });
+        let next = actions.iter().find_map(|action| match action {
+            CueEvent { event } => Some(event),
+            _ => None,
+        });
+
+        // Write the row
+        let fields = [
+            item_pair.id().to_string(),
+            item_pair.description.clone(),
+            csv_optional(scene.map(|scene| scene.id())),
+            csv_optional(
+                next.and_then(|next| next.delay())
+                    .map(|delay| delay.as_secs_f64()),
+            ),
+            csv_optional(media.map(|media| media.uri.clone())),
+            csv_optional(media.map(|media| media.channel)),
+            csv_optional(fade.map(|fade| fade.channel)),
+            csv_optional(fade.map(|fade| fade.value)),
+            csv_optional(next.map(|next| next.id().id())),
+        ];
+        text += &(csv_row(&fields) + "\n");
+    }
+    (pairs.len(), text)
+}
+
+/// A helper function to find the position of each known column in the
+/// header row
+///
+/// # Errors
+///
+/// This function will raise an error if there is no id column.
+///
+fn column_map(header: &[String]) -> Result<FnvHashMap<&'static str, usize>> {
+    // Match each column name, ignoring case and spacing
+    let mut columns = FnvHashMap::default();
+    for (index, name) in header.iter().enumerate() {
+        let name = name.trim().to_lowercase().replace([' ', '-'], "_");
+        if let Some(column) = COLUMNS.iter().find(|column| **column == name) {
+            columns.insert(*column, index);
+        }
+    }
+
+    // Make sure the id column exists
+    if !columns.contains_key("id") {
+        return Err(anyhow!("The cue list has no id column."));
+    }
+    Ok(columns)
+}
+
+/// A helper function to read one cue from a row of the cue list
+///
+/// # Errors
+///
+/// This function will raise an error if any field is not valid.
+///
+fn parse_cue(columns: &FnvHashMap<&'static str, usize>, row: &[String]) -> Result<Cue> {
+    // Return the trimmed field in the column, if it isn't empty
+    let field = |column: &str| {
+        columns
+            .get(column)
+            .and_then(|index| row.get(*index))
+            .map(|field| field.trim())
+            .filter(|field| !field.is_empty())
+    };
+
+    // Read the field as a number, if it isn't empty
+    fn number<T: std::str::FromStr>(column: &str, field: Option<&str>) -> Result<Option<T>> {
+        field
+            .map(|field| {
+                field
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid {}: {}", column, field))
+            })
+            .transpose()
+    }
+
+    // Read each field
+    let id = number("id", field("id"))?.ok_or_else(|| anyhow!("Missing id"))?;
+    let delay = field("delay")
+        .map(|delay| {
+            delay
+                .parse::<f64>()
+                .ok()
+                .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                .ok_or_else(|| anyhow!("Invalid delay: {}", delay))
+        })
+        .transpose()?;
+    let dmx = match (
+        number("dmx_channel", field("dmx_channel"))?,
+        number("dmx_value", field("dmx_value"))?,
+    ) {
+        (Some(channel), Some(value)) => Some((channel, value)),
+        (None, None) => None,
+        _ => return Err(anyhow!("A dmx cue needs both a channel and a value")),
+    };
+    let next_event = number("next_event", field("next_event"))?;
+    if delay.is_some() && next_event.is_none() {
+        return Err(anyhow!("A delay needs a next event"));
+    }
+
+    // Return the cue
+    Ok(Cue {
+        id,
+        description: field("description").unwrap_or_default().into(),
+        scene: number("scene", field("scene"))?,
+        delay,
+        media_uri: field("media_uri").map(|uri| uri.into()),
+        media_channel: number("media_channel", field("media_channel"))?,
+        dmx,
+        next_event,
+    })
+}
+
+/// A helper function to update the first media cue, dmx fade, and next
+/// event of the event to match the cue
+///
+/// # Notes
+///
+/// Only the actions with columns in the cue list are updated. Any details of
+/// the media cue and dmx fade that are not in the cue list (e.g. the media to
+/// loop or the dmx universe) are kept.
+///
+fn update_event(event: &mut Event, cue: &Cue, columns: &FnvHashMap<&'static str, usize>) {
+    // Update the media cue
+    let media = cue.media_uri.as_ref().map(|uri| {
+        let mut media = event
+            .iter()
+            .find_map(|action| match action {
+                CueMedia { cue } => Some(cue.clone()),
+                _ => None,
+            })
+            .unwrap_or(MediaCue {
+                uri: String::new(),
+                channel: DEFAULT_MEDIA_CHANNEL,
+                loop_media: None,
+                on_complete: None,
+            });
+        media.uri = uri.clone();
+        media.channel = cue.media_channel.unwrap_or(media.channel);
+        CueMedia { cue: media }
+    });
+    if columns.contains_key("media_uri") {
+        replace_first(event, media, |action| matches!(action, CueMedia { .. }));
+    }
+
+    // Update the dmx fade
+    let fade = cue.dmx.map(|(channel, value)| {
+        let mut fade = event
+            .iter()
+            .find_map(|action| match action {
+                CueDmx { fade } => Some(fade.clone()),
+                _ => None,
+            })
+            .unwrap_or(DmxFade {
+                universe: None,
+                channel,
+                value,
+                duration: None,
+            });
+        fade.channel = channel;
+        fade.value = value;
+        CueDmx { fade }
+    });
+    if columns.contains_key("dmx_channel") || columns.contains_key("dmx_value") {
+        replace_first(event, fade, |action| matches!(action, CueDmx { .. }));
+    }
+
+    // Update the next event
+    let next = cue.next_event.map(|next_event| CueEvent {
+        event: EventDelay::new(cue.delay, ItemId::new_unchecked(next_event)),
+    });
+    if columns.contains_key("next_event") {
+        replace_first(event, next, |action| matches!(action, CueEvent { .. }));
+    }
+}
+
+/// A helper function to replace the first matching action of the event with
+/// the new action, to remove it (if there is no new action), or to add the
+/// new action (if there is no matching action)
+///
+fn replace_first<F>(event: &mut Event, action: Option<EventAction>, is_match: F)
+where
+    F: Fn(&EventAction) -> bool,
+{
+    match (event.iter().position(is_match), action) {
+        (Some(index), Some(action)) => event[index] = action,
+        (Some(index), None) => {
+            event.remove(index);
+        }
+        (None, Some(action)) => event.push(action),
+        (None, None) => (),
+    }
+}
+
+/// A helper function to split CSV text into rows of fields
+///
+/// # Notes
+///
+/// Fields in double quotes may contain commas, line breaks, and doubled
+/// quotes. A byte order mark at the start of the text is ignored.
+///
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut is_quoted = false;
+    let mut characters = text.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(character) = characters.next() {
+        match (is_quoted, character) {
+            // Handle quotes, including doubled quotes inside a quoted field
+            (true, '"') if characters.peek() == Some(&'"') => {
+                characters.next();
+                field.push('"');
+            }
+            (true, '"') => is_quoted = false,
+            (false, '"') if field.is_empty() => is_quoted = true,
+
+            // Separate fields and rows
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\r') => (),
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+
+            // Keep any other character
+            (_, character) => field.push(character),
+        }
+    }
+
+    // Add the last row, if it wasn't ended
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// A helper function to show an optional value, or nothing if missing
+///
+fn csv_optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// A helper function to compose one row, quoting fields when necessary
+///
+fn csv_row(fields: &[String]) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+// Tests of the cues module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system_interface::event_handler::config::test_config;
+
+    // Test that a cue list is imported into the configuration and exported
+    // again without changes
+    #[test]
+    fn import_and_export() {
+        // Create a configuration with one scene
+        let mut config = test_config(
+            "scene_map:\n  ? id: 10\n  : {items: [{id: 10}], groups: [], key_map: null}\nevent_set:\n  ? id: 10\n    description: First Scene\n  : []\n  ? id: 11\n    description: Old Name\n  : [!newScene {newScene: {id: 10}}]\n",
+        );
+
+        // Import a cue list with quoted fields and extra columns
+        let text = "\u{feff}ID,Description,Scene,Delay,Media URI,DMX Channel,DMX Value,Next Event,Notes\r\n11,\"Lights, Sound\",10,2.5,intro.mp4,5,255,12,keep\r\n12,\"The \"\"Finale\"\"\",10,,,,,,\r\n\r\n";
+        let (count, modifications) = cue_modifications(&config, text).unwrap();
+        assert_eq!(2, count);
+        for modification in modifications.iter() {
+            super::super::batch::apply_to_draft(&mut config, modification);
+        }
+
+        // Check the imported event (keeping the existing scene change)
+        let event = config
+            .event_set
+            .get(&ItemPair::new_default(11))
+            .cloned()
+            .flatten()
+            .unwrap();
+        assert_eq!(4, event.len());
+        assert!(matches!(event[0], NewScene { .. }));
+        assert!(
+            config.scene_map[&ItemId::new_unchecked(10)]
+                .items
+                .contains(&ItemId::new_unchecked(12))
+        );
+
+        // Check that the export includes the imported cues
+        let (count, exported) = cues_csv(&config);
+        assert_eq!(3, count);
+        assert_eq!(
+            "id,description,scene,delay,media_uri,media_channel,dmx_channel,dmx_value,next_event\n10,First Scene,10,,,,,,\n11,\"Lights, Sound\",10,2.5,intro.mp4,1,5,255,12\n12,\"The \"\"Finale\"\"\",10,,,,,,\n",
+            exported
+        );
+
+        // Check that an unchanged cue and missing columns leave the event as is
+        let (_, modifications) = cue_modifications(
+            &config,
+            "id,description\n11,Renamed\n12,\"The \"\"Finale\"\"\"\n",
+        )
+        .unwrap();
+        assert_eq!(1, modifications.len());
+        assert!(matches!(modifications[0], Modification::ModifyItem { .. }));
+
+        // Check that invalid rows are reported
+        let error = cue_modifications(&config, "id,scene,delay\n13,99,\nx,,\n14,,1\n13,,\n")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Row 3: Invalid id: x"));
+        assert!(error.contains("Row 4: A delay needs a next event"));
+        assert!(error.contains("Item 13: A new item needs a description"));
+        assert!(error.contains("Item 13: Scene 99 is not defined"));
+        assert!(error.contains("Row 5: Item 13 is already listed in row 2"));
+    }
+}
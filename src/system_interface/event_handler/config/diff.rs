@@ -0,0 +1,471 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to compare two configurations item by item and to merge selected
+//! changes from one configuration into another.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import the relevant structures into the correct namespace
+use super::YamlConfig;
+use super::batch::{apply_to_draft, check_batch};
+use super::includes::IncludeSources;
+use super::migration::migrate;
+use super::stable::{item_descriptions, to_stable_yaml};
+
+// Import standard library features
+use std::fs;
+use std::path::Path;
+
+// Import FNV HashSet
+use fnv::FnvHashSet;
+
+// Import anyhow features
+use anyhow::Result;
+
+// Import YAML processing library
+use serde_yaml::{self, Value};
+
+/// A function to compare two configuration files and return every
+/// difference between them
+///
+/// # Errors
+///
+/// This function will raise an error if either file could not be read.
+///
+pub fn diff_files(from: &str, to: &str) -> Result<ConfigDiff> {
+    // Read both configurations and compare them
+    let from_config = read_yaml_config(Path::new(from))?;
+    let to_config = read_yaml_config(Path::new(to))?;
+    Ok(ConfigDiff {
+        from: from.into(),
+        to: to.into(),
+        changes: diff_configs(&from_config, &to_config),
+    })
+}
+
+/// A function to merge the selected changes from the other configuration
+/// file into the base configuration file and write the result to the output
+/// file. The function returns the merged changes.
+///
+/// # Notes
+///
+/// Any included files are merged into the output file.
+///
+/// # Errors
+///
+/// This function will raise an error if either file could not be read, if
+/// the merged configuration is not valid, or if the output file could not be
+/// written. The output file is not written if there are any errors.
+///
+pub fn merge_files<F>(base: &str, other: &str, output: &str, select: F) -> Result<Vec<ConfigChange>>
+where
+    F: Fn(&ConfigChange) -> bool,
+{
+    // Read both configurations and select the changes to merge
+    let base_config = read_yaml_config(Path::new(base))?;
+    let other_config = read_yaml_config(Path::new(other))?;
+    let changes: Vec<ConfigChange> = diff_configs(&base_config, &other_config)
+        .into_iter()
+        .filter(|change| select(change))
+        .collect();
+
+    // Check the merged configuration and write the result
+    let modifications = merge_modifications(&other_config, &changes);
+    write_modified(Path::new(base), &modifications, output)?;
+    Ok(changes)
+}
+
+/// A function to apply the modifications to the configuration file and
+/// write the result to the output file
+///
+/// # Notes
+///
+/// Any included files are merged into the output file.
+///
+/// # Errors
+///
+/// This function will raise an error if the file could not be read, if the
+/// modified configuration is not valid, or if the output file could not be
+/// written. The output file is not written if there are any errors.
+///
+pub(super) fn write_modified(
+    base: &Path,
+    modifications: &[Modification],
+    output: &str,
+) -> Result<()> {
+    // Check the modified configuration
+    let mut base_config = read_yaml_config(base)?;
+    let errors = check_batch(read_yaml_config(base)?, modifications);
+    if !errors.is_empty() {
+        let messages: Vec<String> = errors
+            .iter()
+            .map(|error| match error.item {
+                Some(item) => format!("[{}]: {}", item, error.message),
+                None => error.message.clone(),
+            })
+            .collect();
+        return Err(anyhow!(
+            "Modified configuration is not valid:\n{}",
+            messages.join("\n")
+        ));
+    }
+
+    // Apply the modifications and write the result
+    for modification in modifications.iter() {
+        apply_to_draft(&mut base_config, modification);
+    }
+    let descriptions = base_config
+        .describe_items
+        .then(|| item_descriptions(&base_config.event_set));
+    fs::write(output, to_stable_yaml(&base_config, descriptions.as_ref())?)?;
+    Ok(())
+}
+
+/// A function to read a configuration file, upgrading it and merging any
+/// included files, without loading it
+///
+/// # Errors
+///
+/// This function will raise an error if the file could not be read or
+/// parsed, or if any included file could not be merged.
+///
+pub(super) fn read_yaml_config(path: &Path) -> Result<YamlConfig> {
+    // Read, parse, and upgrade the configuration (without saving)
+    let config_string = fs::read_to_string(path)
+        .map_err(|error| anyhow!("Unable to read {}: {}", path.display(), error))?;
+    let mut value: Value = serde_yaml::from_str(&config_string)
+        .map_err(|error| anyhow!("Unable to parse {}: {}", path.display(), error))?;
+    migrate(&mut value)?;
+    let mut yaml_config: YamlConfig = serde_yaml::from_value(value)
+        .map_err(|error| anyhow!("Invalid configuration {}: {}", path.display(), error))?;
+
+    // Merge any included files
+    let (_, errors) = IncludeSources::load(path, &mut yaml_config);
+    if !errors.is_empty() {
+        return Err(anyhow!("{}", errors.join("\n")));
+    }
+    yaml_config.includes.clear();
+    Ok(yaml_config)
+}
+
+/// A function to return every difference between two configurations,
+/// ordered by item and then by section
+///
+pub(super) fn diff_configs(from: &YamlConfig, to: &YamlConfig) -> Vec<ConfigChange> {
+    // Compare the configuration parameters
+    let mut changes = Vec::new();
+    if to_value(&parameters_of(from)) != to_value(&parameters_of(to)) {
+        changes.push(ConfigChange {
+            section: DiffSection::Parameters,
+            item: None,
+            kind: DiffKind::Changed,
+            description: String::new(),
+        });
+    }
+
+    // Compare the other settings
+    let changed_settings = settings_changed(&settings_of(from), &settings_of(to));
+    if !changed_settings.is_empty() {
+        changes.push(ConfigChange {
+            section: DiffSection::Settings,
+            item: None,
+            kind: DiffKind::Changed,
+            description: changed_settings.join(", "),
+        });
+    }
+
+    // Compare each item in either configuration
+    let mut ids: Vec<ItemId> = ids_of(from).union(&ids_of(to)).copied().collect();
+    ids.sort();
+    for id in ids {
+        // Find the item in each configuration
+        let pair = ItemPair::new_default(id.id());
+        let from_pair = from.event_set.get_key_value(&pair);
+        let to_pair = to.event_set.get_key_value(&pair);
+        let description = to_pair
+            .or(from_pair)
+            .map(|(pair, _)| pair.description.clone())
+            .unwrap_or_default();
+        let mut push = |section, kind| {
+            if let Some(kind) = kind {
+                changes.push(ConfigChange {
+                    section,
+                    item: Some(id),
+                    kind,
+                    description: description.clone(),
+                });
+            }
+        };
+
+        // Compare each part of the item
+        push(
+            DiffSection::Item,
+            kind_of(
+                from_pair.map(|(pair, _)| &pair.description),
+                to_pair.map(|(pair, _)| &pair.description),
+            ),
+        );
+        push(
+            DiffSection::Event,
+            kind_of(
+                from_pair.and_then(|(_, event)| event.as_ref()),
+                to_pair.and_then(|(_, event)| event.as_ref()),
+            ),
+        );
+        push(
+            DiffSection::Group,
+            kind_of(from.group_map.get(&id), to.group_map.get(&id)),
+        );
+        push(
+            DiffSection::Scene,
+            kind_of(from.scene_map.get(&id), to.scene_map.get(&id)),
+        );
+        push(
+            DiffSection::Status,
+            kind_of(from.status_map.get(&id), to.status_map.get(&id)),
+        );
+    }
+
+    // Return the differences
+    changes
+}
+
+/// A function to return the modifications that make the selected parts of
+/// a configuration match the provided configuration
+///
+/// # Notes
+///
+/// The modifications are ordered so that items and groups are defined before
+/// they are used, and removed items are removed last.
+///
+pub(super) fn merge_modifications(to: &YamlConfig, changes: &[ConfigChange]) -> Vec<Modification> {
+    // Create the modification for each change
+    let mut modifications = Vec::new();
+    for change in changes.iter() {
+        let modification = match (change.section, change.item) {
+            // Replace the configuration parameters
+            (DiffSection::Parameters, _) => Modification::ModifyParameters {
+                parameters: parameters_of(to),
+            },
+
+            // Replace the other settings
+            (DiffSection::Settings, _) => Modification::ModifySettings {
+                settings: settings_of(to),
+            },
+
+            // Add, replace, or remove the item
+            (DiffSection::Item, Some(item_id)) => {
+                match to
+                    .event_set
+                    .get_key_value(&ItemPair::new_default(item_id.id()))
+                {
+                    Some((item_pair, _)) => Modification::ModifyItem {
+                        item_pair: item_pair.clone(),
+                    },
+                    None => Modification::RemoveItem { item_id },
+                }
+            }
+
+            // Add, replace, or remove the event
+            (DiffSection::Event, Some(item_id)) => Modification::ModifyEvent {
+                item_id,
+                event: to
+                    .event_set
+                    .get(&ItemPair::new_default(item_id.id()))
+                    .cloned()
+                    .flatten()
+                    .map(|event| event.into()),
+            },
+
+            // Add, replace, or remove the group
+            (DiffSection::Group, Some(item_id)) => Modification::ModifyGroup {
+                item_id,
+                group: to
+                    .group_map
+                    .get(&item_id)
+                    .cloned()
+                    .map(|group| group.into()),
+            },
+
+            // Add, replace, or remove the scene
+            (DiffSection::Scene, Some(item_id)) => Modification::ModifyScene {
+                item_id,
+                scene: to
+                    .scene_map
+                    .get(&item_id)
+                    .cloned()
+                    .map(|scene| scene.into()),
+            },
+
+            // Add, replace, or remove the status
+            (DiffSection::Status, Some(item_id)) => Modification::ModifyStatus {
+                item_id,
+                status: to.status_map.get(&item_id).cloned(),
+            },
+
+            // Ignore changes to items without an item
+            _ => continue,
+        };
+        modifications.push(modification);
+    }
+
+    // Order the modifications so that each item is defined before it is used
+    modifications.sort_by_key(|modification| match modification {
+        Modification::ModifyItem { .. } => 0,
+        Modification::ModifyGroup { .. } => 1,
+        Modification::ModifyStatus { .. } => 2,
+        Modification::ModifyEvent { .. } => 3,
+        Modification::ModifyScene { .. } => 4,
+        Modification::ModifyParameters { .. } => 5,
+        Modification::ModifySettings { .. } => 5,
+        Modification::RemoveItem { .. } => 6,
+    });
+    modifications
+}
+
+/// A helper function to collect the configuration parameters
+///
+fn parameters_of(yaml_config: &YamlConfig) -> ConfigParameters {
+    ConfigParameters {
+        identifier: yaml_config.identifier,
+        server_location: yaml_config.server_location.clone(),
+        dmx_controllers: yaml_config.dmx_controllers.clone(),
+        media_players: yaml_config.media_players.clone(),
+        system_connections: yaml_config.system_connections.clone(),
+        background_process: yaml_config.background_process.clone(),
+        default_scene: yaml_config.default_scene,
+    }
+}
+
+/// A helper function to collect the other settings
+///
+fn settings_of(yaml_config: &YamlConfig) -> ConfigSettings {
+    ConfigSettings {
+        state_sync: yaml_config.state_sync.clone(),
+        statistics: yaml_config.statistics.clone(),
+        hint_penalty: yaml_config.hint_penalty.clone(),
+        user_styles: yaml_config.user_styles.clone(),
+        describe_items: yaml_config.describe_items,
+    }
+}
+
+/// A helper function to list the names of the settings that differ
+///
+fn settings_changed(from: &ConfigSettings, to: &ConfigSettings) -> Vec<&'static str> {
+    [
+        ("state_sync", from.state_sync != to.state_sync),
+        ("statistics", from.statistics != to.statistics),
+        ("hint_penalty", from.hint_penalty != to.hint_penalty),
+        ("user_styles", from.user_styles != to.user_styles),
+        ("describe_items", from.describe_items != to.describe_items),
+    ]
+    .into_iter()
+    .filter_map(|(name, is_changed)| is_changed.then_some(name))
+    .collect()
+}
+
+/// A helper function to collect the ids of every item in the configuration
+///
+fn ids_of(yaml_config: &YamlConfig) -> FnvHashSet<ItemId> {
+    let mut ids: FnvHashSet<ItemId> = yaml_config
+        .event_set
+        .keys()
+        .map(|pair| pair.get_id())
+        .collect();
+    ids.extend(yaml_config.group_map.keys());
+    ids.extend(yaml_config.scene_map.keys());
+    ids.extend(yaml_config.status_map.keys());
+    ids
+}
+
+/// A helper function to convert the configuration parameters into a value
+/// that can be compared regardless of the order of any maps
+///
+fn to_value(parameters: &ConfigParameters) -> Value {
+    serde_yaml::to_value(parameters).unwrap_or(Value::Null)
+}
+
+/// A helper function to describe how a part of the configuration differs,
+/// if it differs
+///
+fn kind_of<T: PartialEq>(from: Option<&T>, to: Option<&T>) -> Option<DiffKind> {
+    match (from, to) {
+        (None, Some(_)) => Some(DiffKind::Added),
+        (Some(_), None) => Some(DiffKind::Removed),
+        (Some(from), Some(to)) if from != to => Some(DiffKind::Changed),
+        _ => None,
+    }
+}
+
+// Tests of the diff module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system_interface::event_handler::config::test_config;
+
+    // Test that configurations are compared item by item and merged
+    #[test]
+    fn diff_and_merge() {
+        // Create two versions of a small configuration
+        let config = |extra: &str| {
+            test_config(&format!(
+                "default_scene: {{id: 100}}\nscene_map:\n  ? id: 100\n  : items: [{{id: 10}}, {{id: 11}}]\n    groups: []\n    key_map: ~\nevent_set:\n  ? id: 100\n    description: Scene\n  : ~\n  ? id: 10\n    description: Start\n  : []\n  ? id: 11\n    description: Stop\n  : []\n{}",
+                extra
+            ))
+        };
+        let from = config("");
+        let mut to = config("  ? id: 12\n    description: New\n  : []\n");
+
+        // Check that identical configurations have no differences
+        assert!(diff_configs(&from, &config("")).is_empty());
+
+        // Check that the new item and event are reported
+        let changes = diff_configs(&from, &to);
+        assert_eq!(2, changes.len());
+        assert_eq!(DiffSection::Item, changes[0].section);
+        assert_eq!(DiffSection::Event, changes[1].section);
+        assert_eq!(Some(ItemId::new_unchecked(12)), changes[0].item);
+        assert_eq!(DiffKind::Added, changes[0].kind);
+        assert_eq!("New", changes[0].description);
+
+        // Check that the reverse is reported as removed
+        let changes = diff_configs(&to, &from);
+        assert_eq!(DiffKind::Removed, changes[0].kind);
+
+        // Check that merging the changes removes the differences
+        let modifications = merge_modifications(&to, &diff_configs(&from, &to));
+        let mut merged = config("");
+        for modification in modifications.iter() {
+            apply_to_draft(&mut merged, modification);
+        }
+        assert!(diff_configs(&merged, &to).is_empty());
+
+        // Check that changed settings are reported and merged
+        to.describe_items = true;
+        to.user_styles.insert("body".into(), "color: red;".into());
+        let changes = diff_configs(&merged, &to);
+        assert_eq!(1, changes.len());
+        assert_eq!(DiffSection::Settings, changes[0].section);
+        assert_eq!("user_styles, describe_items", changes[0].description);
+        for modification in merge_modifications(&to, &changes).iter() {
+            apply_to_draft(&mut merged, modification);
+        }
+        assert!(diff_configs(&merged, &to).is_empty());
+    }
+}
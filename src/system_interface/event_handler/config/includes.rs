@@ -0,0 +1,519 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to merge included files into the configuration and to split the
+//! configuration back into those files when it is saved.
+//!
+//! An included file may contain its own includes, groups, scenes, statuses,
+//! and events. The paths of the included files are relative to the file that
+//! includes them. A file may be included by several files, but is only
+//! loaded once. An item may only be defined in one file, so any item that
+//! is defined again in an included file is reported as a conflict.
+//!
+//! Each included file records its own schema and is upgraded separately, in
+//! the same way as the main configuration file.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import the relevant structures into the correct namespace
+use super::YamlConfig;
+use super::migration::{CURRENT_SCHEMA, backup_path, migrate, schema_of};
+use super::stable::{item_descriptions, to_stable_yaml};
+
+// Import standard library features
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Import FNV HashMap
+use fnv::FnvHashMap;
+
+// Import tracing features
+use tracing::{info, warn};
+
+/// The structure of an included configuration file
+///
+#[derive(Default, Serialize, Deserialize)]
+struct YamlInclude {
+    #[serde(default)]
+    schema: u32, // the layout of the file, used to upgrade older files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    includes: Vec<PathBuf>, // the files included by this file, relative to this file
+    #[serde(default, skip_serializing_if = "FnvHashMap::is_empty")]
+    group_map: FnvHashMap<ItemId, Group>, // the groups defined in this file
+    #[serde(default, skip_serializing_if = "FnvHashMap::is_empty")]
+    scene_map: FnvHashMap<ItemId, Scene>, // the scenes defined in this file
+    #[serde(default, skip_serializing_if = "FnvHashMap::is_empty")]
+    status_map: StatusMap, // the statuses defined in this file
+    #[serde(default, skip_serializing_if = "FnvHashMap::is_empty")]
+    event_set: FnvHashMap<ItemPair, Option<Event>>, // the item pairs and events defined in this file
+}
+
+/// A structure to describe one included file
+///
+#[derive(Clone, Debug)]
+struct IncludeFile {
+    path: PathBuf,          // the location of the file
+    includes: Vec<PathBuf>, // the files included by this file, as written
+}
+
+/// A structure to remember the file each included item came from
+///
+/// # Notes
+///
+/// Items that are not listed here (including any new items) belong to the
+/// main configuration file.
+///
+#[derive(Clone, Debug, Default)]
+pub struct IncludeSources {
+    includes: Vec<PathBuf>,  // the files included by the main file, as written
+    files: Vec<IncludeFile>, // every included file, in the order loaded
+    groups: FnvHashMap<ItemId, usize>, // the file of each included group
+    scenes: FnvHashMap<ItemId, usize>, // the file of each included scene
+    statuses: FnvHashMap<ItemId, usize>, // the file of each included status
+    events: FnvHashMap<ItemId, usize>, // the file of each included item pair and event
+}
+
+// Implement key features of the include sources
+impl IncludeSources {
+    /// A function to load every file included by the configuration and merge
+    /// the items into the configuration. The config path is the location of
+    /// the main configuration file.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a description of every included file that could
+    /// not be read and every item that is defined more than once. If there
+    /// are any errors, the merged configuration should not be used.
+    ///
+    pub(super) fn load(config_path: &Path, yaml_config: &mut YamlConfig) -> (Self, Vec<String>) {
+        // Load each file included by the main file
+        let mut sources = Self {
+            includes: yaml_config.includes.clone(),
+            ..Self::default()
+        };
+        let mut errors = Vec::new();
+        let mut stack = vec![canonical(config_path)];
+        let mut loaded = Vec::new();
+        for include in yaml_config.includes.clone() {
+            sources.load_file(
+                &relative_to(config_path, &include),
+                yaml_config,
+                (&mut stack, &mut loaded),
+                &mut errors,
+            );
+        }
+
+        // Return the sources and any errors
+        (sources, errors)
+    }
+
+    /// A method to return the location of every included file
+    ///
+    pub fn get_paths(&self) -> Vec<PathBuf> {
+        self.files.iter().map(|file| file.path.clone()).collect()
+    }
+
+    /// A method to remove the included items from the configuration and
+    /// write them back to the files they came from.
+    ///
+    /// # Errors
+    ///
+    /// This method returns a description of every file that could not be
+    /// written. The included items are removed from the configuration
+    /// regardless.
+    ///
+    pub(super) fn save(&self, yaml_config: &mut YamlConfig) -> Vec<String> {
+        // Restore the includes of the main file
+        yaml_config.includes = self.includes.clone();
+
+        // Collect the descriptions of every item, if requested
+        let descriptions = yaml_config
+            .describe_items
+            .then(|| item_descriptions(&yaml_config.event_set));
+
+        // Move every included item back to its file
+        let mut contents: Vec<YamlInclude> = self
+            .files
+            .iter()
+            .map(|file| YamlInclude {
+                schema: CURRENT_SCHEMA,
+                includes: file.includes.clone(),
+                ..YamlInclude::default()
+            })
+            .collect();
+        for (index, groups) in take_included(&mut yaml_config.group_map, &self.groups) {
+            contents[index].group_map.extend(groups);
+        }
+        for (index, scenes) in take_included(&mut yaml_config.scene_map, &self.scenes) {
+            contents[index].scene_map.extend(scenes);
+        }
+        for (index, statuses) in take_included(&mut yaml_config.status_map, &self.statuses) {
+            contents[index].status_map.extend(statuses);
+        }
+        // Move the included item pairs and events, keeping the rest in the main file
+        let event_set = std::mem::take(&mut yaml_config.event_set);
+        for (item_pair, event) in event_set {
+            match self.events.get(&item_pair.get_id()) {
+                Some(index) => {
+                    contents[*index].event_set.insert(item_pair, event);
+                }
+                None => {
+                    yaml_config.event_set.insert(item_pair, event);
+                }
+            }
+        }
+
+        // Write each included file
+        let mut errors = Vec::new();
+        for (file, content) in self.files.iter().zip(contents) {
+            let result = to_stable_yaml(&content, descriptions.as_ref())
+                .map_err(|error| error.to_string())
+                .and_then(|string| {
+                    fs::write(&file.path, string).map_err(|error| error.to_string())
+                });
+            if let Err(error) = result {
+                errors.push(format!(
+                    "Unable to write included file {}: {}",
+                    file.path.display(),
+                    error
+                ));
+            }
+        }
+        errors
+    }
+
+    /// A helper method to load one included file, and any files it includes
+    ///
+    /// # Notes
+    ///
+    /// The stack holds the files that are currently being loaded, so that a
+    /// file that includes itself (directly or indirectly) is reported as an
+    /// error. A file that was already loaded through another include is
+    /// skipped quietly.
+    ///
+    fn load_file(
+        &mut self,
+        path: &Path,
+        yaml_config: &mut YamlConfig,
+        (stack, loaded): (&mut Vec<PathBuf>, &mut Vec<PathBuf>),
+        errors: &mut Vec<String>,
+    ) {
+        // Check that the file doesn't include itself and isn't already loaded
+        let canonical_path = canonical(path);
+        if stack.contains(&canonical_path) {
+            errors.push(format!("File includes itself: {}", path.display()));
+            return;
+        } else if loaded.contains(&canonical_path) {
+            return;
+        }
+        loaded.push(canonical_path.clone());
+
+        // Try to read, upgrade, and parse the file
+        let include = match load_include(path) {
+            Ok(include) => include,
+            Err(error) => {
+                errors.push(format!(
+                    "Unable to load included file {}: {}",
+                    path.display(),
+                    error
+                ));
+                return;
+            }
+        };
+
+        // Add the file to the list
+        let index = self.files.len();
+        self.files.push(IncludeFile {
+            path: path.to_path_buf(),
+            includes: include.includes.clone(),
+        });
+
+        // Merge each kind of item, keeping the first definition of any conflict
+        let file = path.display();
+        for (id, group) in include.group_map {
+            if yaml_config.group_map.contains_key(&id) {
+                errors.push(format!("Group {} in {} is already defined", id, file));
+                continue;
+            }
+            yaml_config.group_map.insert(id, group);
+            self.groups.insert(id, index);
+        }
+        for (id, scene) in include.scene_map {
+            if yaml_config.scene_map.contains_key(&id) {
+                errors.push(format!("Scene {} in {} is already defined", id, file));
+                continue;
+            }
+            yaml_config.scene_map.insert(id, scene);
+            self.scenes.insert(id, index);
+        }
+        for (id, status) in include.status_map {
+            if yaml_config.status_map.contains_key(&id) {
+                errors.push(format!("Status {} in {} is already defined", id, file));
+                continue;
+            }
+            yaml_config.status_map.insert(id, status);
+            self.statuses.insert(id, index);
+        }
+        for (item_pair, event) in include.event_set {
+            let id = item_pair.get_id();
+            if yaml_config.event_set.contains_key(&item_pair) {
+                errors.push(format!("Item {} in {} is already defined", id, file));
+                continue;
+            }
+            yaml_config.event_set.insert(item_pair, event);
+            self.events.insert(id, index);
+        }
+
+        // Load any files included by this file
+        stack.push(canonical_path);
+        for nested in include.includes {
+            self.load_file(
+                &relative_to(path, &nested),
+                yaml_config,
+                (stack, loaded),
+                errors,
+            );
+        }
+        stack.pop();
+    }
+}
+
+/// A helper function to read and parse one included file. If the file was
+/// written with an older schema, it is upgraded to the current schema. The
+/// original file is copied to a backup and the upgraded file is written in
+/// its place.
+///
+/// # Errors
+///
+/// This function will raise an error if the file could not be read, parsed,
+/// or upgraded, or if the original could not be backed up. If the upgraded
+/// file could not be written, this function will only raise a warning (the
+/// upgrade is repeated the next time the file is loaded).
+///
+fn load_include(path: &Path) -> Result<YamlInclude, String> {
+    // Read and parse the raw file
+    let original = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let mut value: serde_yaml::Value =
+        serde_yaml::from_str(&original).map_err(|error| error.to_string())?;
+
+    // Upgrade the file if it has an older schema
+    let schema = schema_of(&value);
+    let applied = migrate(&mut value).map_err(|error| error.to_string())?;
+    let include: YamlInclude = serde_yaml::from_value(value).map_err(|error| error.to_string())?;
+    if applied.is_empty() {
+        return Ok(include);
+    }
+
+    // Describe each step of the upgrade
+    for step in applied.iter() {
+        info!("Upgrading included file {}: {}.", path.display(), step);
+    }
+
+    // Copy the original file to the backup
+    let backup = backup_path(path, schema);
+    fs::write(&backup, original)
+        .map_err(|error| format!("Unable to backup file before upgrade: {}", error))?;
+    info!("Original included file saved to {}.", backup.display());
+
+    // Write the upgraded file in place of the original
+    match to_stable_yaml(&include, None) {
+        Ok(upgraded) => {
+            if let Err(error) = fs::write(path, upgraded) {
+                warn!("Unable to save upgraded included file: {}.", error);
+            }
+        }
+        Err(error) => warn!("Unable to save upgraded included file: {}.", error),
+    }
+    Ok(include)
+}
+
+/// A helper function to find the location of an included file relative to
+/// the file that includes it
+///
+fn relative_to(file: &Path, include: &Path) -> PathBuf {
+    match file.parent() {
+        Some(directory) => directory.join(include),
+        None => include.to_path_buf(),
+    }
+}
+
+/// A helper function to find the canonical location of a file, if possible
+///
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or(path.to_path_buf())
+}
+
+/// A helper function to remove the included items from one map and sort them
+/// by file
+///
+fn take_included<T>(
+    map: &mut FnvHashMap<ItemId, T>,
+    sources: &FnvHashMap<ItemId, usize>,
+) -> Vec<(usize, FnvHashMap<ItemId, T>)> {
+    // Sort the included items by file
+    let mut files: FnvHashMap<usize, FnvHashMap<ItemId, T>> = FnvHashMap::default();
+    for (id, index) in sources.iter() {
+        if let Some(item) = map.remove(id) {
+            files.entry(*index).or_default().insert(*id, item);
+        }
+    }
+    files.into_iter().collect()
+}
+
+// Tests of the includes module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system_interface::event_handler::config::test_config;
+
+    // Test loading, merging, and saving included files
+    #[test]
+    fn load_and_save_includes() {
+        // Create the main file and the included files
+        let directory = std::env::temp_dir().join("minerva_includes");
+        fs::create_dir_all(directory.join("rooms")).unwrap();
+        let main_path = directory.join("main.yaml");
+        fs::write(
+            directory.join("rooms/first.yaml"),
+            "includes: [library.yaml]\nevent_set:\n  ? id: 20\n    description: First Room\n  : []\n",
+        )
+        .unwrap();
+        fs::write(
+            directory.join("rooms/second.yaml"),
+            "includes: [library.yaml]\nevent_set:\n  ? id: 25\n    description: Second Room\n  : []\n",
+        )
+        .unwrap();
+        fs::write(
+            directory.join("rooms/library.yaml"),
+            "event_set:\n  ? id: 30\n    description: Shared Event\n  : []\n  ? id: 10\n    description: Conflict\n  : []\n",
+        )
+        .unwrap();
+
+        // Load the main configuration and merge the includes
+        let main_config = || {
+            test_config(
+                "includes: [rooms/first.yaml, rooms/second.yaml]\nevent_set:\n  ? id: 10\n    description: Main Event\n  : []\n",
+            )
+        };
+        let mut yaml_config = main_config();
+        let (sources, errors) = IncludeSources::load(&main_path, &mut yaml_config);
+        assert_eq!(
+            vec![format!(
+                "Item 10 in {} is already defined",
+                directory.join("rooms/library.yaml").display()
+            )],
+            errors
+        );
+        assert_eq!(4, yaml_config.event_set.len());
+        assert_eq!(
+            "Main Event",
+            yaml_config
+                .event_set
+                .get_key_value(&ItemPair::new_unchecked(10, ""))
+                .unwrap()
+                .0
+                .description
+        );
+        assert_eq!(3, sources.get_paths().len());
+
+        // Add a new item and save the configuration back to the files
+        yaml_config
+            .event_set
+            .insert(ItemPair::new_unchecked(40, "New Event"), None);
+        assert!(sources.save(&mut yaml_config).is_empty());
+        assert_eq!(
+            vec![
+                PathBuf::from("rooms/first.yaml"),
+                PathBuf::from("rooms/second.yaml")
+            ],
+            yaml_config.includes
+        );
+        assert_eq!(2, yaml_config.event_set.len());
+        assert!(
+            yaml_config
+                .event_set
+                .contains_key(&ItemPair::new_unchecked(40, ""))
+        );
+
+        // Check that the included files kept their items
+        let first = fs::read_to_string(directory.join("rooms/first.yaml")).unwrap();
+        assert!(first.contains("library.yaml") && first.contains("First Room"));
+        let library = fs::read_to_string(directory.join("rooms/library.yaml")).unwrap();
+        assert!(library.contains("Shared Event"));
+
+        // Check that a file that includes itself is reported
+        fs::write(
+            directory.join("rooms/library.yaml"),
+            "includes: [first.yaml]\n",
+        )
+        .unwrap();
+        let mut yaml_config = main_config();
+        let (_, errors) = IncludeSources::load(&main_path, &mut yaml_config);
+        assert_eq!(
+            vec![format!(
+                "File includes itself: {}",
+                directory.join("rooms/first.yaml").display()
+            )],
+            errors
+        );
+
+        // Clean up the files
+        fs::remove_dir_all(&directory).unwrap_or(());
+    }
+
+    // Test upgrading an included file written with an older schema
+    #[test]
+    fn upgrade_old_include() {
+        // Create the main file and an included file without a schema
+        let directory = std::env::temp_dir().join("minerva_old_include");
+        fs::create_dir_all(&directory).unwrap();
+        let main_path = directory.join("main.yaml");
+        let include_path = directory.join("old.yaml");
+        let original = "event_set:\n  ? id: 20\n    description: Old Event\n  : [!NewScene {new_scene: {id: 10}}]\n";
+        fs::write(&include_path, original).unwrap();
+
+        // Load the main configuration and check the upgraded event
+        let mut yaml_config = test_config(
+            "schema: 2\nincludes: [old.yaml]\nevent_set:\n  ? id: 10\n    description: Main Event\n  : []\n",
+        );
+        let (sources, errors) = IncludeSources::load(&main_path, &mut yaml_config);
+        assert!(errors.is_empty());
+        assert_eq!(
+            Some(&Some(vec![EventAction::NewScene {
+                new_scene: ItemId::new_unchecked(10)
+            }])),
+            yaml_config.event_set.get(&ItemPair::new_unchecked(20, ""))
+        );
+
+        // Check that the original was backed up and the upgrade was saved
+        assert_eq!(
+            original,
+            fs::read_to_string(backup_path(&include_path, 0)).unwrap()
+        );
+        let upgraded = fs::read_to_string(&include_path).unwrap();
+        assert!(upgraded.contains("schema: 2") && upgraded.contains("!newScene"));
+
+        // Check that saving the configuration keeps the schema
+        assert!(sources.save(&mut yaml_config).is_empty());
+        let saved = fs::read_to_string(&include_path).unwrap();
+        assert!(saved.contains("schema: 2") && saved.contains("Old Event"));
+
+        // Clean up the files
+        fs::remove_dir_all(&directory).unwrap_or(());
+    }
+}
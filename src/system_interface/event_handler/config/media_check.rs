@@ -0,0 +1,226 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements the media checker to verify that all the media in
+//! the configuration is available before the media is cued. This includes
+//! local media files and the media channels of the media players.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import standard library features
+use std::fs::File;
+use std::path::Path;
+
+// Import FNV HashMap and HashSet
+use fnv::{FnvHashMap, FnvHashSet};
+
+/// A structure to verify the media referenced in the configuration.
+///
+pub struct MediaChecker;
+
+// Implement the media checker functions
+impl MediaChecker {
+    /// A function to check all the media in the media players and events
+    /// and return a report of any problems that were found.
+    ///
+    /// # Note
+    ///
+    /// Only local files (those that start with "file://") are checked. If a
+    /// media player does not specify any channels, all channels are allowed.
+    ///
+    pub fn check(media_players: &[MediaPlayer], events: &FnvHashMap<ItemId, Event>) -> MediaReport {
+        // Collect the valid channels (None if any channel is allowed)
+        let mut channels = Some(FnvHashSet::default());
+        for player in media_players.iter() {
+            // If the player does not restrict the channels, allow any channel
+            if player.channel_map.is_empty() {
+                channels = None;
+                break;
+            }
+
+            // Otherwise, add the channels to the list
+            if let Some(ref mut list) = channels {
+                list.extend(player.channel_map.keys().cloned());
+            }
+        }
+
+        // Check the loop media of each channel
+        let mut report = MediaReport::new();
+        for player in media_players.iter() {
+            for (channel, media_channel) in player.channel_map.iter() {
+                if let Some(ref uri) = media_channel.loop_media {
+                    MediaChecker::check_uri(&mut report, None, *channel, uri);
+                }
+            }
+        }
+
+        // Check every media cue in the events
+        for (event_id, event) in events.iter() {
+            for action in event.iter() {
+                // Collect the channel and media files from any media actions
+                let (channel, uris) = match action {
+                    CueMedia { cue } => {
+                        let mut uris = vec![&cue.uri];
+                        uris.extend(cue.loop_media.iter());
+                        (cue.channel, uris)
+                    }
+                    CueMediaPlaylist { playlist } => (
+                        playlist.channel,
+                        playlist.items.iter().map(|item| &item.uri).collect(),
+                    ),
+                    _ => continue,
+                };
+
+                // Check each media file
+                for uri in uris.iter() {
+                    MediaChecker::check_uri(&mut report, Some(*event_id), channel, uri);
+                }
+
+                // Check that the channel exists
+                if channels
+                    .as_ref()
+                    .is_some_and(|list| !list.contains(&channel))
+                {
+                    report.push(MediaIssue {
+                        event_id: Some(*event_id),
+                        channel,
+                        uri: uris.first().map(|uri| uri.to_string()).unwrap_or_default(),
+                        problem: MediaProblem::UnknownChannel,
+                    });
+                }
+            }
+        }
+
+        // Sort the report for consistent display
+        report.sort_by_key(|issue| (issue.event_id, issue.channel));
+
+        // Return the completed report
+        report
+    }
+
+    /// A helper function to check a single uri and add any problem to the report
+    ///
+    fn check_uri(report: &mut MediaReport, event_id: Option<ItemId>, channel: u32, uri: &str) {
+        // Ignore anything that isn't a local file
+        if !uri.starts_with("file://") {
+            return;
+        }
+
+        // Check that the file exists
+        let path = Path::new(&uri[7..]);
+        let problem = if !path.exists() {
+            MediaProblem::MissingFile
+
+        // Check that the file can be read
+        } else if !path.is_file() || File::open(path).is_err() {
+            MediaProblem::UnreadableFile
+
+        // Otherwise, there is no problem
+        } else {
+            return;
+        };
+
+        // Add the problem to the report
+        report.push(MediaIssue {
+            event_id,
+            channel,
+            uri: uri.into(),
+            problem,
+        });
+    }
+}
+
+// Tests of the media check module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test checking media files and channels
+    #[test]
+    fn check_media() {
+        // Create a media player with a single channel
+        let mut channel_map = ChannelMap::default();
+        channel_map.insert(
+            1,
+            MediaChannel {
+                video_frame: None,
+                audio_device: None,
+                loop_media: Some("file:///nonexistent/loop.mp4".into()),
+            },
+        );
+        let media_players = vec![MediaPlayer {
+            channel_map,
+            window_map: WindowMap::default(),
+            apollo_params: ApolloParams::default(),
+            backend: MediaBackend::Null,
+        }];
+
+        // Create events with valid and invalid media
+        let event1 = ItemId::new_unchecked(10);
+        let event2 = ItemId::new_unchecked(11);
+        let mut events = FnvHashMap::default();
+        events.insert(
+            event1,
+            vec![CueMedia {
+                cue: MediaCue {
+                    uri: format!("file://{}", file!()),
+                    channel: 1,
+                    loop_media: None,
+                    on_complete: None,
+                },
+            }],
+        );
+        events.insert(
+            event2,
+            vec![CueMedia {
+                cue: MediaCue {
+                    uri: "https://example.com/video.mp4".into(),
+                    channel: 2,
+                    loop_media: Some("file:///nonexistent/video.mp4".into()),
+                    on_complete: None,
+                },
+            }],
+        );
+
+        // Check the media and compare the report
+        let report = MediaChecker::check(&media_players, &events);
+        assert_eq!(
+            vec![
+                MediaIssue {
+                    event_id: None,
+                    channel: 1,
+                    uri: "file:///nonexistent/loop.mp4".into(),
+                    problem: MediaProblem::MissingFile,
+                },
+                MediaIssue {
+                    event_id: Some(event2),
+                    channel: 2,
+                    uri: "file:///nonexistent/video.mp4".into(),
+                    problem: MediaProblem::MissingFile,
+                },
+                MediaIssue {
+                    event_id: Some(event2),
+                    channel: 2,
+                    uri: "https://example.com/video.mp4".into(),
+                    problem: MediaProblem::UnknownChannel,
+                },
+            ],
+            report
+        );
+    }
+}
@@ -0,0 +1,277 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to upgrade configurations written with an older layout.
+//!
+//! Each configuration records the schema (layout) it was written with. When
+//! a configuration with an older schema is loaded, each migration is applied
+//! in order, one schema at a time, until the configuration matches the
+//! current schema. The migrations operate on the raw YAML so that no part of
+//! the configuration is lost before it matches the current structures.
+
+// Import standard library features
+use std::path::{Path, PathBuf};
+
+// Import YAML processing library
+use serde_yaml::value::{Tag, TaggedValue};
+use serde_yaml::{Mapping, Value};
+
+// Import anyhow features
+use anyhow::Result;
+
+/// The current schema of the configuration layout
+pub const CURRENT_SCHEMA: u32 = 2;
+
+/// A structure to describe one step to upgrade the configuration layout
+///
+struct Migration {
+    from: u32,                 // the schema upgraded by this step (to the next schema)
+    description: &'static str, // a description of the changes for the user
+    apply: fn(&mut Mapping),   // the function to change the raw configuration
+}
+
+/// Every migration, in order of the schema
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: 0,
+        description: "Add any configuration sections missing from older layouts",
+        apply: add_missing_sections,
+    },
+    Migration {
+        from: 1,
+        description: "Rename the event actions and their fields to camel case",
+        apply: camel_case_actions,
+    },
+];
+
+/// A function to return the schema of the raw configuration. Configurations
+/// without a schema predate schema versioning and are treated as schema 0.
+///
+pub fn schema_of(value: &Value) -> u32 {
+    value
+        .get("schema")
+        .and_then(|schema| schema.as_u64())
+        .unwrap_or(0) as u32
+}
+
+/// A function to upgrade the raw configuration to the current schema. The
+/// function returns a description of each migration that was applied, or an
+/// empty list if the configuration was already up to date.
+///
+/// # Errors
+///
+/// This function will raise an error if the configuration is not a mapping,
+/// if the configuration was written with a newer schema than this version of
+/// the software supports, or if no migration exists for an older schema. The
+/// configuration is unchanged when an error is raised.
+///
+pub fn migrate(value: &mut Value) -> Result<Vec<String>> {
+    // Check that the schema is supported
+    let schema = schema_of(value);
+    if schema > CURRENT_SCHEMA {
+        return Err(anyhow!(
+            "Configuration schema {} is newer than the supported schema {}",
+            schema,
+            CURRENT_SCHEMA
+        ));
+    }
+
+    // Check that every migration is available before changing anything
+    let mut steps = Vec::new();
+    for from in schema..CURRENT_SCHEMA {
+        match MIGRATIONS.iter().find(|migration| migration.from == from) {
+            Some(migration) => steps.push(migration),
+            None => return Err(anyhow!("No migration from configuration schema {}", from)),
+        }
+    }
+
+    // Apply each migration in order
+    let Some(mapping) = value.as_mapping_mut() else {
+        return Err(anyhow!("Configuration is not a mapping"));
+    };
+    let mut applied = Vec::new();
+    for migration in steps {
+        (migration.apply)(mapping);
+        mapping.insert("schema".into(), (migration.from + 1).into());
+        applied.push(format!(
+            "Schema {} to {}: {}",
+            migration.from,
+            migration.from + 1,
+            migration.description
+        ));
+    }
+
+    // Return the applied migrations
+    Ok(applied)
+}
+
+/// A function to return the location of the backup for a configuration file
+/// upgraded from the provided schema
+///
+pub fn backup_path(config_path: &Path, schema: u32) -> PathBuf {
+    PathBuf::from(format!("{}.schema{}.bak", config_path.display(), schema))
+}
+
+/// Schema 0 to 1: Add the sections that older configurations may not have
+/// included, with empty values
+///
+fn add_missing_sections(mapping: &mut Mapping) {
+    // Add the missing mappings
+    for section in [
+        "identifier",
+        "dmx_controllers",
+        "group_map",
+        "scene_map",
+        "status_map",
+        "event_set",
+        "user_styles",
+    ] {
+        if !mapping.contains_key(section) {
+            mapping.insert(section.into(), Value::Mapping(Mapping::new()));
+        }
+    }
+
+    // Add the missing lists
+    for section in ["media_players", "system_connections"] {
+        if !mapping.contains_key(section) {
+            mapping.insert(section.into(), Value::Sequence(Vec::new()));
+        }
+    }
+}
+
+/// Schema 1 to 2: Rename the tag of each event action and the fields of the
+/// action to camel case (e.g. `!NewScene { new_scene }` to
+/// `!newScene { newScene }`)
+///
+fn camel_case_actions(mapping: &mut Mapping) {
+    // Look through every event in the event set
+    let Some(Value::Mapping(event_set)) = mapping.get_mut("event_set") else {
+        return;
+    };
+    for (_, event) in event_set.iter_mut() {
+        // Skip empty events
+        let Value::Sequence(actions) = event else {
+            continue;
+        };
+
+        // Rename each action and the fields of the action
+        for action in actions.iter_mut() {
+            let Value::Tagged(tagged) = action else {
+                continue;
+            };
+            let TaggedValue { tag, value } = tagged.as_mut();
+            *tag = Tag::new(camel_case(tag.to_string().trim_start_matches('!')));
+            if let Value::Mapping(fields) = value {
+                *fields = fields
+                    .iter()
+                    .map(|(key, value)| match key.as_str() {
+                        Some(key) => (camel_case(key).into(), value.clone()),
+                        None => (key.clone(), value.clone()),
+                    })
+                    .collect();
+            }
+        }
+    }
+}
+
+/// A helper function to convert a snake case or pascal case name to camel case
+///
+fn camel_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut upper = false;
+    for (index, character) in name.chars().enumerate() {
+        if character == '_' {
+            upper = true;
+        } else if upper {
+            result.push(character.to_ascii_uppercase());
+            upper = false;
+        } else if index == 0 {
+            result.push(character.to_ascii_lowercase());
+        } else {
+            result.push(character);
+        }
+    }
+    result
+}
+
+// Tests of the migration module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::*;
+    use fnv::FnvHashMap;
+
+    // Test upgrading an older configuration and rejecting a newer one
+    #[test]
+    fn migrate_config() {
+        // Upgrade a configuration without a schema
+        let mut value: Value =
+            serde_yaml::from_str("version: 0.9.0\ndefault_scene:\n  id: 100\n").unwrap();
+        let applied = migrate(&mut value).unwrap();
+        assert_eq!(2, applied.len());
+        assert_eq!(CURRENT_SCHEMA, schema_of(&value));
+        assert!(value.get("event_set").is_some_and(|set| set.is_mapping()));
+        assert!(
+            value
+                .get("media_players")
+                .is_some_and(|set| set.is_sequence())
+        );
+
+        // Upgrade the event actions of a schema 1 configuration
+        let mut value: Value = serde_yaml::from_str(
+            "schema: 1\nevent_set:\n  ? id: 10\n  : [!NewScene {new_scene: {id: 11}}]\n  ? id: 11\n  : [!SelectEvent {status_id: {id: 12}, event_map: {}}, !CueEvent {event: {delay: null, event_id: {id: 10}}}]\n  ? id: 12\n  : null\n",
+        )
+        .unwrap();
+        assert_eq!(1, migrate(&mut value).unwrap().len());
+        let event_set: FnvHashMap<ItemId, Option<Event>> =
+            serde_yaml::from_value(value["event_set"].clone()).unwrap();
+        assert_eq!(
+            Some(&Some(vec![NewScene {
+                new_scene: ItemId::new_unchecked(11)
+            }])),
+            event_set.get(&ItemId::new_unchecked(10))
+        );
+        assert_eq!(
+            Some(&Some(vec![
+                SelectEvent {
+                    status_id: ItemId::new_unchecked(12),
+                    event_map: FnvHashMap::default(),
+                },
+                CueEvent {
+                    event: EventDelay::new(None, ItemId::new_unchecked(10)),
+                },
+            ])),
+            event_set.get(&ItemId::new_unchecked(11))
+        );
+
+        // Check that a current configuration is unchanged
+        let original = value.clone();
+        assert!(migrate(&mut value).unwrap().is_empty());
+        assert_eq!(original, value);
+
+        // Check that a newer configuration is rejected
+        let mut value: Value = serde_yaml::from_str("schema: 999\n").unwrap();
+        assert!(migrate(&mut value).is_err());
+        assert_eq!(999, schema_of(&value));
+
+        // Check the backup location
+        assert_eq!(
+            PathBuf::from("show.yaml.schema0.bak"),
+            backup_path(Path::new("show.yaml"), 0)
+        );
+    }
+}
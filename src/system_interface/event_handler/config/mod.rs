@@ -23,13 +23,33 @@
 use crate::definitions::*;
 
 // Define private submodules
+mod batch;
+mod cues;
+mod diff;
+mod includes;
+mod media_check;
+mod migration;
+mod stable;
 mod status;
+mod validation;
 
 // Import the relevant structures into the correct namespace
+use self::batch::check_batch;
+use self::cues::{cue_modifications, cues_csv};
+use self::diff::{diff_configs, merge_modifications, read_yaml_config};
+pub use self::includes::IncludeSources;
+use self::media_check::MediaChecker;
+use self::migration::{CURRENT_SCHEMA, backup_path, migrate, schema_of};
+use self::stable::{item_descriptions, to_stable_yaml};
 use self::status::StatusHandler;
 
+// Reexport the validation function
+pub use self::cues::{export_cues_file, import_cues_file};
+pub use self::diff::{diff_files, merge_files};
+pub use self::validation::validate_config;
+
 // Import standard library features
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 // Import tokio features
 use tokio::fs::File;
@@ -156,19 +176,28 @@ impl BackgroundThread {
 /// serialization and deserialization for the program configuration file.
 /// This structure is saved to the external configuration file.
 ///
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 struct YamlConfig {
-    version: String,        // a version tag to warn the user of incompatible versions
+    version: String, // a version tag to warn the user of incompatible versions
+    #[serde(default)]
+    schema: u32, // the layout of the configuration, used to upgrade older configurations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    includes: Vec<PathBuf>, // the files to merge into this configuration, relative to this file
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    describe_items: bool, // a flag to write the item descriptions as comments when saved
     identifier: Identifier, // unique identifier for the controller instance, if specified
     server_location: Option<String>, // the location of the backup server, if specified
     dmx_controllers: DmxControllers, // the details of the dmx controller(s)
     media_players: Vec<MediaPlayer>, // the details of the media player(s)
     system_connections: ConnectionSet, // the type of connection(s) to the underlying system
     background_process: Option<BackgroundProcess>, // an option background process to run
-    default_scene: ItemId,  // the starting scene for the configuration
+    state_sync: Option<SyncParams>, // the statuses and scenes to share with other instances, if specified
+    statistics: Option<StatisticsParams>, // the milestone and hint events for the room statistics, if specified
+    hint_penalty: Option<HintPenalty>,    // the penalty for each hint delivered, if specified
+    default_scene: ItemId,                // the starting scene for the configuration
     group_map: FnvHashMap<ItemId, Group>, // hash map of all availble groups
     scene_map: FnvHashMap<ItemId, Scene>, // hash map of all availble scenes
-    status_map: StatusMap,  // hash map of the default game status
+    status_map: StatusMap,                // hash map of the default game status
     event_set: FnvHashMap<ItemPair, Option<Event>>, // hash map of all the item pairs and events
     user_styles: StyleMap, // A string representing arbitrary css for styling the user and edit interfaces
 }
@@ -185,16 +214,21 @@ pub struct Config {
     media_players: Vec<MediaPlayer>, // the details of the media player(s)
     server_location: Option<String>, // the location of the backup server, if specified
     background_thread: Option<BackgroundThread>, // a copy of the background process info
-    default_scene: ItemId,  // the starting scene for the configuration
-    current_scene: ItemId,  // identifier for the current scene
+    state_sync: Option<SyncParams>, // the statuses and scenes to share with other instances, if specified
+    statistics: Option<StatisticsParams>, // the milestone and hint events for the room statistics, if specified
+    hint_penalty: Option<HintPenalty>,    // the penalty for each hint delivered, if specified
+    default_scene: ItemId,                // the starting scene for the configuration
+    current_scene: ItemId,                // identifier for the current scene
     group_map: FnvHashMap<ItemId, Group>, // hash map of all availble groups
     scene_map: FnvHashMap<ItemId, Scene>, // hash map of all availble scenes
-    status_handler: StatusHandler, // status handler for the current game status
+    status_handler: StatusHandler,        // status handler for the current game status
     event_set: FnvHashMap<ItemId, Event>, // hash map of all the events
-    index_access: IndexAccess, // access point to the item index
-    style_access: StyleAccess, // access point to the style sheet
-    interface_send: InterfaceSend, // sending line for updates to the user interface
-    limited_send: LimitedSend, // sending line for limited updates
+    index_access: IndexAccess,            // access point to the item index
+    style_access: StyleAccess,            // access point to the style sheet
+    interface_send: InterfaceSend,        // sending line for updates to the user interface
+    limited_send: LimitedSend,            // sending line for limited updates
+    includes: IncludeSources, // the included file of each item, if not from the main file
+    describe_items: bool,     // a flag to write the item descriptions as comments when saved
 }
 
 // Implement key features for the configuration
@@ -224,6 +258,9 @@ impl Config {
             media_players: Vec::new(),
             server_location: None,
             background_thread: None,
+            state_sync: None,
+            statistics: None,
+            hint_penalty: None,
             default_scene: ItemId::all_stop(),
             current_scene: ItemId::all_stop(),
             group_map: FnvHashMap::default(),
@@ -234,6 +271,8 @@ impl Config {
             style_access,
             interface_send,
             limited_send,
+            includes: IncludeSources::default(),
+            describe_items: false,
         }
     }
 
@@ -248,11 +287,20 @@ impl Config {
     /// It is highly recommended that you use the provided configuration
     /// generation/modification tool to create the configuration file.
     ///
+    /// Any files included by the configuration are loaded relative to the
+    /// provided config path and merged into the configuration.
+    ///
+    /// If the configuration was written with an older schema, it is upgraded
+    /// to the current schema. The original file is copied to a backup and
+    /// the upgraded configuration is written in its place.
+    ///
     /// # Errors
     ///
     /// This function will raise an error if it is unable to parse the
-    /// configuration file and will raise a warning if there is an internal
-    /// consistency problem with the provided configuration.
+    /// configuration file or any included file, if an item is defined in
+    /// more than one file, or if the configuration cannot be upgraded (or
+    /// the original cannot be backed up). This function will raise a warning if there is an
+    /// internal consistency problem with the provided configuration.
     ///
     /// Like all EventHandler functions and methods, this function will fail
     /// gracefully by notifying of any errors on the update line and returning
@@ -264,6 +312,7 @@ impl Config {
         interface_send: InterfaceSend,
         limited_send: LimitedSend,
         mut config_file: File,
+        config_path: &Path,
     ) -> Result<Config> {
         // Try to read from the configuration file
         let mut config_string = String::new();
@@ -275,8 +324,27 @@ impl Config {
             }
         }
 
-        // Try to parse the configuration file
-        let yaml_config: YamlConfig = match serde_yaml::from_str(config_string.as_str()) {
+        // Try to parse the raw configuration file
+        let mut value: serde_yaml::Value = match serde_yaml::from_str(config_string.as_str()) {
+            Ok(value) => value,
+            Err(error) => {
+                error!("Unable to parse configuration file: {}.", error);
+                return Err(anyhow!("Unable to parse configuration file: {}", error));
+            }
+        };
+
+        // Upgrade the configuration if it has an older schema
+        let schema = schema_of(&value);
+        let applied = match migrate(&mut value) {
+            Ok(applied) => applied,
+            Err(error) => {
+                error!("Unable to upgrade configuration file: {}.", error);
+                return Err(anyhow!("Unable to upgrade configuration file: {}", error));
+            }
+        };
+
+        // Try to parse the configuration structure
+        let mut yaml_config: YamlConfig = match serde_yaml::from_value(value.clone()) {
             Ok(config) => config,
             Err(error) => {
                 error!("Unable to parse configuration file: {}.", error);
@@ -284,6 +352,20 @@ impl Config {
             }
         };
 
+        // If the configuration was upgraded, backup the original and save the upgrade
+        if !applied.is_empty() {
+            Config::save_upgrade(config_path, schema, &config_string, &value, &applied).await?;
+        }
+
+        // Merge any included files, failing if there are any conflicts
+        let (includes, errors) = IncludeSources::load(config_path, &mut yaml_config);
+        if !errors.is_empty() {
+            for error in errors.iter() {
+                error!("{}.", error);
+            }
+            return Err(anyhow!("Unable to load included files."));
+        }
+
         // Check the version id and warn the user if they differ
         let version = env!("CARGO_PKG_VERSION");
         if &yaml_config.version != version {
@@ -331,6 +413,14 @@ impl Config {
         let status_map = yaml_config.status_map;
         Config::verify_config(&scene_map, &group_map, &status_map, &item_index, &event_set).await; // FIXME check groups as well
 
+        // Verify that the media is available
+        for issue in MediaChecker::check(&yaml_config.media_players, &event_set) {
+            warn!(
+                "Media problem on channel {} ({:?}): {}.",
+                issue.channel, issue.problem, issue.uri
+            );
+        }
+
         // Load the item index
         index_access.send_index(item_index).await;
 
@@ -362,6 +452,9 @@ impl Config {
             server_location: yaml_config.server_location,
             media_players: yaml_config.media_players,
             background_thread,
+            state_sync: yaml_config.state_sync,
+            statistics: yaml_config.statistics,
+            hint_penalty: yaml_config.hint_penalty,
             default_scene: yaml_config.default_scene,
             current_scene,
             group_map,
@@ -372,9 +465,57 @@ impl Config {
             style_access,
             interface_send,
             limited_send,
+            includes,
+            describe_items: yaml_config.describe_items,
         })
     }
 
+    /// A helper function to save a configuration upgraded from an older
+    /// schema, after copying the original file to a backup.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if the original file could not be
+    /// backed up. If the upgraded configuration could not be written, this
+    /// function will only raise a warning (the upgrade is repeated the next
+    /// time the file is loaded).
+    ///
+    async fn save_upgrade(
+        config_path: &Path,
+        schema: u32,
+        original: &str,
+        value: &serde_yaml::Value,
+        applied: &[String],
+    ) -> Result<()> {
+        // Describe each step of the upgrade
+        for step in applied.iter() {
+            info!("Upgrading configuration: {}.", step);
+        }
+
+        // Copy the original file to the backup
+        let backup = backup_path(config_path, schema);
+        if let Err(error) = tokio::fs::write(&backup, original).await {
+            error!("Unable to backup configuration before upgrade: {}.", error);
+            return Err(anyhow!(
+                "Unable to backup configuration before upgrade: {}",
+                error
+            ));
+        }
+        info!("Original configuration saved to {}.", backup.display());
+
+        // Write the upgraded configuration in place of the original, in the
+        // same stable order as a saved configuration
+        match to_stable_yaml(value, None) {
+            Ok(upgraded) => {
+                if let Err(error) = tokio::fs::write(config_path, upgraded).await {
+                    warn!("Unable to save upgraded configuration: {}.", error);
+                }
+            }
+            Err(error) => warn!("Unable to save upgraded configuration: {}.", error),
+        }
+        Ok(())
+    }
+
     /// A method to return a copy of the background process
     ///
     pub fn get_background_process(&self) -> Option<BackgroundProcess> {
@@ -385,6 +526,25 @@ impl Config {
         }
     }
 
+    /// A method to return the location of every included file
+    ///
+    pub fn get_include_paths(&self) -> Vec<PathBuf> {
+        self.includes.get_paths()
+    }
+
+    /// A method to return a copy of the included file of each item
+    ///
+    pub fn get_includes(&self) -> IncludeSources {
+        self.includes.clone()
+    }
+
+    /// A method to replace the included file of each item (for example, after
+    /// recovering a configuration that was written to a single file)
+    ///
+    pub fn set_includes(&mut self, includes: IncludeSources) {
+        self.includes = includes;
+    }
+
     /// A method to return a copy of the system connections
     ///
     pub fn get_connections(&self) -> ConnectionSet {
@@ -441,12 +601,38 @@ impl Config {
         self.media_players.clone()
     }
 
+    /// A method to check all the media files and channels and return a report
+    /// of any problems
+    ///
+    pub fn get_media_report(&self) -> MediaReport {
+        MediaChecker::check(&self.media_players, &self.event_set)
+    }
+
     /// A method to return the backup server location
     ///
     pub fn get_server_location(&self) -> Option<String> {
         self.server_location.clone()
     }
 
+    /// A method to return a copy of the shared statuses and scenes, if specified
+    ///
+    pub fn get_state_sync(&self) -> Option<SyncParams> {
+        self.state_sync.clone()
+    }
+
+    /// A method to return a copy of the milestone and hint events for the room
+    /// statistics (empty if not specified)
+    ///
+    pub fn get_statistics(&self) -> StatisticsParams {
+        self.statistics.clone().unwrap_or_default()
+    }
+
+    /// A method to return the penalty for each hint delivered, if specified
+    ///
+    pub fn get_hint_penalty(&self) -> Option<HintPenalty> {
+        self.hint_penalty.clone()
+    }
+
     /// A method to return a status from the status handler.
     ///
     pub fn get_status(&self, item_id: &ItemId) -> Option<Status> {
@@ -603,6 +789,28 @@ impl Config {
         self.default_scene = parameters.default_scene;
     }
 
+    /// A method to return the other settings of the configuration
+    ///
+    pub async fn get_settings(&self) -> ConfigSettings {
+        ConfigSettings {
+            state_sync: self.state_sync.clone(),
+            statistics: self.statistics.clone(),
+            hint_penalty: self.hint_penalty.clone(),
+            user_styles: self.style_access.get_all_rules().await,
+            describe_items: self.describe_items,
+        }
+    }
+
+    /// A method to update the other settings of the configuration
+    ///
+    pub async fn save_settings(&mut self, settings: ConfigSettings) {
+        self.state_sync = settings.state_sync;
+        self.statistics = settings.statistics;
+        self.hint_penalty = settings.hint_penalty;
+        self.style_access.send_styles(settings.user_styles).await;
+        self.describe_items = settings.describe_items;
+    }
+
     /// A method to select a scene map from existing configuration based on the
     /// provided scene id.
     ///
@@ -637,6 +845,23 @@ impl Config {
         }
     }
 
+    /// A method to send a hint to the limited interface clients and notify
+    /// the user interface
+    ///
+    pub async fn send_hint(&self, number: u32, message: String) {
+        // Notify the user interface
+        self.interface_send
+            .send(InterfaceUpdate::Notify {
+                message: format!("Hint {} delivered: {}", number, message),
+            })
+            .await;
+
+        // Send the hint to the limited interface
+        self.limited_send
+            .send(LimitedUpdate::DeliverHint { number, message })
+            .await;
+    }
+
     /// A method to modify a status state within the current scene based
     /// on the provided status id and new state. Method returns the new state or
     /// None. None is returned either because
@@ -997,6 +1222,21 @@ impl Config {
                         }
                     }
 
+                    CueMedia { cue } if cue.on_complete == Some(item_id) => {
+                        is_broken = true;
+                        break;
+                    }
+
+                    CueMediaPlaylist { playlist }
+                        if playlist
+                            .items
+                            .iter()
+                            .any(|item| item.event == Some(item_id)) =>
+                    {
+                        is_broken = true;
+                        break;
+                    }
+
                     NewScene { new_scene } => {
                         if new_scene == &item_id {
                             is_broken = true;
@@ -1107,19 +1347,82 @@ impl Config {
         }
     }
 
-    /// A method to write the current configuration to a file.
+    /// A method to check a batch of modifications against the configuration
+    /// that would result from applying the whole batch. The method returns
+    /// every new error in the resulting configuration, attributed to the
+    /// modification that introduced it, or an empty list if the batch is
+    /// valid.
+    ///
+    /// # Notes
+    ///
+    /// This method does not change the configuration. Any errors already
+    /// present in the configuration are ignored.
+    ///
+    pub async fn check_modifications(
+        &self,
+        modifications: &[Modification],
+    ) -> Vec<ModificationError> {
+        check_batch(self.to_yaml_config().await, modifications)
+    }
+
+    /// A method to compare the current configuration with the provided
+    /// configuration file and return every difference between them
     ///
     /// # Errors
     ///
-    /// This function will raise an error if the current configuration is broken
-    /// or the provided file was not usable. This usually indicates a problem
-    /// the provided file type.
+    /// This method will raise an error if the file could not be read.
     ///
-    /// Like all EventHandler functions and methods, this method will fail
-    /// gracefully by notifying of errors on the update line and making no
-    /// modifications to the file.
+    pub async fn diff_with(&self, path: &Path) -> Result<Vec<ConfigChange>> {
+        let other = read_yaml_config(path)?;
+        Ok(diff_configs(&self.to_yaml_config().await, &other))
+    }
+
+    /// A method to return the modifications that merge the selected changes
+    /// from the provided configuration file into the current configuration
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error if the file could not be read.
+    ///
+    pub fn merge_from(&self, path: &Path, changes: &[ConfigChange]) -> Result<Vec<Modification>> {
+        let other = read_yaml_config(path)?;
+        Ok(merge_modifications(&other, changes))
+    }
+
+    /// A method to return the modifications that import the cue list in the
+    /// provided CSV file into the current configuration, along with the
+    /// number of cues
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error if the file could not be read or if
+    /// any row of the cue list is not valid.
+    ///
+    pub async fn import_cues(&self, path: &Path) -> Result<(usize, Vec<Modification>)> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| anyhow!("Unable to read {}: {}", path.display(), error))?;
+        cue_modifications(&self.to_yaml_config().await, &text)
+    }
+
+    /// A method to export the items and events of the current configuration
+    /// to a cue list in the provided CSV file. The method returns the number
+    /// of cues exported.
     ///
-    pub async fn to_config(&self, mut config_file: File) {
+    /// # Errors
+    ///
+    /// This method will raise an error if the file could not be written.
+    ///
+    pub async fn export_cues(&self, path: &Path) -> Result<usize> {
+        let (count, text) = cues_csv(&self.to_yaml_config().await);
+        std::fs::write(path, text)
+            .map_err(|error| anyhow!("Unable to write {}: {}", path.display(), error))?;
+        Ok(count)
+    }
+
+    /// A helper method to assemble the current configuration into the
+    /// structure of the configuration file
+    ///
+    async fn to_yaml_config(&self) -> YamlConfig {
         // Assemble the event set from the item index and events
         let mut item_index = self.index_access.get_all_pairs().await;
         let mut event_set = FnvHashMap::default();
@@ -1143,24 +1446,87 @@ impl Config {
         let user_styles = self.style_access.get_all_rules().await;
 
         // Create a YAML config from the elements
-        let yaml_config = YamlConfig {
+        YamlConfig {
             version: env!("CARGO_PKG_VERSION").into(),
+            schema: CURRENT_SCHEMA,
+            includes: Vec::new(),
+            describe_items: self.describe_items,
             identifier: self.get_identifier(),
             server_location: self.server_location.clone(),
             system_connections: self.get_connections(),
             dmx_controllers: self.dmx_controllers.clone(),
             media_players: self.media_players.clone(),
             background_process: self.get_background_process(),
+            state_sync: self.state_sync.clone(),
+            statistics: self.statistics.clone(),
+            hint_penalty: self.hint_penalty.clone(),
             default_scene: self.default_scene,
             group_map: self.group_map.clone(),
             scene_map: self.scene_map.clone(),
             status_map: self.status_handler.get_map(),
             event_set,
             user_styles,
-        };
+        }
+    }
+
+    /// A method to write the current configuration to a file.
+    ///
+    /// # Notes
+    ///
+    /// Any items loaded from an included file are written back to that file,
+    /// in its original location. New items are written to the provided file.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if the current configuration is broken
+    /// or the provided file was not usable. This usually indicates a problem
+    /// the provided file type.
+    ///
+    /// Like all EventHandler functions and methods, this method will fail
+    /// gracefully by notifying of errors on the update line and making no
+    /// modifications to the file.
+    ///
+    pub async fn to_config(&self, config_file: File) {
+        // Assemble the configuration
+        let mut yaml_config = self.to_yaml_config().await;
+
+        // Write the included items back to their files
+        for error in self.includes.save(&mut yaml_config) {
+            error!("{}.", error);
+        }
+
+        // Write the configuration to the file
+        Config::write_yaml_config(&yaml_config, config_file).await;
+    }
+
+    /// A method to write the current configuration to an autosave file.
+    ///
+    /// # Notes
+    ///
+    /// Unlike to_config, every item (including any items loaded from an
+    /// included file) is written to the provided file and the included files
+    /// are left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Like all EventHandler functions and methods, this method will fail
+    /// gracefully by notifying of errors on the update line.
+    ///
+    pub async fn to_autosave(&self, autosave_file: File) {
+        // Assemble and write the configuration
+        let yaml_config = self.to_yaml_config().await;
+        Config::write_yaml_config(&yaml_config, autosave_file).await;
+    }
 
+    /// A helper function to write the provided configuration to a file, in a
+    /// stable order (with the item descriptions as comments, if requested)
+    ///
+    async fn write_yaml_config(yaml_config: &YamlConfig, mut config_file: File) {
         // Try to parse the configuration
-        let config_string = match serde_yaml::to_string(&yaml_config) {
+        let descriptions = yaml_config
+            .describe_items
+            .then(|| item_descriptions(&yaml_config.event_set));
+        let config_string = match to_stable_yaml(yaml_config, descriptions.as_ref()) {
             Ok(config_string) => config_string,
             Err(error) => {
                 error!("Unable to parse current configuration: {}.", error);
@@ -1410,6 +1776,40 @@ impl Config {
                             }
                         }
                     }
+
+                    // If there is a completion event, verify that it exists
+                    if let Some(ref event_id) = cue.on_complete
+                        && !event_list.contains_key(event_id)
+                    {
+                        warn!("Event contains invalid media complete event: {}.", event_id);
+                        return false;
+                    }
+                }
+
+                // If there is a media playlist to cue, check the files and events
+                CueMediaPlaylist { playlist } => {
+                    // Verify that the playlist isn't empty
+                    if playlist.items.is_empty() {
+                        warn!("Event contains empty media playlist.");
+                        return false;
+                    }
+
+                    // Check each item in the playlist
+                    for item in playlist.items.iter() {
+                        // If the item is referencing a local file
+                        if item.uri.starts_with("file://") && !Path::new(&item.uri[7..]).exists() {
+                            warn!("Media file missing for media playlist: {}.", item.uri);
+                            return false;
+                        }
+
+                        // If there is an item event, verify that it exists
+                        if let Some(ref event_id) = item.event
+                            && !event_list.contains_key(event_id)
+                        {
+                            warn!("Event contains invalid media playlist event: {}.", event_id);
+                            return false;
+                        }
+                    }
                 }
 
                 // If there is media to adjust, assume validity
@@ -1428,6 +1828,13 @@ impl Config {
                 &SaveData { .. } => (),
                 &SendData { .. } => (),
 
+                // If there is a session to start or end, assume validity
+                &StartSession {} => (),
+                &EndSession { .. } => (),
+
+                // If there is a hint to deliver, assume validity (scenes may have no hints)
+                &DeliverHint {} => (),
+
                 // If there is a select event, verify the components of the event
                 &SelectEvent {
                     ref status_id,
@@ -1485,6 +1892,25 @@ impl Config {
     }
 }
 
+/// A helper function to create a small configuration for the tests of the
+/// submodules. The provided yaml sections replace the empty defaults.
+///
+#[cfg(test)]
+fn test_config(sections: &str) -> YamlConfig {
+    // Start from an empty configuration with a default scene
+    let mut config: serde_yaml::Value = serde_yaml::from_str(
+        "version: test\nidentifier: {}\ndmx_controllers: {}\nmedia_players: []\nsystem_connections: []\ndefault_scene: {id: 10}\ngroup_map: {}\nscene_map: {}\nstatus_map: {}\nevent_set: {}\nuser_styles: {}\n",
+    )
+    .unwrap();
+
+    // Replace the provided sections
+    let sections: serde_yaml::Mapping = serde_yaml::from_str(sections).unwrap();
+    if let Some(config) = config.as_mapping_mut() {
+        config.extend(sections);
+    }
+    serde_yaml::from_value(config).unwrap()
+}
+
 // Tests of the scene module
 #[cfg(test)]
 mod tests {
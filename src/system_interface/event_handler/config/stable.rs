@@ -0,0 +1,276 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to write configuration files in a stable order, so that saving
+//! an unchanged configuration produces an identical file.
+//!
+//! Maps keyed by item id are sorted by id and the user styles are sorted by
+//! selector. The fields of each structure keep their declared order. When
+//! requested, the description of each item is added as a comment after any
+//! line that refers to the item by id alone.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import FNV HashMap
+use fnv::FnvHashMap;
+
+// Import Serde features
+use serde::ser::{SerializeMap, Serializer};
+
+// Import YAML processing library
+use serde_yaml::value::Tag;
+use serde_yaml::{self, Mapping, Value};
+
+/// The top-level sections of the configuration that are not item ids
+const UNDESCRIBED_SECTIONS: [&str; 1] = ["identifier:"];
+
+/// A function to write the provided content as yaml in a stable order, with
+/// the provided item descriptions added as comments, if any
+///
+/// # Errors
+///
+/// This function will raise an error if the content could not be converted
+/// to yaml.
+///
+pub(super) fn to_stable_yaml<T: Serialize>(
+    content: &T,
+    descriptions: Option<&FnvHashMap<u32, String>>,
+) -> Result<String, serde_yaml::Error> {
+    // Convert the content and sort each map
+    let mut value = serde_yaml::to_value(content)?;
+    sort_value(&mut value);
+    if let Some(Value::Mapping(styles)) = value.get_mut("user_styles") {
+        let mut entries: Vec<(Value, Value)> = std::mem::take(styles).into_iter().collect();
+        entries.sort_by(|first, second| first.0.as_str().cmp(&second.0.as_str()));
+        *styles = entries.into_iter().collect();
+    }
+
+    // Write the content and add the descriptions, if requested
+    let string = serde_yaml::to_string(&StableValue(&value))?;
+    Ok(match descriptions {
+        Some(descriptions) => add_descriptions(&string, descriptions),
+        None => string,
+    })
+}
+
+/// A helper function to return a map of every item id to its description,
+/// from the provided event set
+///
+pub(super) fn item_descriptions(
+    event_set: &FnvHashMap<ItemPair, Option<Event>>,
+) -> FnvHashMap<u32, String> {
+    event_set
+        .keys()
+        .map(|pair| (pair.id(), pair.description.replace(['\n', '\r'], " ")))
+        .collect()
+}
+
+/// A helper struct to write a yaml value
+///
+/// # Notes
+///
+/// The yaml serializer treats any map with a single entry as a possible
+/// enum variant, which fails when the key of that entry is itself a map
+/// (e.g. a scene map with one scene). This struct writes every map without
+/// a length to avoid the check.
+///
+struct StableValue<'a>(&'a Value);
+
+// Implement serialization for the stable value
+impl Serialize for StableValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            // Write maps without a length
+            Value::Mapping(mapping) => {
+                let mut map = serializer.serialize_map(None)?;
+                for (key, value) in mapping {
+                    map.serialize_entry(&StableValue(key), &StableValue(value))?;
+                }
+                map.end()
+            }
+
+            // Write each element of a sequence
+            Value::Sequence(sequence) => serializer.collect_seq(sequence.iter().map(StableValue)),
+
+            // Write tagged enum variants with their tag
+            Value::Tagged(tagged) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(&StableTag(&tagged.tag), &StableValue(&tagged.value))?;
+                map.end()
+            }
+
+            // Write other values unchanged
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+/// A helper struct to write the tag of an enum variant
+///
+struct StableTag<'a>(&'a Tag);
+
+// Implement serialization for the stable tag
+impl Serialize for StableTag<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self.0)
+    }
+}
+
+/// A helper function to sort every map keyed by item id, recursively
+///
+fn sort_value(value: &mut Value) {
+    match value {
+        // Sort the map if every key is an item id
+        Value::Mapping(mapping) => {
+            for (_, inner) in mapping.iter_mut() {
+                sort_value(inner);
+            }
+            if mapping.keys().all(|key| key_id(key).is_some()) {
+                let mut entries: Vec<(Value, Value)> =
+                    std::mem::take(mapping).into_iter().collect();
+                entries.sort_by_key(|(key, _)| key_id(key));
+                *mapping = entries.into_iter().collect::<Mapping>();
+            }
+        }
+
+        // Sort inside each element of a sequence
+        Value::Sequence(sequence) => {
+            for inner in sequence.iter_mut() {
+                sort_value(inner);
+            }
+        }
+
+        // Sort inside tagged enum variants
+        Value::Tagged(tagged) => sort_value(&mut tagged.value),
+
+        // Leave other values unchanged
+        _ => (),
+    }
+}
+
+/// A helper function to return the id of a map key, if the key is a number,
+/// an item id, or an item pair
+///
+fn key_id(key: &Value) -> Option<u64> {
+    match key {
+        Value::Number(number) => number.as_u64(),
+        Value::Mapping(mapping) => mapping.get("id").and_then(|id| id.as_u64()),
+        _ => None,
+    }
+}
+
+/// A helper function to add the description of each item as a comment after
+/// any line that refers to the item by id alone
+///
+fn add_descriptions(string: &str, descriptions: &FnvHashMap<u32, String>) -> String {
+    let lines: Vec<&str> = string.lines().collect();
+    let mut result = String::with_capacity(string.len());
+    let mut section = "";
+    for (index, line) in lines.iter().enumerate() {
+        result += line;
+
+        // Track the current top-level section
+        if !line.starts_with([' ', '-', '?', ':']) {
+            section = line;
+        }
+
+        // Look for a line with only an id, not followed by a description
+        let content = line
+            .trim_start()
+            .trim_start_matches(['-', '?', ' '])
+            .strip_prefix("id: ");
+        let is_described = lines
+            .get(index + 1)
+            .map(|next| next.trim_start().starts_with("description:"))
+            .unwrap_or(false);
+        if let Some(content) = content
+            && !is_described
+            && !UNDESCRIBED_SECTIONS.contains(&section)
+            && let Ok(id) = content.parse::<u32>()
+            && let Some(description) = descriptions.get(&id)
+        {
+            result += &format!("  # {}", description);
+        }
+        result += "\n";
+    }
+    result
+}
+
+// Tests of the stable module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A simplified configuration to write
+    #[derive(Serialize)]
+    struct TestConfig {
+        scene_map: FnvHashMap<ItemId, Scene>,
+        event_set: FnvHashMap<ItemPair, Option<Event>>,
+    }
+
+    // Test that the output doesn't depend on the insertion order and that the
+    // descriptions don't change the content
+    #[test]
+    fn stable_output() {
+        // Create the same configuration in two different orders
+        let ids = [300, 7, 45, 12, 1000, 2];
+        let config = |ids: Vec<u32>| {
+            let mut scene_map = FnvHashMap::default();
+            scene_map.insert(
+                ItemId::new_unchecked(7),
+                Scene {
+                    items: ids.iter().map(|id| ItemId::new_unchecked(*id)).collect(),
+                    groups: Default::default(),
+                    key_map: None,
+                    hints: Vec::new(),
+                },
+            );
+            let event_set = ids
+                .iter()
+                .map(|id| (ItemPair::new_unchecked(*id, &format!("Item {}", id)), None))
+                .collect();
+            TestConfig {
+                scene_map,
+                event_set,
+            }
+        };
+        let first = config(ids.to_vec());
+        let second = config(ids.iter().rev().copied().collect());
+
+        // Check that the output is identical and sorted
+        let descriptions = item_descriptions(&first.event_set);
+        let first_string = to_stable_yaml(&first, Some(&descriptions)).unwrap();
+        let second_string = to_stable_yaml(&second, Some(&descriptions)).unwrap();
+        assert_eq!(first_string, second_string);
+        let event_set = &first_string[first_string.find("event_set:").unwrap()..];
+        let positions: Vec<usize> = [2, 7, 12, 45, 300, 1000]
+            .iter()
+            .map(|id| event_set.find(&format!("id: {}\n", id)).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // Check that the scene items are described and the content is unchanged
+        assert!(first_string.contains("- id: 45  # Item 45\n"));
+        let plain_string = to_stable_yaml(&first, None).unwrap();
+        let parsed: Value = serde_yaml::from_str(&first_string).unwrap();
+        assert_eq!(
+            parsed,
+            serde_yaml::from_str::<Value>(&plain_string).unwrap()
+        );
+    }
+}
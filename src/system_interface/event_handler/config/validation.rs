@@ -0,0 +1,637 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This module implements the full validation of a configuration without
+//! loading it. Unlike the checks performed while loading, the validation
+//! continues past the first problem and collects every problem into a report.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import the relevant structures into the correct namespace
+use super::YamlConfig;
+use super::includes::IncludeSources;
+use super::media_check::MediaChecker;
+use super::migration::migrate;
+
+// Import standard library features
+use std::path::Path;
+
+// Import FNV HashMap and HashSet
+use fnv::{FnvHashMap, FnvHashSet};
+
+// Import YAML processing library
+use serde_yaml::{self, Value};
+
+/// A function to validate the text of a configuration file and return a
+/// report of every problem found. The file name labels the report and is
+/// used to find any included files.
+///
+/// # Notes
+///
+/// The validation checks the groups, scenes, events, statuses, key maps,
+/// media channels, DMX universes, and duplicate ids, and warns of events
+/// that can never be triggered and items that are never used.
+///
+pub fn validate_config(file: &str, config_string: &str) -> ValidationReport {
+    // Try to parse the raw configuration
+    let mut report = ValidationReport::new(file);
+    let mut value: Value = match serde_yaml::from_str(config_string) {
+        Ok(value) => value,
+        Err(error) => {
+            report.error(
+                None,
+                format!("Unable to parse configuration file: {}", error),
+            );
+            return report;
+        }
+    };
+
+    // Check for duplicate ids (these are silently merged when loaded)
+    check_duplicates(&mut report, &value);
+
+    // Upgrade the configuration if it has an older schema (without saving)
+    match migrate(&mut value) {
+        Ok(applied) => {
+            for step in applied {
+                report.warning(None, format!("Configuration will be upgraded: {}", step));
+            }
+        }
+        Err(error) => {
+            report.error(None, format!("Unable to upgrade configuration: {}", error));
+            return report;
+        }
+    }
+
+    // Try to parse the configuration structure
+    let mut yaml_config: YamlConfig = match serde_yaml::from_value(value) {
+        Ok(config) => config,
+        Err(error) => {
+            report.error(None, format!("Invalid configuration: {}", error));
+            return report;
+        }
+    };
+
+    // Merge any included files, reporting any conflicts
+    let (_, errors) = IncludeSources::load(Path::new(file), &mut yaml_config);
+    for error in errors {
+        report.error(None, error);
+    }
+
+    // Check the version
+    let version = env!("CARGO_PKG_VERSION");
+    if yaml_config.version != version {
+        report.warning(
+            None,
+            format!(
+                "Version of configuration ({}) does not match software version ({})",
+                yaml_config.version, version
+            ),
+        );
+    }
+
+    // Validate the rest of the configuration
+    Validator::new(&yaml_config).validate(&mut report);
+    report
+}
+
+/// A function to return the errors (but not the warnings) found in a
+/// configuration that is already loaded. The errors are used to check a
+/// batch of modifications before it is applied, so the media files are not
+/// checked.
+///
+pub(super) fn config_errors(yaml_config: &YamlConfig) -> Vec<ValidationIssue> {
+    // Validate the configuration and keep only the errors
+    let mut report = ValidationReport::new("");
+    let mut validator = Validator::new(yaml_config);
+    validator.check_media = false;
+    validator.validate(&mut report);
+    report
+        .issues
+        .into_iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .collect()
+}
+
+/// A helper function to report any item ids that are defined more than once
+///
+fn check_duplicates(report: &mut ValidationReport, value: &Value) {
+    // Count each id in the event set
+    let mut counts: FnvHashMap<u64, usize> = FnvHashMap::default();
+    if let Some(event_set) = value.get("event_set").and_then(|set| set.as_mapping()) {
+        for key in event_set.keys() {
+            if let Some(id) = key.get("id").and_then(|id| id.as_u64()) {
+                *counts.entry(id).or_insert(0) += 1;
+            }
+        }
+    }
+
+    // Report each duplicate, sorted for consistent display
+    let mut duplicates: Vec<(u64, usize)> =
+        counts.into_iter().filter(|(_, count)| *count > 1).collect();
+    duplicates.sort();
+    for (id, count) in duplicates {
+        report.error(
+            ItemId::new(id as u32),
+            format!("Item is defined {} times", count),
+        );
+    }
+}
+
+/// A helper structure to hold the parts of the configuration for validation
+///
+struct Validator<'a> {
+    config: &'a YamlConfig,            // the configuration to validate
+    lookup: FnvHashSet<ItemId>,        // every item with a description
+    events: FnvHashMap<ItemId, Event>, // every defined event
+    channels: Option<FnvHashSet<u32>>, // the valid media channels (None if any channel is allowed)
+    referenced: FnvHashSet<ItemId>,    // every item referenced by another part of the configuration
+    check_media: bool,                 // a flag to check the media files and channels
+}
+
+// Implement the validation checks
+impl<'a> Validator<'a> {
+    /// A function to collect the parts of the configuration
+    ///
+    fn new(config: &'a YamlConfig) -> Self {
+        // Collect the item descriptions and events
+        let mut lookup = FnvHashSet::default();
+        let mut events = FnvHashMap::default();
+        for (item_pair, possible_event) in config.event_set.iter() {
+            lookup.insert(item_pair.get_id());
+            if let Some(event) = possible_event {
+                events.insert(item_pair.get_id(), event.clone());
+            }
+        }
+
+        // Collect the valid media channels (matching the media checker)
+        let mut channels = Some(FnvHashSet::default());
+        for player in config.media_players.iter() {
+            if player.channel_map.is_empty() {
+                channels = None;
+                break;
+            }
+            if let Some(ref mut list) = channels {
+                list.extend(player.channel_map.keys().cloned());
+            }
+        }
+
+        // Return the validator
+        Self {
+            config,
+            lookup,
+            events,
+            channels,
+            referenced: FnvHashSet::default(),
+            check_media: true,
+        }
+    }
+
+    /// A method to run every check and add the problems to the report
+    ///
+    fn validate(mut self, report: &mut ValidationReport) {
+        // Check the default scene
+        let default_scene = self.config.default_scene;
+        self.referenced.insert(default_scene);
+        if !self.config.scene_map.contains_key(&default_scene) {
+            report.error(Some(default_scene), "Default scene is not defined".into());
+        }
+
+        // Check each part of the configuration
+        self.check_scenes(report);
+        self.check_groups(report);
+        self.check_statuses(report);
+        self.check_events(report);
+        self.check_options(report);
+
+        // Check the media files and channels, if requested
+        let media_issues = match self.check_media {
+            true => MediaChecker::check(&self.config.media_players, &self.events),
+            false => Vec::new(),
+        };
+        for issue in media_issues {
+            report.error(
+                issue.event_id,
+                format!(
+                    "Media problem on channel {} ({:?}): {}",
+                    issue.channel, issue.problem, issue.uri
+                ),
+            );
+        }
+
+        // Look for items that are never used
+        self.check_unused(report);
+    }
+
+    /// A helper method to check each scene, its key map, and its hints
+    ///
+    fn check_scenes(&mut self, report: &mut ValidationReport) {
+        let config = self.config;
+        for (scene_id, scene) in sorted(&config.scene_map) {
+            // Check that the scene is described
+            self.check_described(report, scene_id, "Scene");
+
+            // Check each item in the scene
+            for item_id in scene.items.iter() {
+                self.referenced.insert(*item_id);
+                if !self.events.contains_key(item_id)
+                    && !self.config.status_map.contains_key(item_id)
+                {
+                    report.error(
+                        Some(*item_id),
+                        format!("Item listed in scene {} but not found", scene_id),
+                    );
+                }
+            }
+
+            // Check each group in the scene
+            for group_id in scene.groups.iter() {
+                self.referenced.insert(*group_id);
+                if !self.config.group_map.contains_key(group_id) {
+                    report.error(
+                        Some(*group_id),
+                        format!("Group listed in scene {} but not found", scene_id),
+                    );
+                }
+            }
+
+            // Check that each key mapping is available in the scene
+            for event_id in scene.key_map.iter().flat_map(|key_map| key_map.values()) {
+                let is_found = scene.items.contains(event_id)
+                    || scene.groups.iter().any(|group_id| {
+                        self.config
+                            .group_map
+                            .get(group_id)
+                            .is_some_and(|group| group.items.contains(event_id))
+                    });
+                if !is_found {
+                    report.error(
+                        Some(*event_id),
+                        format!("Event in keyboard shortcuts, but not in scene {}", scene_id),
+                    );
+                }
+            }
+
+            // Check the media channel of each hint
+            for hint in scene.hints.iter() {
+                if let Some(ref cue) = hint.media {
+                    self.check_channel(report, scene_id, cue.channel);
+                }
+            }
+        }
+    }
+
+    /// A helper method to check each group
+    ///
+    fn check_groups(&mut self, report: &mut ValidationReport) {
+        let config = self.config;
+        for (group_id, group) in sorted(&config.group_map) {
+            // Check that the group is described
+            self.check_described(report, group_id, "Group");
+
+            // Check each item in the group
+            for item_id in group.items.iter() {
+                self.referenced.insert(*item_id);
+                if !self.events.contains_key(item_id)
+                    && !self.config.status_map.contains_key(item_id)
+                {
+                    report.error(
+                        Some(*item_id),
+                        format!("Item listed in group {} but not found", group_id),
+                    );
+                }
+            }
+        }
+    }
+
+    /// A helper method to check each status and its states
+    ///
+    fn check_statuses(&mut self, report: &mut ValidationReport) {
+        let config = self.config;
+        for (status_id, status) in sorted(&config.status_map) {
+            // Check that the status is described
+            self.check_described(report, status_id, "Status");
+
+            // Check each state of the status
+            for state_id in status.allowed() {
+                self.referenced.insert(state_id);
+                if !self.lookup.contains(&state_id) {
+                    report.error(
+                        Some(state_id),
+                        format!("State of status {} not described", status_id),
+                    );
+                } else if !self.events.contains_key(&state_id) {
+                    report.warning(
+                        Some(state_id),
+                        format!("State of status {} has no event", status_id),
+                    );
+                }
+            }
+        }
+    }
+
+    /// A helper method to check every action of every event
+    ///
+    fn check_events(&mut self, report: &mut ValidationReport) {
+        let events = self.events.clone();
+        for (event_id, event) in sorted(&events) {
+            for action in event.iter() {
+                match action {
+                    // Check that the media channel exists
+                    AdjustMedia { adjustment } => {
+                        self.check_channel(report, event_id, adjustment.channel);
+                    }
+
+                    // Check that the cancelled or cued event exists
+                    CancelEvent { event } => self.check_event(report, event_id, event),
+                    CueEvent { event } => self.check_event(report, event_id, &event.id()),
+
+                    // Check that the DMX universe exists
+                    CueDmx { fade } => {
+                        let universe = fade.universe.unwrap_or(0);
+                        if !self.config.dmx_controllers.contains_key(&universe) {
+                            report.error(
+                                Some(*event_id),
+                                format!("Event uses undefined DMX universe {}", universe),
+                            );
+                        }
+                    }
+
+                    // Check the events triggered by the media (the media checker handles the rest)
+                    CueMedia { cue } => {
+                        if let Some(ref on_complete) = cue.on_complete {
+                            self.check_event(report, event_id, on_complete);
+                        }
+                    }
+                    CueMediaPlaylist { playlist } => {
+                        for item in playlist.items.iter() {
+                            if let Some(ref event) = item.event {
+                                self.check_event(report, event_id, event);
+                            }
+                        }
+                    }
+
+                    // Check that the status and state exist
+                    ModifyStatus {
+                        status_id,
+                        new_state,
+                    } => self.check_state(report, event_id, status_id, new_state),
+
+                    // Check that the scene exists
+                    NewScene { new_scene } => {
+                        self.referenced.insert(*new_scene);
+                        if !self.config.scene_map.contains_key(new_scene) {
+                            report.error(
+                                Some(*event_id),
+                                format!("Event changes to undefined scene {}", new_scene),
+                            );
+                        }
+                    }
+
+                    // Check the events used for the data
+                    SaveData { data } | SendData { data } => match data {
+                        DataType::TimeUntil { event_id: target }
+                        | DataType::TimePassedUntil {
+                            event_id: target, ..
+                        } => self.check_event(report, event_id, target),
+                        DataType::StaticString { .. } => (),
+                    },
+
+                    // Check the status, states, and events of the selection
+                    SelectEvent {
+                        status_id,
+                        event_map,
+                    } => {
+                        for (state_id, target) in event_map.iter() {
+                            self.check_state(report, event_id, status_id, state_id);
+                            self.check_event(report, event_id, target);
+                        }
+                    }
+
+                    // Assume the rest are valid
+                    DeliverHint {} | EndSession { .. } | StartSession {} => (),
+                }
+            }
+        }
+    }
+
+    /// A helper method to check the optional features that refer to items
+    ///
+    fn check_options(&mut self, report: &mut ValidationReport) {
+        // Check the shared statuses and their reactions
+        if let Some(ref sync) = self.config.state_sync {
+            for status_id in sync.statuses.iter() {
+                self.referenced.insert(*status_id);
+                if !self.config.status_map.contains_key(status_id) {
+                    report.error(Some(*status_id), "Shared status is not defined".into());
+                }
+            }
+            for reaction in sync.reactions.iter() {
+                self.referenced.insert(reaction.item_id);
+                self.check_event(report, &reaction.item_id, &reaction.event_id);
+            }
+        }
+
+        // Check the milestone and hint events
+        if let Some(ref statistics) = self.config.statistics {
+            for event_id in statistics.milestones.iter().chain(statistics.hints.iter()) {
+                if !self.events.contains_key(event_id) {
+                    report.error(Some(*event_id), "Statistics event is not defined".into());
+                }
+            }
+        }
+
+        // Check the hint penalty
+        if let Some(ref penalty) = self.config.hint_penalty {
+            self.check_state(
+                report,
+                &penalty.status_id,
+                &penalty.status_id,
+                &penalty.new_state,
+            );
+        }
+    }
+
+    /// A helper method to warn of events that can't be triggered and items
+    /// that are never used
+    ///
+    fn check_unused(&self, report: &mut ValidationReport) {
+        let mut lookup: Vec<&ItemId> = self.lookup.iter().collect();
+        lookup.sort();
+        for item_id in lookup {
+            // Ignore anything that is referenced elsewhere or is a scene (scene events are triggered on entry)
+            if self.referenced.contains(item_id) || self.config.scene_map.contains_key(item_id) {
+                continue;
+            }
+
+            // Warn of events that can't be triggered
+            if self.events.contains_key(item_id) {
+                report.warning(
+                    Some(*item_id),
+                    "Event is not in any scene or group and is never cued".into(),
+                );
+
+            // Warn of items that are never used
+            } else if !self.config.status_map.contains_key(item_id)
+                && !self.config.group_map.contains_key(item_id)
+            {
+                report.warning(Some(*item_id), "Item is never used".into());
+            }
+        }
+    }
+
+    /// A helper method to check that an item is described
+    ///
+    fn check_described(&self, report: &mut ValidationReport, item_id: &ItemId, kind: &str) {
+        if !self.lookup.contains(item_id) {
+            report.warning(Some(*item_id), format!("{} not described in lookup", kind));
+        }
+    }
+
+    /// A helper method to check that an event referenced by an item exists
+    ///
+    fn check_event(&mut self, report: &mut ValidationReport, item_id: &ItemId, event: &ItemId) {
+        self.referenced.insert(*event);
+        if !self.events.contains_key(event) {
+            report.error(
+                Some(*item_id),
+                format!("Refers to undefined event {}", event),
+            );
+        }
+    }
+
+    /// A helper method to check that a status exists and allows the state
+    ///
+    fn check_state(
+        &mut self,
+        report: &mut ValidationReport,
+        item_id: &ItemId,
+        status_id: &ItemId,
+        state_id: &ItemId,
+    ) {
+        self.referenced.insert(*status_id);
+        match self.config.status_map.get(status_id) {
+            Some(status) if !status.allowed().contains(state_id) => report.error(
+                Some(*item_id),
+                format!("State {} is not allowed for status {}", state_id, status_id),
+            ),
+            Some(_) => (),
+            None => report.error(
+                Some(*item_id),
+                format!("Refers to undefined status {}", status_id),
+            ),
+        }
+    }
+
+    /// A helper method to check that a media channel exists
+    ///
+    fn check_channel(&self, report: &mut ValidationReport, item_id: &ItemId, channel: u32) {
+        if self
+            .channels
+            .as_ref()
+            .is_some_and(|channels| !channels.contains(&channel))
+        {
+            report.error(
+                Some(*item_id),
+                format!("Uses undefined media channel {}", channel),
+            );
+        }
+    }
+}
+
+/// A helper function to sort a map by id for consistent reports
+///
+fn sorted<T>(map: &FnvHashMap<ItemId, T>) -> Vec<(&ItemId, &T)> {
+    let mut items: Vec<(&ItemId, &T)> = map.iter().collect();
+    items.sort_by_key(|(id, _)| **id);
+    items
+}
+
+// Tests of the validation module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that a broken configuration reports each problem
+    #[test]
+    fn validate_broken_config() {
+        // Create a configuration with several problems
+        let config = format!(
+            r#"
+version: {}
+schema: 2
+identifier:
+  id: ~
+server_location: ~
+dmx_controllers: {{}}
+media_players: []
+system_connections: []
+background_process: ~
+default_scene:
+  id: 100
+group_map: {{}}
+scene_map:
+  ? id: 100
+  : items:
+      - id: 10
+      - id: 11
+    groups: []
+    key_map: ~
+status_map: {{}}
+event_set:
+  ? id: 100
+    description: Scene
+  : ~
+  ? id: 10
+    description: Start
+  : - !cueEvent
+      event:
+        delay: ~
+        event_id:
+          id: 99
+    - !cueDmx
+      fade:
+        universe: ~
+        channel: 1
+        value: 255
+        duration: ~
+  ? id: 12
+    description: Unused
+  : - !startSession {{}}
+  ? id: 12
+    description: Duplicate Unused
+  : ~
+user_styles: {{}}
+"#,
+            env!("CARGO_PKG_VERSION")
+        );
+
+        // Validate the configuration and check the problems
+        let report = validate_config("test.yaml", &config);
+        let messages: Vec<&str> = report
+            .issues
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect();
+        assert!(messages.contains(&"Item is defined 2 times"));
+        assert!(messages.contains(&"Item listed in scene 100 but not found"));
+        assert!(messages.contains(&"Refers to undefined event 99"));
+        assert!(messages.contains(&"Event uses undefined DMX universe 0"));
+        assert!(report.has_errors());
+        assert_eq!(1, report.count(Severity::Warning));
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to write and query the structured game log. The game log is
+//! saved as one file per day with one JSON record per line, so that it can be
+//! loaded directly into spreadsheets and other analysis tools. The file for
+//! the most recent day is kept open and is only reopened when the day changes.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import standard library features
+use std::path::PathBuf;
+
+// Import Chrono features
+use chrono::NaiveDate;
+
+// Import Tokio features
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+// Import tracing features
+use tracing::{error, warn};
+
+// Import anyhow features
+use anyhow::Result;
+
+/// The file extension for the game log files
+const EXTENSION: &str = "jsonl";
+
+/// A structure to write and query the game log files
+///
+pub struct GameLogger {
+    folder: PathBuf,                 // the folder for the game log files
+    file: Option<(NaiveDate, File)>, // the open file and its day, if any
+}
+
+// Implement key functionality for the game logger
+impl GameLogger {
+    /// A function to create a new game logger in the provided folder
+    ///
+    pub fn new(folder: PathBuf) -> Self {
+        Self { folder, file: None }
+    }
+
+    /// A method to add a record to the game log for the day of the record
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error if the record could not be written.
+    ///
+    pub async fn write(&mut self, record: GameRecord) {
+        if let Err(error) = self.try_write(&record).await {
+            error!("Unable to write to game log: {}.", error);
+
+            // Reopen the file for the next record
+            self.file = None;
+        }
+    }
+
+    /// A method to return every record in the game log that matches the query,
+    /// from oldest to newest
+    ///
+    pub async fn query(&self, query: &GameLogQuery) -> Vec<GameRecord> {
+        // Try to open the folder (it will not exist before the first record)
+        let mut records = Vec::new();
+        let mut entries = match fs::read_dir(&self.folder).await {
+            Ok(entries) => entries,
+            Err(_) => return records,
+        };
+
+        // Collect the files for each day in the range
+        let mut files = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            if let Some(date) = GameLogger::file_date(&entry.file_name().to_string_lossy())
+                && query.includes_date(&date)
+            {
+                files.push((date, entry.path()));
+            }
+        }
+        files.sort();
+
+        // Read the matching records from each file
+        for (_, path) in files {
+            // Try to read the file
+            let text = match fs::read_to_string(&path).await {
+                Ok(text) => text,
+                Err(error) => {
+                    warn!("Unable to read game log {:?}: {}.", path, error);
+                    continue;
+                }
+            };
+
+            // Parse each line, skipping any invalid lines
+            for line in text.lines().filter(|line| !line.trim().is_empty()) {
+                match serde_json::from_str::<GameRecord>(line) {
+                    Ok(record) if query.matches(&record) => records.push(record),
+                    Ok(_) => (),
+                    Err(error) => warn!("Invalid record in game log {:?}: {}.", path, error),
+                }
+            }
+        }
+
+        // Return the records
+        records
+    }
+
+    /// A helper method to append a record to the correct file
+    ///
+    async fn try_write(&mut self, record: &GameRecord) -> Result<()> {
+        // Compose the line
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        // Open the file for the day, unless it is already open
+        let date = record.time.date();
+        let file = match self.file {
+            Some((open_date, ref mut file)) if open_date == date => file,
+            _ => {
+                fs::create_dir_all(&self.folder).await?;
+                let path = self.folder.join(format!(
+                    "{}.{}.{}",
+                    GAME_LOG,
+                    date.format("%Y-%m-%d"),
+                    EXTENSION
+                ));
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .await?;
+                &mut self.file.insert((date, file)).1
+            }
+        };
+
+        // Append the line to the file
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    /// A helper function to find the day of a game log file from its name
+    ///
+    fn file_date(filename: &str) -> Option<NaiveDate> {
+        let date = filename
+            .strip_prefix(GAME_LOG)?
+            .strip_prefix('.')?
+            .strip_suffix(EXTENSION)?
+            .strip_suffix('.')?;
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+    }
+}
+
+// Tests of the game logger module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test writing and querying the game log
+    #[tokio::test]
+    async fn query_game_log() {
+        // Create a game logger in the temporary directory
+        let folder = std::env::temp_dir().join("minerva_game_log_test");
+        fs::remove_dir_all(&folder).await.unwrap_or(());
+        let mut logger = GameLogger::new(folder.clone());
+
+        // Write records on two days
+        let record = GameRecord {
+            time: NaiveDate::from_ymd_opt(2026, 3, 1)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap(),
+            session: Some("first".into()),
+            scene: ItemPair::new_unchecked(100, "Scene"),
+            event: Some(ItemPair::new_unchecked(10, "Start")),
+            status: None,
+            state: None,
+            data: None,
+        };
+        logger.write(record.clone()).await;
+        let mut later = record.clone();
+        later.time += chrono::Duration::days(1);
+        later.session = Some("second".into());
+        later.event = None;
+        later.data = Some("Time 10:00".into());
+        logger.write(later.clone()).await;
+
+        // Query by date, event, and session
+        assert_eq!(2, logger.query(&GameLogQuery::default()).await.len());
+        let by_date = GameLogQuery {
+            start_date: Some(later.time.date()),
+            ..Default::default()
+        };
+        assert_eq!(vec![later.clone()], logger.query(&by_date).await);
+        let by_event = GameLogQuery {
+            event_id: Some(ItemId::new_unchecked(10)),
+            ..Default::default()
+        };
+        assert_eq!(vec![record.clone()], logger.query(&by_event).await);
+        let by_session = GameLogQuery {
+            session: Some("second".into()),
+            ..Default::default()
+        };
+        assert_eq!(vec![later], logger.query(&by_session).await);
+
+        // Write another record on the first day and check that it is appended
+        let mut again = record.clone();
+        again.time += chrono::Duration::hours(1);
+        logger.write(again.clone()).await;
+        let by_first_day = GameLogQuery {
+            end_date: Some(record.time.date()),
+            ..Default::default()
+        };
+        assert_eq!(vec![record, again], logger.query(&by_first_day).await);
+
+        // Clean up the folder
+        fs::remove_dir_all(&folder).await.unwrap_or(());
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to track which hints have been delivered in each scene during
+//! the current game session. The event handler copies the delivered hints to
+//! the backup so that they survive a restart.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import FNV HashMap
+use fnv::FnvHashMap;
+
+/// A structure to track the hints delivered in each scene
+///
+pub struct HintHandler {
+    delivered: FnvHashMap<ItemId, usize>, // the number of hints delivered in each scene
+}
+
+// Implement key functionality for the hint handler
+impl HintHandler {
+    /// A function to create a new hint handler with no hints delivered
+    ///
+    pub fn new() -> Self {
+        Self {
+            delivered: FnvHashMap::default(),
+        }
+    }
+
+    /// A method to select the next hint for the scene. The method returns the
+    /// number of the hint (starting at one) and the hint, or None if every
+    /// hint for the scene has already been delivered.
+    ///
+    pub fn next_hint(&mut self, scene_id: ItemId, hints: &[Hint]) -> Option<(u32, Hint)> {
+        // Find the next hint for the scene
+        let count = self.delivered.entry(scene_id).or_insert(0);
+        let hint = hints.get(*count)?.clone();
+
+        // Mark the hint as delivered
+        *count += 1;
+        Some((*count as u32, hint))
+    }
+
+    /// A method to forget every delivered hint (e.g. for a new session)
+    ///
+    pub fn reset(&mut self) {
+        self.delivered.clear();
+    }
+
+    /// A method to return the number of hints delivered in each scene (e.g.
+    /// to save in the backup)
+    ///
+    pub fn get_delivered(&self) -> Vec<(ItemId, u32)> {
+        self.delivered
+            .iter()
+            .map(|(scene_id, count)| (*scene_id, *count as u32))
+            .collect()
+    }
+
+    /// A method to replace the number of hints delivered in each scene (e.g.
+    /// from the backup)
+    ///
+    pub fn load_delivered(&mut self, delivered: Vec<(ItemId, u32)>) {
+        self.delivered = delivered
+            .into_iter()
+            .map(|(scene_id, count)| (scene_id, count as usize))
+            .collect();
+    }
+}
+
+// Tests of the hint handler module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test delivering hints in order and resetting them
+    #[test]
+    fn next_hint() {
+        // Create the hints for one scene
+        let hints: Vec<Hint> = ["Look up", "Try the clock"]
+            .iter()
+            .map(|message| Hint {
+                message: message.to_string(),
+                media: None,
+            })
+            .collect();
+        let scene = ItemId::new_unchecked(100);
+        let mut handler = HintHandler::new();
+
+        // Deliver each hint in turn, then run out
+        assert_eq!(
+            Some((1, hints[0].clone())),
+            handler.next_hint(scene, &hints)
+        );
+        assert_eq!(
+            Some((2, hints[1].clone())),
+            handler.next_hint(scene, &hints)
+        );
+        assert_eq!(None, handler.next_hint(scene, &hints));
+
+        // Check that other scenes are separate and that reset starts over
+        assert!(
+            handler
+                .next_hint(ItemId::new_unchecked(200), &hints)
+                .is_some()
+        );
+        handler.reset();
+        assert_eq!(
+            Some((1, hints[0].clone())),
+            handler.next_hint(scene, &hints)
+        );
+
+        // Check that the delivered hints can be saved and loaded again
+        let delivered = handler.get_delivered();
+        assert_eq!(vec!((scene, 1)), delivered);
+        let mut restored = HintHandler::new();
+        restored.load_delivered(delivered);
+        assert_eq!(
+            Some((2, hints[1].clone())),
+            restored.next_hint(scene, &hints)
+        );
+    }
+}
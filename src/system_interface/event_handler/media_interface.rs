@@ -20,20 +20,32 @@
 // Import crate definitions
 use crate::definitions::*;
 
+// Import other definitions
+use super::audio_player::AudioPlayer;
+
 // Import tokio elements
 use tokio::process::Command;
 use tokio::sync::mpsc;
-use tokio::time::{Duration, sleep};
+use tokio::time::{Duration, Instant, sleep};
 
 // Import reqwest elements
 use reqwest::Client;
 
+// Import FNV HashMap
+use fnv::FnvHashMap;
+
+// Import rand features
+use rand::seq::SliceRandom;
+
 // Import tracing features
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 // Import anyhow features
 use anyhow::Result;
 
+// Define the number of attempts to restore media after a crash
+const RESTORE_ATTEMPTS: u32 = 10;
+
 /// A structure to hold and manage the Apollo media player thread
 ///
 struct ApolloThread;
@@ -180,25 +192,107 @@ impl ApolloThread {
     }
 }
 
+/// A structure to hold the progress of a media playlist on one channel
+///
+struct PlaylistState {
+    playlist: MediaPlaylist, // the playlist that is playing
+    order: Vec<usize>,       // the order to play the playlist items
+    position: usize,         // the position of the current item in the order
+}
+
+// Implement the PlaylistState functions
+impl PlaylistState {
+    /// A function to create a new playlist state, shuffled if requested
+    fn new(playlist: MediaPlaylist) -> Self {
+        // Compose the play order
+        let mut order: Vec<usize> = (0..playlist.items.len()).collect();
+        if playlist.shuffle {
+            order.shuffle(&mut rand::thread_rng());
+        }
+
+        // Return the new state
+        Self {
+            playlist,
+            order,
+            position: 0,
+        }
+    }
+
+    /// A method to return the current playlist item
+    fn current(&self) -> &PlaylistItem {
+        &self.playlist.items[self.order[self.position]]
+    }
+
+    /// A method to advance to the next item, returning false if the playlist is complete
+    fn advance(&mut self) -> bool {
+        // Move to the next item
+        self.position += 1;
+
+        // If the end of the playlist was reached
+        if self.position >= self.order.len() {
+            // Stop if the playlist doesn't loop
+            if !self.playlist.loop_playlist {
+                return false;
+            }
+
+            // Otherwise, restart (and reshuffle if requested)
+            self.position = 0;
+            if self.playlist.shuffle {
+                self.order.shuffle(&mut rand::thread_rng());
+            }
+        }
+
+        // Indicate there is another item
+        true
+    }
+
+    /// A method to check if the current item is the last in the playlist
+    fn is_last(&self) -> bool {
+        self.position + 1 >= self.order.len()
+    }
+
+    /// A method to compose a media cue for the current item
+    fn current_cue(&self) -> MediaCue {
+        MediaCue {
+            uri: self.current().uri.clone(),
+            channel: self.playlist.channel,
+            loop_media: None,
+            on_complete: None,
+        }
+    }
+}
+
 /// A structure to hold and manipulate the connection to the media backend
 ///
 pub struct MediaInterface {
-    channel_list: Vec<u32>, // a list of valid channels for this instance
-    client: Option<Client>, // the reqwest client for passing media changes
-    address: String,        // the address for requests to Apollo
-    _close_sender: mpsc::Sender<()>, // a line to notify the background thread to close
-                            // the line is never used, but is poisoned when dropped
+    channel_list: Vec<u32>,    // a list of valid channels for this instance
+    loop_channels: Vec<u32>,   // a list of channels with default loop media
+    client: Option<Client>,    // the reqwest client for passing media changes
+    address: String,           // the address for requests to Apollo
+    complete_callback: String, // the address for Apollo to report completed media
+    audio_player: Option<AudioPlayer>, // the built-in audio player, if used instead of Apollo
+    complete_events: FnvHashMap<u32, (String, ItemId)>, // the uri and event to cue when the media on each channel completes
+    playlists: FnvHashMap<u32, PlaylistState>, // the media playlist currently playing on each channel
+    _close_sender: mpsc::Sender<()>,           // a line to notify the background thread to close
+                                               // the line is never used, but is poisoned when dropped
 }
 
 // Implement key functionality for the Media Interface structure
 impl MediaInterface {
     /// A function to create a new instance of the MediaInterface
     ///
+    /// Apollo reports completed media to the media complete route of the run
+    /// interface at the provided run address. The built-in audio player
+    /// reports completed media on the internal send line instead.
+    ///
     pub async fn new(
         channel_map: ChannelMap,
         window_map: WindowMap,
         apollo_params: ApolloParams,
+        backend: MediaBackend,
         backup_location: Option<String>,
+        run_address: &str,
+        internal_send: InternalSend,
     ) -> Self {
         // Copy the specified address or use the default
         let address = apollo_params
@@ -208,35 +302,88 @@ impl MediaInterface {
 
         // Collect the list of valid channels
         let channel_list = channel_map.keys().map(|key| key.clone()).collect();
+        let loop_channels = channel_map
+            .iter()
+            .filter(|(_, media_channel)| media_channel.loop_media.is_some())
+            .map(|(channel, _)| *channel)
+            .collect();
 
         // Create a channel to notify the background thread to close
         let (_close_sender, close_receiver) = mpsc::channel(1); // don't need space for any messages
 
-        // Spin out thread to monitor and restart apollo, if requested
-        if apollo_params.spawn {
-            ApolloThread::spawn(
-                close_receiver,
-                address.clone(),
-                backup_location,
-                window_map,
-                channel_map,
-            )
-            .await;
+        // Select the media backend
+        let mut audio_player = None;
+        match backend {
+            // Spin out thread to monitor and restart apollo, if requested
+            MediaBackend::Apollo => {
+                if apollo_params.spawn {
+                    ApolloThread::spawn(
+                        close_receiver,
+                        address.clone(),
+                        backup_location,
+                        window_map,
+                        channel_map,
+                    )
+                    .await;
+                }
+            }
+
+            // Create the built-in audio player with the command
+            MediaBackend::CommandLine { command, arguments } => {
+                audio_player = Some(AudioPlayer::new(
+                    Some((command, arguments)),
+                    &channel_map,
+                    internal_send,
+                ));
+            }
+
+            // Create the built-in audio player without a command
+            MediaBackend::Null => {
+                audio_player = Some(AudioPlayer::new(None, &channel_map, internal_send));
+            }
         }
 
         // Return the complete module
         Self {
             channel_list,
+            loop_channels,
             client: None,
             address,
+            complete_callback: MediaInterface::complete_callback(run_address),
+            audio_player,
+            complete_events: FnvHashMap::default(),
+            playlists: FnvHashMap::default(),
             _close_sender,
         }
     }
 
+    /// A method to check if this instance owns the channel. An instance
+    /// without a channel list owns every channel.
+    pub fn owns_channel(&self, channel: u32) -> bool {
+        self.channel_list.is_empty() || self.channel_list.contains(&channel)
+    }
+
+    /// A method to check if this instance lists the channel explicitly
+    pub fn lists_channel(&self, channel: u32) -> bool {
+        self.channel_list.contains(&channel)
+    }
+
+    /// A method to check if the channel has default loop media
+    pub fn has_loop_media(&self, channel: u32) -> bool {
+        self.loop_channels.contains(&channel)
+    }
+
+    /// A method to forget any pending completion events and media playlists
+    /// (e.g. before restoring a snapshot of the game)
+    pub fn clear_pending(&mut self) {
+        self.complete_events.clear();
+        self.playlists.clear();
+    }
+
     // A helper method to send a new media cue
     pub async fn play_cue(&mut self, cue: MediaCue) -> Result<()> {
         // If there is a channel list
-        if self.channel_list.len() > 0 {
+        if !self.channel_list.is_empty() {
             // Check that the channel is valid
             if !self.channel_list.contains(&cue.channel) {
                 // If not, note the error
@@ -244,31 +391,220 @@ impl MediaInterface {
             }
         }
 
-        // Create the request client if it doesn't exist
-        if self.client.is_none() {
-            self.client = Some(Client::new());
+        // Save the channel, uri, and completion event
+        let channel = cue.channel;
+        let uri = cue.uri.clone();
+        let on_complete = cue.on_complete;
+
+        // Pass the media cue to Apollo
+        self.send_cue(cue).await?;
+
+        // Stop any playlist on this channel
+        self.playlists.remove(&channel);
+
+        // Replace any previous completion event for this channel
+        match on_complete {
+            Some(event_id) => {
+                self.complete_events.insert(channel, (uri, event_id));
+            }
+            None => {
+                self.complete_events.remove(&channel);
+            }
         }
 
+        // Indicate success
+        Ok(())
+    }
+
+    // A helper method to start a new media playlist
+    //
+    // Returns the media cue for the first item and its event, if specified.
+    pub async fn play_playlist(
+        &mut self,
+        playlist: MediaPlaylist,
+    ) -> Result<(MediaCue, Option<ItemId>)> {
+        // If there is a channel list
+        if !self.channel_list.is_empty() {
+            // Check that the channel is valid
+            if !self.channel_list.contains(&playlist.channel) {
+                // If not, note the error
+                return Err(anyhow!("Channel for Media Playlist not found."));
+            }
+        }
+
+        // Check that the playlist isn't empty
+        if playlist.items.is_empty() {
+            return Err(anyhow!("Media Playlist is empty."));
+        }
+
+        // Create the playlist state and play the first item
+        let channel = playlist.channel;
+        let state = PlaylistState::new(playlist);
+        let cue = state.current_cue();
+        self.send_cue(cue.clone()).await?;
+
+        // Replace any previous media on this channel
+        let event = state.current().event;
+        self.complete_events.remove(&channel);
+        self.playlists.insert(channel, state);
+
+        // Return the first item and its event
+        Ok((cue, event))
+    }
+
+    // A helper method to restart media at a particular position (e.g. after a crash)
+    //
+    // The media is cued and then seeked from a background task, retrying until
+    // the media player is available.
+    pub async fn restore_media(&mut self, cue: MediaCue, position: Duration) -> Result<()> {
+        // If there is a channel list
+        if !self.channel_list.is_empty() {
+            // Check that the channel is valid
+            if !self.channel_list.contains(&cue.channel) {
+                // If not, note the error
+                return Err(anyhow!("Channel for Media Cue not found."));
+            }
+        }
+
+        // Stop any stale playlist on this channel
+        self.playlists.remove(&cue.channel);
+
+        // Replace any previous completion event for this channel
+        match cue.on_complete {
+            Some(event_id) => {
+                self.complete_events
+                    .insert(cue.channel, (cue.uri.clone(), event_id));
+            }
+            None => {
+                self.complete_events.remove(&cue.channel);
+            }
+        }
+
+        // If using the built-in audio player, restart the media from the beginning
+        if let Some(ref mut player) = self.audio_player {
+            player.play(cue);
+            return Ok(());
+        }
+
+        // Note the start time to correct the position on a delayed restore
+        let start = Instant::now();
+
         // Recompose the media cue into a helper
-        let helper: MediaCueHelper = cue.into();
+        let channel = cue.channel;
+        let mut helper: MediaCueHelper = cue.into();
+        helper.complete_callback = Some(self.complete_callback.clone());
 
-        // Pass the media cue to Apollo
-        self.client
-            .as_ref()
-            .unwrap()
-            .post(&format!("http://{}/cueMedia", &self.address))
-            .json(&helper)
-            .send()
-            .await?;
+        // Spin out a thread to restore the media
+        let address = self.address.clone();
+        tokio::spawn(async move {
+            // Create a client for restoring the media
+            let tmp_client = Client::new();
+
+            // Try several times to reach the media player
+            for _ in 0..RESTORE_ATTEMPTS {
+                // Try to cue the media
+                if tmp_client
+                    .post(format!("http://{}/cueMedia", &address))
+                    .json(&helper)
+                    .send()
+                    .await
+                    .is_ok()
+                {
+                    // Compose the seek with the corrected position
+                    let seek = SeekMediaHelper {
+                        channel,
+                        position: (position + start.elapsed()).as_millis() as u64,
+                    };
+
+                    // Seek to the correct position
+                    if let Err(error) = tmp_client
+                        .post(format!("http://{}/seek", &address))
+                        .json(&seek)
+                        .send()
+                        .await
+                    {
+                        error!("Unable to restore media position: {}.", error);
+                    }
+                    return;
+                }
+
+                // Wait for the media player to start
+                sleep(Duration::from_secs(1)).await;
+            }
+
+            // Warn that the media could not be restored
+            error!("Unable to restore media on channel {}.", channel);
+        });
 
         // Indicate success
         Ok(())
     }
 
+    // A helper method to process a notification that media has completed
+    //
+    // Returns the events to cue, if the uri matches the most recent media cued
+    // on the channel and that media had a completion event or was part of a
+    // playlist with more items. Also returns the next playlist item, if one
+    // was started.
+    pub async fn media_complete(
+        &mut self,
+        channel: u32,
+        uri: &str,
+    ) -> (Vec<ItemId>, Option<MediaCue>) {
+        // Check for a completion event on this channel
+        let mut events = Vec::new();
+        if let Some((expected_uri, _)) = self.complete_events.get(&channel) {
+            // Only cue the event if the media hasn't already been replaced
+            if expected_uri == uri
+                && let Some((_, event_id)) = self.complete_events.remove(&channel)
+            {
+                events.push(event_id);
+            }
+        }
+
+        // Check for a playlist on this channel that was playing this media
+        let mut next_cue = None;
+        if let Some(state) = self.playlists.get_mut(&channel)
+            && state.current().uri == uri
+        {
+            // Stop a looping playlist if the audio is discarded (it would never pause)
+            let is_discarding = self
+                .audio_player
+                .as_ref()
+                .is_some_and(|player| player.is_discarding());
+            if is_discarding && state.playlist.loop_playlist && state.is_last() {
+                warn!(
+                    "Stopped looping media playlist on channel {}: audio is discarded.",
+                    channel
+                );
+                self.playlists.remove(&channel);
+
+            // Advance to the next item, if there is one
+            } else if state.advance() {
+                events.extend(state.current().event);
+                next_cue = Some(state.current_cue());
+
+            // Otherwise, the playlist is complete
+            } else {
+                self.playlists.remove(&channel);
+            }
+        }
+
+        // Play the next item in the playlist, if there is one
+        if let Some(cue) = next_cue.as_ref()
+            && let Err(error) = self.send_cue(cue.clone()).await
+        {
+            error!("Unable to play next item in media playlist: {}.", error);
+        }
+
+        // Return the events to cue and the next item
+        (events, next_cue)
+    }
+
     // A helper method to adjust the location of a video frame by one pixel in any direction
     pub async fn adjust_media(&mut self, adjustment: MediaAdjustment) -> Result<()> {
         // If there is a channel list
-        if self.channel_list.len() > 0 {
+        if !self.channel_list.is_empty() {
             // Check that the channel is valid
             if !self.channel_list.contains(&adjustment.channel) {
                 // If not, note the error
@@ -276,6 +612,11 @@ impl MediaInterface {
             }
         }
 
+        // Ignore the adjustment if using the built-in audio player
+        if self.audio_player.is_some() {
+            return Ok(());
+        }
+
         // Create the request client if it doesn't exist
         if self.client.is_none() {
             self.client = Some(Client::new());
@@ -296,4 +637,258 @@ impl MediaInterface {
         // Indicate success
         Ok(())
     }
+
+    // A helper method to pass a media cue to Apollo
+    async fn send_cue(&mut self, cue: MediaCue) -> Result<()> {
+        // If using the built-in audio player, play the cue directly
+        if let Some(ref mut player) = self.audio_player {
+            player.play(cue);
+            return Ok(());
+        }
+
+        // Create the request client if it doesn't exist
+        if self.client.is_none() {
+            self.client = Some(Client::new());
+        }
+
+        // Recompose the media cue into a helper
+        let mut helper: MediaCueHelper = cue.into();
+        helper.complete_callback = Some(self.complete_callback.clone());
+
+        // Pass the media cue to Apollo
+        self.client
+            .as_ref()
+            .unwrap()
+            .post(&format!("http://{}/cueMedia", &self.address))
+            .json(&helper)
+            .send()
+            .await?;
+
+        // Indicate success
+        Ok(())
+    }
+
+    /// A helper function to compose the address of the media complete route
+    /// from the run address (replacing an unspecified host with localhost)
+    fn complete_callback(run_address: &str) -> String {
+        let host = match run_address.parse::<std::net::SocketAddr>() {
+            Ok(mut address) if address.ip().is_unspecified() => {
+                address.set_ip(std::net::Ipv4Addr::LOCALHOST.into());
+                address.to_string()
+            }
+            _ => run_address.to_string(),
+        };
+        format!("http://{}/mediaComplete", host)
+    }
+}
+
+// Tests of the media interface module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use warp::Filter;
+
+    // Test advancing through a looping media playlist
+    #[test]
+    fn playlist_order() {
+        // Create a looping playlist with two items
+        let mut state = PlaylistState::new(MediaPlaylist {
+            items: vec![
+                PlaylistItem {
+                    uri: "file:///first.mp4".into(),
+                    event: Some(ItemId::new_unchecked(10)),
+                },
+                PlaylistItem {
+                    uri: "file:///second.mp4".into(),
+                    event: None,
+                },
+            ],
+            channel: 1,
+            shuffle: false,
+            loop_playlist: true,
+        });
+
+        // Check the order of the items, including the loop
+        assert_eq!("file:///first.mp4", state.current_cue().uri);
+        assert_eq!(Some(ItemId::new_unchecked(10)), state.current().event);
+        assert!(state.advance());
+        assert_eq!("file:///second.mp4", state.current_cue().uri);
+        assert!(state.advance());
+        assert_eq!("file:///first.mp4", state.current_cue().uri);
+
+        // Check that the playlist completes without looping
+        state.playlist.loop_playlist = false;
+        assert!(state.advance());
+        assert!(!state.advance());
+    }
+
+    // A helper function to start a stand-in for Apollo that reports each
+    // cue as complete to its callback. Returns the address of Apollo.
+    fn fake_apollo() -> String {
+        let cue_media = warp::post()
+            .and(warp::path("cueMedia"))
+            .and(warp::body::json())
+            .then(|helper: MediaCueHelper| async move {
+                if let Some(callback) = helper.complete_callback {
+                    Client::new()
+                        .post(&callback)
+                        .json(&serde_json::json!({ "channel": helper.channel, "uri": helper.uri }))
+                        .send()
+                        .await
+                        .unwrap();
+                }
+                warp::reply()
+            });
+        let (address, server) = warp::serve(cue_media).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        address.to_string()
+    }
+
+    // A helper function to start a stand-in for the media complete route of
+    // the run interface. Returns the run address and the completed media.
+    fn fake_run_interface() -> (String, mpsc::UnboundedReceiver<(u32, String)>) {
+        let (complete_send, complete_receive) = mpsc::unbounded_channel();
+        let media_complete = warp::post()
+            .and(warp::path("mediaComplete"))
+            .and(warp::body::json())
+            .map(move |complete: serde_json::Value| {
+                complete_send
+                    .send((
+                        complete["channel"].as_u64().unwrap() as u32,
+                        complete["uri"].as_str().unwrap().to_string(),
+                    ))
+                    .unwrap();
+                warp::reply()
+            });
+        let (address, server) = warp::serve(media_complete).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        (address.to_string(), complete_receive)
+    }
+
+    // Test that Apollo reports completed media to the run interface
+    #[tokio::test]
+    async fn apollo_complete() {
+        // Create a media interface for the stand-in Apollo
+        let (run_address, mut completed) = fake_run_interface();
+        let (internal_send, _internal_receive) = InternalSend::new();
+        let mut interface = MediaInterface::new(
+            ChannelMap::default(),
+            WindowMap::default(),
+            ApolloParams {
+                spawn: false,
+                address: Some(fake_apollo()),
+            },
+            MediaBackend::Apollo,
+            None,
+            &run_address,
+            internal_send,
+        )
+        .await;
+
+        // Cue media with a completion event and wait for Apollo to report it
+        interface
+            .play_cue(MediaCue {
+                uri: "file:///intro.mp4".into(),
+                channel: 1,
+                loop_media: None,
+                on_complete: Some(ItemId::new_unchecked(20)),
+            })
+            .await
+            .unwrap();
+        let (channel, uri) = completed.recv().await.unwrap();
+        assert_eq!(
+            vec![ItemId::new_unchecked(20)],
+            interface.media_complete(channel, &uri).await.0
+        );
+    }
+
+    // Test that Apollo advances a media playlist when each item completes
+    #[tokio::test]
+    async fn apollo_playlist() {
+        // Create a media interface for the stand-in Apollo
+        let (run_address, mut completed) = fake_run_interface();
+        let (internal_send, _internal_receive) = InternalSend::new();
+        let mut interface = MediaInterface::new(
+            ChannelMap::default(),
+            WindowMap::default(),
+            ApolloParams {
+                spawn: false,
+                address: Some(fake_apollo()),
+            },
+            MediaBackend::Apollo,
+            None,
+            &run_address,
+            internal_send,
+        )
+        .await;
+
+        // Start a playlist with two items and check the first item
+        let (cue, event) = interface
+            .play_playlist(MediaPlaylist {
+                items: vec![
+                    PlaylistItem {
+                        uri: "file:///first.mp4".into(),
+                        event: None,
+                    },
+                    PlaylistItem {
+                        uri: "file:///second.mp4".into(),
+                        event: Some(ItemId::new_unchecked(30)),
+                    },
+                ],
+                channel: 2,
+                shuffle: false,
+                loop_playlist: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!("file:///first.mp4", cue.uri);
+        assert_eq!(None, event);
+
+        // Check that completing the first item starts the second
+        let (channel, uri) = completed.recv().await.unwrap();
+        let (events, next_cue) = interface.media_complete(channel, &uri).await;
+        assert_eq!(vec![ItemId::new_unchecked(30)], events);
+        assert_eq!("file:///second.mp4", next_cue.unwrap().uri);
+
+        // Check that completing the second item ends the playlist
+        let (channel, uri) = completed.recv().await.unwrap();
+        let (events, next_cue) = interface.media_complete(channel, &uri).await;
+        assert!(events.is_empty());
+        assert!(next_cue.is_none());
+    }
+
+    // Test that clearing the pending events forgets completion events
+    #[tokio::test]
+    async fn clear_pending_events() {
+        // Create a media interface for the stand-in Apollo
+        let (run_address, mut completed) = fake_run_interface();
+        let (internal_send, _internal_receive) = InternalSend::new();
+        let mut interface = MediaInterface::new(
+            ChannelMap::default(),
+            WindowMap::default(),
+            ApolloParams {
+                spawn: false,
+                address: Some(fake_apollo()),
+            },
+            MediaBackend::Apollo,
+            None,
+            &run_address,
+            internal_send,
+        )
+        .await;
+
+        // Cue media with a completion event and clear it before it completes
+        interface
+            .play_cue(MediaCue {
+                uri: "file:///intro.mp4".into(),
+                channel: 1,
+                loop_media: None,
+                on_complete: Some(ItemId::new_unchecked(20)),
+            })
+            .await
+            .unwrap();
+        interface.clear_pending();
+        let (channel, uri) = completed.recv().await.unwrap();
+        assert!(interface.media_complete(channel, &uri).await.0.is_empty());
+    }
 }
@@ -22,30 +22,47 @@
 //! of the program.
 
 // Define private submodules
+mod audio_player;
 mod backup_handler;
 mod config;
 mod dmx_interface;
+mod game_logger;
+mod hint_handler;
 mod media_interface;
 mod queue;
+mod session_handler;
+mod statistics;
+mod sync_handler;
+
+// Reexport the backup lease and the validation function
+pub use self::backup_handler::BackupLease;
+pub use self::config::{
+    diff_files, export_cues_file, import_cues_file, merge_files, validate_config,
+};
 
 // Import crate definitions
 use crate::definitions::*;
 
 // Import other definitions
 use self::backup_handler::BackupHandler;
-use self::config::Config;
+use self::config::{Config, IncludeSources};
 use self::dmx_interface::DmxInterface;
+use self::game_logger::GameLogger;
+use self::hint_handler::HintHandler;
 use self::media_interface::MediaInterface;
 use self::queue::Queue;
+use self::session_handler::SessionHandler;
+use self::statistics::compute_statistics;
+use self::sync_handler::SyncHandler;
 
 // Import standard library features
 use std::env;
 use std::ffi::OsStr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 // Import Chrono features
-use chrono::NaiveDateTime;
+use chrono::{Local, NaiveDateTime};
 
 // Import Tokio features
 use tokio::fs::File;
@@ -58,7 +75,7 @@ use fnv::FnvHashMap;
 use async_recursion::async_recursion;
 
 // Import tracing features
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 // Import anyhow features
 use anyhow::Result;
@@ -75,6 +92,10 @@ pub struct EventHandler {
     config_path: PathBuf,                          // current configuration path
     index_access: IndexAccess,                     // access point to the item index
     backup: BackupHandler,                         // current backup server
+    sync: Option<SyncHandler>,                     // current link to other instances, if any
+    sessions: SessionHandler,                      // current game session tracker
+    game_log: GameLogger,                          // the structured game log
+    hints: HintHandler,                            // the hints delivered in each scene
 }
 
 // Implement the event handler functions
@@ -88,6 +109,10 @@ impl EventHandler {
     /// updates appropriately. See the event::EventUpdate enum for more detail
     /// on the possible update types.
     ///
+    /// If a previous game state is provided, the new event handler resumes
+    /// from that state in place of any backup data, keeping only the scene,
+    /// statuses, and queued events that still exist in the configuration.
+    ///
     /// # Errors
     ///
     /// This function will raise an error if the provided configuration
@@ -107,7 +132,9 @@ impl EventHandler {
         internal_send: InternalSend,
         interface_send: InterfaceSend,
         limited_send: LimitedSend,
+        run_address: &str,
         log_failure: bool,
+        resume_state: Option<GameSnapshot>,
     ) -> Result<Self> {
         // If a file was specified
         let mut config;
@@ -135,6 +162,7 @@ impl EventHandler {
                 interface_send.clone(),
                 limited_send.clone(),
                 config_file,
+                &resolved_path,
             )
             .await?;
 
@@ -153,12 +181,17 @@ impl EventHandler {
             resolved_path.push("default.yaml");
         }
 
+        // Only share the backup location with Vulcan and Apollo if it is a Redis server
+        let redis_location = config
+            .get_server_location()
+            .filter(|location| !location.starts_with("file://"));
+
         // Attempt to create any DMX interfaces
         let mut dmx_interfaces = FnvHashMap::default();
         for (universe_number, params) in config.get_dmx_controllers() {
             dmx_interfaces.insert(
                 universe_number,
-                DmxInterface::new(params, config.get_server_location()).await,
+                DmxInterface::new(params, redis_location.clone()).await,
             );
         }
 
@@ -170,7 +203,10 @@ impl EventHandler {
                     details.channel_map,
                     details.window_map,
                     details.apollo_params,
-                    config.get_server_location(),
+                    details.backend,
+                    redis_location.clone(),
+                    run_address,
+                    internal_send.clone(),
                 )
                 .await,
             );
@@ -183,9 +219,25 @@ impl EventHandler {
         let mut backup =
             BackupHandler::new(config.get_identifier(), config.get_server_location()).await;
 
-        // Check for existing data from the backup handler
-        if let Some((current_scene, mut status_pairs, queued_events)) =
-            backup.reload_backup(config.get_status_ids())
+        // Use the previous game state, if provided, or check for existing data from the backup handler
+        let mut is_resuming = false;
+        let is_reloading = resume_state.is_some();
+        let reloaded = match resume_state {
+            Some(state) => {
+                let state = EventHandler::filter_state(&mut config, state);
+                Some((
+                    state.current_scene,
+                    state.statuses,
+                    state.queued_events,
+                    state.media_playlist,
+                    state.hints,
+                ))
+            }
+            None => backup.reload_backup(config.get_status_ids()).await,
+        };
+        let mut hints = HintHandler::new();
+        if let Some((current_scene, mut status_pairs, queued_events, media_playlist, delivered)) =
+            reloaded
         {
             // Change the current scene silently (i.e. do not trigger the scene's default event)
             info!(
@@ -197,6 +249,17 @@ impl EventHandler {
             // Update the current status states based on the backup
             config.load_backup_status(status_pairs.clone()).await;
 
+            // Restore the hints delivered in each scene
+            hints.load_delivered(delivered.clone());
+
+            // Copy the previous game state to the backup, if reloading
+            if is_reloading {
+                for (status_id, state) in status_pairs.iter() {
+                    backup.backup_status(status_id, state).await;
+                }
+                backup.backup_hints(delivered).await;
+            }
+
             // Restate the all of the current states to the system, restricted by the current scene
             for (count, (_, current_state)) in status_pairs.drain(..).enumerate() {
                 queue
@@ -220,9 +283,42 @@ impl EventHandler {
             // Wait 20 nanoseconds for the queued events to process
             sleep(Duration::from_nanos(20)).await;
 
+            // Restart the media on each channel at the correct position
+            for playback in media_playlist {
+                // Keep the media that is still playing, if reloading
+                if is_reloading {
+                    backup.backup_playback(playback).await;
+                    continue;
+                }
+
+                info!(
+                    "Restoring media on channel {}: {}.",
+                    playback.media_cue.channel, playback.media_cue.uri
+                );
+
+                // Select the interface that lists the channel, or else the first that accepts any channel
+                let channel = playback.media_cue.channel;
+                let owner = media_owner(&media_interfaces, channel);
+
+                // Report the error if no media player accepted the media
+                let mut success = false;
+                if let Some(index) = owner {
+                    success = media_interfaces[index]
+                        .restore_media(playback.media_cue, playback.time_since)
+                        .await
+                        .is_ok();
+                }
+                if !success {
+                    error!("Failed to restore media cue.");
+                }
+            }
+
             // Trigger a redraw of the window and timeline
             interface_send.send(InterfaceUpdate::RefreshAll).await;
 
+            // Resume any unfinished game session
+            is_resuming = true;
+
         // If there was no existing data in the backup, trigger the scene reset event
         } else {
             queue
@@ -235,8 +331,18 @@ impl EventHandler {
             .backup_current_scene(&config.get_current_scene())
             .await;
 
-        // Return the completed EventHandler with a new queue
-        Ok(Self {
+        // Start sharing statuses and scenes with other instances, if specified
+        let mut sync = None;
+        if let Some(params) = config.get_state_sync() {
+            sync = SyncHandler::new(config.get_identifier(), params, internal_send.clone()).await;
+        }
+
+        // Load the game sessions
+        let sessions =
+            SessionHandler::new(PathBuf::from(LOG_FOLDER).join("sessions"), is_resuming).await;
+
+        // Create the completed EventHandler with a new queue
+        let mut handler = Self {
             queue,
             dmx_interfaces,
             media_interfaces,
@@ -244,7 +350,60 @@ impl EventHandler {
             config_path: resolved_path,
             index_access,
             backup,
-        })
+            sync,
+            sessions,
+            game_log: GameLogger::new(PathBuf::from(LOG_FOLDER)),
+            hints,
+        };
+
+        // Share the current scene and statuses with the other instances
+        handler.share_current_state().await;
+
+        // Return the completed EventHandler
+        Ok(handler)
+    }
+
+    /// A helper function to remove any parts of a previous game state that
+    /// no longer exist in the configuration. If the previous scene no longer
+    /// exists, the default scene of the configuration is used instead.
+    ///
+    fn filter_state(config: &mut Config, mut state: GameSnapshot) -> GameSnapshot {
+        // Keep the current scene, if it still exists
+        if config.get_scene(&state.current_scene).is_none() {
+            warn!("Previous scene no longer exists. Using the default scene.");
+            state.current_scene = config.get_current_scene();
+        }
+
+        // Keep the statuses that still exist
+        let status_ids = config.get_status_ids();
+        state
+            .statuses
+            .retain(|(status_id, _)| status_ids.contains(status_id));
+
+        // Keep the queued events that still exist
+        state
+            .queued_events
+            .retain(|event| config.get_event(&event.event_id).is_some());
+
+        // Return the remaining state
+        state
+    }
+
+    /// A method to capture the current game state, including the current
+    /// scene, statuses, queued events, and media. Returns None if there is no
+    /// current scene yet.
+    ///
+    pub fn capture_state(&self) -> Option<GameSnapshot> {
+        // Collect the current state of every status
+        let statuses = self
+            .config
+            .get_statuses()
+            .drain()
+            .map(|(status_id, description)| (status_id, description.current))
+            .collect();
+
+        // Compose the game state
+        self.backup.current_snapshot(statuses).ok()
     }
 
     /// A method to add an event to the timed queue.
@@ -253,6 +412,40 @@ impl EventHandler {
         self.queue.add_event(event_delay).await;
     }
 
+    /// A method to process a notification that media has finished playing.
+    ///
+    /// If the media was cued with an on complete event, and no other media
+    /// has been cued on the channel since, the event is added to the queue.
+    /// If the media was part of a playlist, the next item is played and its
+    /// event (if any) is added to the queue. Unless the media loops, it is
+    /// also removed from the backup.
+    ///
+    pub async fn media_complete(&mut self, channel: u32, uri: String) {
+        // Check each media interface for matching events
+        let mut events = Vec::new();
+        let mut next_cue = None;
+        let mut is_looping = false;
+        for interface in self.media_interfaces.iter_mut() {
+            let (new_events, cue) = interface.media_complete(channel, &uri).await;
+            events.extend(new_events);
+            next_cue = next_cue.or(cue);
+            is_looping |= interface.owns_channel(channel) && interface.has_loop_media(channel);
+        }
+
+        // Remove the media from the backup, unless it loops
+        self.backup.media_complete(channel, &uri, is_looping).await;
+
+        // Backup the next item of a playlist, if one was started
+        if let Some(cue) = next_cue {
+            self.backup.backup_media(cue).await;
+        }
+
+        // Cue any events that were found immediately
+        for event_id in events {
+            self.queue.add_event(EventDelay::new(None, event_id)).await;
+        }
+    }
+
     /// A method to clear the existing events in the timed queue.
     ///
     /// This method clears all the events in the timed queue, effective
@@ -284,6 +477,87 @@ impl EventHandler {
         self.config_path.clone()
     }
 
+    /// A method to check a batch of modifications against the configuration
+    /// that would result from applying the whole batch. The method returns
+    /// every new error, or an empty list if the batch is valid.
+    ///
+    pub async fn check_modifications(
+        &self,
+        modifications: &[Modification],
+    ) -> Vec<ModificationError> {
+        self.config.check_modifications(modifications).await
+    }
+
+    /// A method to compare the current configuration with the provided
+    /// configuration file and return every difference between them
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error if the file could not be read.
+    ///
+    pub async fn diff_config(&self, path: &Path) -> Result<ConfigDiff> {
+        Ok(ConfigDiff {
+            from: self.config_path.display().to_string(),
+            to: path.display().to_string(),
+            changes: self.config.diff_with(path).await?,
+        })
+    }
+
+    /// A method to return the modifications that merge the selected changes
+    /// from the provided configuration file into the current configuration
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error if the file could not be read.
+    ///
+    pub fn merge_config(&self, path: &Path, changes: &[ConfigChange]) -> Result<Vec<Modification>> {
+        self.config.merge_from(path, changes)
+    }
+
+    /// A method to return the modifications that import the cue list in the
+    /// provided CSV file into the current configuration, along with the
+    /// number of cues
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error if the file could not be read or if
+    /// any row of the cue list is not valid.
+    ///
+    pub async fn import_cues(&self, path: &Path) -> Result<(usize, Vec<Modification>)> {
+        self.config.import_cues(path).await
+    }
+
+    /// A method to export the items and events of the current configuration
+    /// to a cue list in the provided CSV file. The method returns the number
+    /// of cues exported.
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error if the file could not be written.
+    ///
+    pub async fn export_cues(&self, path: &Path) -> Result<usize> {
+        self.config.export_cues(path).await
+    }
+
+    /// A method to return the location of every file included by the
+    /// configuration
+    ///
+    pub fn get_include_paths(&self) -> Vec<PathBuf> {
+        self.config.get_include_paths()
+    }
+
+    /// A method to return a copy of the included file of each item
+    ///
+    pub fn get_includes(&self) -> IncludeSources {
+        self.config.get_includes()
+    }
+
+    /// A method to replace the included file of each item
+    ///
+    pub fn set_includes(&mut self, includes: IncludeSources) {
+        self.config.set_includes(includes);
+    }
+
     /// A method to return a copy of the system connections
     ///
     pub fn get_connections(&self) -> ConnectionSet {
@@ -361,6 +635,12 @@ impl EventHandler {
         self.config.get_media_players()
     }
 
+    /// A method to return a report of any problems with the media
+    ///
+    pub fn get_media_report(&self) -> MediaReport {
+        self.config.get_media_report()
+    }
+
     /// A method to return a scene with available events and optional keymap, given
     /// an item id
     ///
@@ -393,6 +673,52 @@ impl EventHandler {
         self.config.get_statuses()
     }
 
+    /// A method to return the latency metrics for the backup, if connected
+    pub fn get_backup_metrics(&self) -> Option<BackupMetrics> {
+        self.backup.get_metrics()
+    }
+
+    /// A method to return the latest shared statuses and scenes from all instances
+    pub fn get_sync_state(&self) -> Vec<SyncEntry> {
+        match self.sync {
+            Some(ref sync) => sync.get_entries(),
+            None => Vec::new(),
+        }
+    }
+
+    /// A method to stop sharing statuses and scenes with other instances,
+    /// waiting until the sync address has been released
+    ///
+    pub async fn stop_sync(&mut self) {
+        if let Some(sync) = self.sync.take() {
+            sync.close().await;
+        }
+    }
+
+    /// A method to close the connection to the backup without removing the
+    /// backup, so that a new event handler can take over the backup.
+    ///
+    pub fn close_backup(&mut self) {
+        self.backup.close();
+    }
+
+    /// A method to reopen the connection to the backup, if the new event
+    /// handler could not be created.
+    ///
+    pub fn reopen_backup(&mut self) {
+        self.backup.reopen();
+    }
+
+    /// A method to resume sharing statuses and scenes with other instances,
+    /// if specified in the configuration
+    ///
+    pub async fn restart_sync(&mut self, internal_send: InternalSend) {
+        if let Some(params) = self.config.get_state_sync() {
+            self.sync = SyncHandler::new(self.config.get_identifier(), params, internal_send).await;
+            self.share_current_state().await;
+        }
+    }
+
     /// A method to return the backup server location
     pub fn get_server_location(&self) -> Option<String> {
         self.config.get_server_location()
@@ -403,6 +729,22 @@ impl EventHandler {
         self.config.save_parameters(parameters).await;
     }
 
+    /// A method to return the other settings of the configuration
+    pub async fn get_settings(&self) -> ConfigSettings {
+        self.config.get_settings().await
+    }
+
+    /// A method to save the other settings of the configuration, restarting
+    /// the link to other instances if it changed
+    pub async fn save_settings(&mut self, settings: ConfigSettings, internal_send: InternalSend) {
+        let is_sync_changed = settings.state_sync != self.config.get_state_sync();
+        self.config.save_settings(settings).await;
+        if is_sync_changed {
+            self.stop_sync().await;
+            self.restart_sync(internal_send).await;
+        }
+    }
+
     /// A method to change the selected status within the current configuration.
     ///
     pub async fn modify_status(&mut self, status_id: &ItemId, new_state: &ItemId) {
@@ -413,6 +755,45 @@ impl EventHandler {
         }
     }
 
+    /// A method to merge the shared statuses and scenes received from another
+    /// instance. Shared statuses that changed are followed locally and any
+    /// matching reactions are triggered.
+    ///
+    pub async fn sync_update(&mut self, message: SyncMessage) {
+        // Merge the changes from the other instance
+        let changes = match self.sync {
+            Some(ref mut sync) => sync.merge(message),
+            None => return,
+        };
+
+        // Respond to each change
+        for entry in changes {
+            // Follow the change if the status is shared and the state is different
+            if let SyncKey::Status { status_id } = entry.key
+                && self
+                    .sync
+                    .as_ref()
+                    .is_some_and(|sync| sync.is_shared(&status_id))
+                && self.config.get_state(&status_id).await != Some(entry.value.get_id())
+            {
+                info!(
+                    "Following shared status: {} to {}.",
+                    entry.description, entry.value.description
+                );
+                self.modify_status(&status_id, &entry.value.get_id()).await;
+            }
+
+            // Trigger any reactions to the change
+            let reactions = match self.sync {
+                Some(ref sync) => sync.get_reactions(&entry),
+                None => Vec::new(),
+            };
+            for event_id in reactions {
+                self.queue.add_event(EventDelay::new(None, event_id)).await;
+            }
+        }
+    }
+
     /// A method to add or modify an event within the current configuration.
     ///
     pub async fn edit_event(&mut self, event_id: ItemId, new_event: Option<Event>) {
@@ -552,6 +933,291 @@ impl EventHandler {
         self.config_path = config_path;
     }
 
+    /// A method to write the current configuration to the provided autosave
+    /// file. Unlike save_config, the current config path is not changed.
+    ///
+    /// # Errors
+    ///
+    /// This method will fail silently if it was unable to create the desired
+    /// file. This usually indicates that there is an underlying file system
+    /// error.
+    ///
+    pub async fn autosave_config(&self, autosave_path: &Path) {
+        // Attempt to open the autosave file
+        let autosave_file = match File::create(autosave_path).await {
+            Ok(file) => file,
+            Err(_) => {
+                error!("Unable to open autosave file.");
+                return;
+            }
+        };
+
+        // Save the configuration to the autosave file
+        self.config.to_autosave(autosave_file).await;
+    }
+
+    /// A method to change the current path for the configuration (without
+    /// saving the configuration)
+    ///
+    pub fn set_config_path(&mut self, config_path: PathBuf) {
+        self.config_path = config_path;
+    }
+
+    /// A method to save the current game state as a snapshot with the
+    /// provided name, replacing any existing snapshot with the same name.
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error if the backup handler was unable to
+    /// save the snapshot.
+    ///
+    pub fn save_snapshot(&mut self, name: String) -> Result<()> {
+        // Collect the current state of every status
+        let statuses = self
+            .config
+            .get_statuses()
+            .drain()
+            .map(|(status_id, description)| (status_id, description.current))
+            .collect();
+
+        // Save the snapshot
+        self.backup.save_snapshot(name.clone(), statuses)?;
+        info!("Saved snapshot: {}.", name);
+        Ok(())
+    }
+
+    /// A method to set the team name and player count for the current game
+    /// session, or the next session if none is running
+    ///
+    pub async fn set_session_details(
+        &mut self,
+        team_name: Option<String>,
+        player_count: Option<u32>,
+    ) {
+        self.sessions.set_details(team_name, player_count).await;
+    }
+
+    /// A method to start a new game session
+    ///
+    pub async fn start_session(&mut self) {
+        self.sessions.start().await;
+        self.hints.reset();
+        self.backup.backup_hints(Vec::new()).await;
+    }
+
+    /// A method to deliver the next hint for the current scene. The hint is
+    /// sent to the limited interface, any media for the hint is cued, and the
+    /// hint penalty is applied (if specified).
+    ///
+    /// # Errors
+    ///
+    /// This method will raise a warning if there are no hints remaining for
+    /// the current scene.
+    ///
+    #[async_recursion]
+    pub async fn deliver_hint(&mut self) {
+        // Find the hints for the current scene
+        let scene_id = self.config.get_current_scene();
+        let hints = self
+            .config
+            .get_scene(&scene_id)
+            .map(|scene| scene.hints)
+            .unwrap_or_default();
+
+        // Select the next hint
+        let Some((number, hint)) = self.hints.next_hint(scene_id, &hints) else {
+            warn!("No hints remaining for the current scene.");
+            return;
+        };
+        self.backup.backup_hints(self.hints.get_delivered()).await;
+
+        // Record the hint
+        self.save_data(format!("Hint {}: {}", number, hint.message))
+            .await;
+        self.sessions.add_hint().await;
+
+        // Send the hint to the interfaces
+        self.config.send_hint(number, hint.message).await;
+
+        // Cue the media for the hint, if specified
+        if let Some(cue) = hint.media {
+            self.unpack_action(CueMedia { cue }).await;
+        }
+
+        // Apply the penalty, if specified
+        if let Some(penalty) = self.config.get_hint_penalty() {
+            self.modify_status(&penalty.status_id, &penalty.new_state)
+                .await;
+        }
+    }
+
+    /// A method to end the current game session with the provided outcome
+    ///
+    pub async fn end_session(&mut self, outcome: String) {
+        self.sessions.end(outcome).await;
+    }
+
+    /// A method to return the records of the game log that match the query
+    ///
+    pub async fn query_game_log(&self, query: GameLogQuery) -> Vec<GameRecord> {
+        self.game_log.query(&query).await
+    }
+
+    /// A method to compute the room statistics for the sessions that started
+    /// within the date range of the query
+    ///
+    pub async fn get_statistics(&self, query: GameLogQuery) -> RoomStatistics {
+        // Collect the sessions in the date range
+        let mut sessions = self.sessions.records().await;
+        sessions.retain(|session| query.includes_date(&session.start.date()));
+
+        // Collect the matching records from the game log
+        let records = self.game_log.query(&query).await;
+
+        // Look up the descriptions of the milestones
+        let params = self.config.get_statistics();
+        let mut milestones = Vec::new();
+        for milestone in params.milestones.iter() {
+            milestones.push(self.index_access.get_pair(milestone).await);
+        }
+
+        // Compute the statistics
+        compute_statistics(&sessions, &records, &milestones, &params.hints)
+    }
+
+    /// A method to return a summary of every saved game session
+    ///
+    pub async fn list_sessions(&self) -> Vec<SessionSummary> {
+        self.sessions.list().await
+    }
+
+    /// A method to return a description of each available snapshot
+    ///
+    pub fn list_snapshots(&self) -> Vec<SnapshotInfo> {
+        self.backup.list_snapshots()
+    }
+
+    /// A method to delete the snapshot with the provided name
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error if the snapshot does not exist.
+    ///
+    pub fn delete_snapshot(&mut self, name: &str) -> Result<()> {
+        self.backup.delete_snapshot(name)
+    }
+
+    /// A method to restore the game state from the snapshot with the provided
+    /// name. The event queue is replaced with the queue from the snapshot and
+    /// the current scene and statuses are changed silently (i.e. without
+    /// triggering the scene's default event). The media and dmx values are
+    /// restored to their values when the snapshot was saved.
+    ///
+    /// # Note
+    ///
+    /// As when reloading a backup, the event for the current state of each
+    /// status is triggered to restate the status to the rest of the system.
+    ///
+    /// # Errors
+    ///
+    /// This method will raise an error if the snapshot does not exist or the
+    /// scene in the snapshot is no longer valid. Any errors while restoring
+    /// the media or dmx values will be reported and otherwise ignored.
+    ///
+    pub async fn restore_snapshot(&mut self, name: &str) -> Result<()> {
+        // Try to find the snapshot
+        let snapshot = self
+            .backup
+            .get_snapshot(name)
+            .ok_or(anyhow!("Snapshot not found: {}.", name))?;
+        info!("Restoring snapshot: {}.", name);
+
+        // Change the current scene silently
+        if self
+            .choose_scene_no_broadcast(snapshot.current_scene)
+            .await
+            .is_err()
+        {
+            return Err(anyhow!("Snapshot scene is no longer valid."));
+        }
+
+        // Clear the existing event queue
+        self.queue.clear().await;
+
+        // Restore and backup the hints delivered in each scene
+        self.hints.load_delivered(snapshot.hints.clone());
+        self.backup.backup_hints(snapshot.hints.clone()).await;
+
+        // Update and backup the current status states
+        self.config
+            .load_backup_status(snapshot.statuses.clone())
+            .await;
+        for (status_id, state) in snapshot.statuses.iter() {
+            self.backup.backup_status(status_id, state).await;
+        }
+
+        // Restate the all of the current states to the system, restricted by the current scene
+        for (count, (_, current_state)) in snapshot.statuses.iter().enumerate() {
+            self.queue
+                .add_event(EventDelay::new(
+                    Some(Duration::from_millis(count as u64)),
+                    *current_state,
+                ))
+                .await; // small delay for each to keep from overwhelming the system connections
+        }
+
+        // Update the queue with the saved events
+        for event in snapshot.queued_events {
+            self.queue
+                .add_event(EventDelay::new(Some(event.remaining), event.event_id))
+                .await;
+        }
+
+        // Forget any completion events and playlists of the current game
+        for interface in self.media_interfaces.iter_mut() {
+            interface.clear_pending();
+        }
+
+        // Restart the media on each channel at the saved position
+        for playback in snapshot.media_playlist {
+            // Select the interface that lists the channel, or else the first that accepts any channel
+            let mut success = false;
+            if let Some(index) = media_owner(&self.media_interfaces, playback.media_cue.channel) {
+                success = self.media_interfaces[index]
+                    .restore_media(playback.media_cue.clone(), playback.time_since)
+                    .await
+                    .is_ok();
+            }
+
+            // If successful, backup the media at the saved position
+            if success {
+                self.backup.backup_playback(playback).await;
+
+            // Otherwise, report the error
+            } else {
+                error!("Failed to restore media cue.");
+            }
+        }
+
+        // Restore the values of each dmx universe
+        for (universe, values) in snapshot.dmx_universes.iter() {
+            // Send the values to the dmx interface, if it exists
+            if let Some(interface) = self.dmx_interfaces.get_mut(universe) {
+                if let Err(err) = interface.restore_universe(values.clone()).await {
+                    error!("Error restoring DMX universe: {}.", err);
+                }
+
+            // Warn that there is no active Dmx interface
+            } else {
+                error!("Failed to restore DMX: No DMX interface available for that universe.");
+            }
+        }
+        self.backup.restore_dmx(snapshot.dmx_universes);
+
+        // Indicate success
+        Ok(())
+    }
+
     /// A method to process a new event in the event handler. If the event was
     /// processed successfully, it will return any events that should be broadcast
     /// to the system (including their associated data, if applicable).
@@ -577,7 +1243,9 @@ impl EventHandler {
         // Try to retrieve the event and unpack the event
         if let Some(event) = self.config.try_event(event_id, checkscene).await {
             // Log the event
-            info!("Event: {}.", self.index_access.get_pair(&event_id).await);
+            let event_pair = self.index_access.get_pair(event_id).await;
+            info!("Event: {}.", event_pair);
+            self.log_game(Some(event_pair), None, None).await;
 
             // Collect the events to broadcast
             let mut broadcast_events = BroadcastEvents::new(); // collect events to broadcast from each action
@@ -639,6 +1307,12 @@ impl EventHandler {
             // Backup the current scene change
             self.backup.backup_current_scene(&scene_id).await;
 
+            // Share the scene change, if shared
+            if let Some(ref mut sync) = self.sync {
+                sync.share_scene(self.index_access.get_pair(&scene_id).await)
+                    .await;
+            }
+
             // Indicate a successful change
             Ok(())
 
@@ -661,6 +1335,21 @@ impl EventHandler {
             // Backup the status change
             self.backup.backup_status(status_id, &new_id).await;
 
+            // Log the status change
+            let status_pair = self.index_access.get_pair(status_id).await;
+            let state_pair = self.index_access.get_pair(&new_id).await;
+            self.log_game(None, Some((status_pair, state_pair)), None)
+                .await;
+
+            // Share the status change, if shared
+            if let Some(ref mut sync) = self.sync {
+                sync.share_status(
+                    self.index_access.get_pair(status_id).await,
+                    self.index_access.get_pair(&new_id).await,
+                )
+                .await;
+            }
+
             // Indicate success and return the new state
             Ok(new_id)
 
@@ -670,6 +1359,74 @@ impl EventHandler {
         }
     }
 
+    /// A helper method to save game data to the game log and the current
+    /// game session
+    ///
+    async fn save_data(&mut self, data: String) {
+        info!("Game data: {}.", data);
+        self.log_game(None, None, Some(data.clone())).await;
+        self.sessions.record(data).await;
+    }
+
+    /// A helper method to add a record to the game log with the current time,
+    /// session, and scene
+    ///
+    async fn log_game(
+        &mut self,
+        event: Option<ItemPair>,
+        status_change: Option<(ItemPair, ItemPair)>,
+        data: Option<String>,
+    ) {
+        // Separate the status and state
+        let (status, state) = match status_change {
+            Some((status, state)) => (Some(status), Some(state)),
+            None => (None, None),
+        };
+
+        // Write the record
+        self.game_log
+            .write(GameRecord {
+                time: Local::now().naive_local(),
+                session: self.sessions.current_id(),
+                scene: self
+                    .index_access
+                    .get_pair(&self.config.get_current_scene())
+                    .await,
+                event,
+                status,
+                state,
+                data,
+            })
+            .await;
+    }
+
+    /// A helper method to share the current scene and statuses with the other
+    /// instances, if sharing is enabled
+    ///
+    async fn share_current_state(&mut self) {
+        // Ignore if not sharing
+        if let Some(ref mut sync) = self.sync {
+            // Share the current scene
+            sync.share_scene(
+                self.index_access
+                    .get_pair(&self.config.get_current_scene())
+                    .await,
+            )
+            .await;
+
+            // Share the current state of each status
+            for status_id in self.config.get_status_ids() {
+                if let Some(state) = self.config.get_state(&status_id).await {
+                    sync.share_status(
+                        self.index_access.get_pair(&status_id).await,
+                        self.index_access.get_pair(&state).await,
+                    )
+                    .await;
+                }
+            }
+        }
+    }
+
     /// An internal method to unpack the event and act on it. If the
     /// event results in data to broadcast, the data will be returned.
     ///
@@ -722,8 +1479,12 @@ impl EventHandler {
             CueDmx { fade } => {
                 // Send it to the dmx interface, if it exists
                 if let Some(interface) = self.dmx_interfaces.get_mut(&fade.universe.unwrap_or(0)) {
-                    if let Err(err) = interface.play_fade(fade.clone()).await {
-                        error!("Error with DMX playback: {}.", err);
+                    match interface.play_fade(fade.clone()).await {
+                        // If successful, record the fade for snapshots
+                        Ok(_) => self.backup.backup_dmx(&fade),
+
+                        // Otherwise, report the error
+                        Err(err) => error!("Error with DMX playback: {}.", err),
                     }
 
                 // Warn that there is no active Dmx interface
@@ -766,12 +1527,38 @@ impl EventHandler {
                     }
                 }
 
+                // If successful, backup the media cue
+                if success {
+                    self.backup.backup_media(cue).await;
+
                 // Otherwise, report the error
-                if !success {
+                } else {
                     error!("Failed to play media cue.");
                 }
             }
 
+            // If there is a media playlist to cue, send it to the media connection
+            CueMediaPlaylist { playlist } => {
+                // Send the playlist only to the interface that owns the channel
+                let result = match media_owner(&self.media_interfaces, playlist.channel) {
+                    Some(index) => self.media_interfaces[index].play_playlist(playlist).await,
+                    None => Err(anyhow!("No media interface for channel.")),
+                };
+
+                // If successful, backup the first item and cue its event
+                match result {
+                    Ok((cue, event)) => {
+                        self.backup.backup_media(cue).await;
+                        if let Some(event_id) = event {
+                            self.queue.add_event(EventDelay::new(None, event_id)).await;
+                        }
+                    }
+
+                    // Otherwise, report the error
+                    Err(error) => error!("Failed to play media playlist: {}.", error),
+                }
+            }
+
             // If there is media to adjust, send it to the media connection
             AdjustMedia { adjustment } => {
                 // Send the cue to each media interface in turn
@@ -810,7 +1597,7 @@ impl EventHandler {
                             let data_string = format!("Time {}:{}", minutes, seconds);
 
                             // Save the data to the game log
-                            info!(target: GAME_LOG, "Game data: {}.", data_string);
+                            self.save_data(data_string).await;
                         }
                     }
 
@@ -831,7 +1618,7 @@ impl EventHandler {
                                 let data_string = format!("Time {}:{}", minutes, seconds);
 
                                 // Save the data to the game log
-                                info!(target: GAME_LOG, "Game data: {}.", data_string);
+                                self.save_data(data_string).await;
                             }
                         }
                     }
@@ -839,11 +1626,26 @@ impl EventHandler {
                     // Send the static string to the event
                     DataType::StaticString { string } => {
                         // Save the string to the game log
-                        info!(target: GAME_LOG, "Game data: {}.", string);
+                        self.save_data(string).await;
                     }
                 }
             }
 
+            // If there is a session to start, start it
+            StartSession {} => {
+                self.start_session().await;
+            }
+
+            // If there is a hint to deliver, deliver it
+            DeliverHint {} => {
+                self.deliver_hint().await;
+            }
+
+            // If there is a session to end, end it
+            EndSession { outcome } => {
+                self.sessions.end(outcome).await;
+            }
+
             // If there is data to send, collect and send it
             SendData { data } => {
                 // Select for the type of data
@@ -970,6 +1772,21 @@ enum UnpackResult {
     Data(Vec<u32>),
 }
 
+/// A helper function to select the media interface that plays a channel:
+/// the interface that lists the channel, or else the first interface that
+/// accepts any channel.
+///
+fn media_owner(media_interfaces: &[MediaInterface], channel: u32) -> Option<usize> {
+    media_interfaces
+        .iter()
+        .position(|interface| interface.lists_channel(channel))
+        .or_else(|| {
+            media_interfaces
+                .iter()
+                .position(|interface| interface.owns_channel(channel))
+        })
+}
+
 // Tests of the event handler module
 #[cfg(test)]
 mod tests {
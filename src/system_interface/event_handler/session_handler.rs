@@ -0,0 +1,314 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to track each game session. Every session is saved to its own
+//! record in the session folder, along with any game data saved during the
+//! session.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import standard library features
+use std::path::PathBuf;
+
+// Import Chrono features
+use chrono::{Local, NaiveDateTime};
+
+// Import Tokio features
+use tokio::fs;
+
+// Import tracing features
+use tracing::{error, info, warn};
+
+// Import anyhow features
+use anyhow::Result;
+
+/// The outcome recorded for a session that was replaced by a new session
+pub const ABANDONED: &str = "Abandoned";
+
+/// A structure to track the current game session and save each session record
+///
+pub struct SessionHandler {
+    folder: PathBuf,                        // the folder for the session records
+    current: Option<SessionRecord>,         // the current session, if one is running
+    details: (Option<String>, Option<u32>), // the team name and player count for the next session
+}
+
+// Implement key functionality for the session handler
+impl SessionHandler {
+    /// A function to create a new session handler. If the resume flag is set,
+    /// the most recent unfinished session is resumed.
+    ///
+    pub async fn new(folder: PathBuf, is_resuming: bool) -> Self {
+        // Find the most recent unfinished session, if resuming
+        let mut current = None;
+        if is_resuming {
+            current = SessionHandler::load_records(&folder)
+                .await
+                .into_iter()
+                .filter(|record| record.end.is_none())
+                .max_by_key(|record| record.start);
+
+            // Notify of the resumed session
+            if let Some(ref record) = current {
+                info!("Resuming game session: {}.", record.id);
+            }
+        }
+
+        // Return the new session handler
+        Self {
+            folder,
+            current,
+            details: (None, None),
+        }
+    }
+
+    /// A method to set the team name and player count of the current session,
+    /// or of the next session if none is running
+    ///
+    pub async fn set_details(&mut self, team_name: Option<String>, player_count: Option<u32>) {
+        // Update the current session, if running
+        if let Some(ref mut record) = self.current {
+            record.team_name = team_name;
+            record.player_count = player_count;
+            self.save_current().await;
+
+        // Otherwise, save the details for the next session
+        } else {
+            self.details = (team_name, player_count);
+        }
+    }
+
+    /// A method to start a new session. Any session that is still running is
+    /// ended and marked as abandoned.
+    ///
+    pub async fn start(&mut self) {
+        // End any session that is still running
+        if self.current.is_some() {
+            warn!("Previous game session was not ended.");
+            self.end(ABANDONED.into()).await;
+        }
+
+        // Create the new session with any saved details
+        let start = Local::now().naive_local();
+        let (team_name, player_count) = std::mem::take(&mut self.details);
+        let record = SessionRecord {
+            id: self.new_id(&start).await,
+            team_name,
+            player_count,
+            start,
+            end: None,
+            outcome: None,
+            hint_count: 0,
+            data: Vec::new(),
+        };
+        info!("Starting game session: {}.", record.id);
+
+        // Save the new session
+        self.current = Some(record);
+        self.save_current().await;
+    }
+
+    /// A method to end the current session with the provided outcome
+    ///
+    /// # Errors
+    ///
+    /// This method will raise a warning if there is no session running.
+    ///
+    pub async fn end(&mut self, outcome: String) {
+        // Update the current session, if running
+        if let Some(ref mut record) = self.current {
+            record.end = Some(Local::now().naive_local());
+            record.outcome = Some(outcome);
+            info!("Ending game session: {}.", record.id);
+            self.save_current().await;
+            self.current = None;
+
+        // Otherwise, warn that there was no session
+        } else {
+            warn!("No game session to end.");
+        }
+    }
+
+    /// A method to add game data to the current session, if running
+    ///
+    pub async fn record(&mut self, data: String) {
+        // Add the data to the current session
+        if let Some(ref mut record) = self.current {
+            record.data.push(SessionData {
+                time: Local::now().naive_local(),
+                data,
+            });
+            self.save_current().await;
+        }
+    }
+
+    /// A method to count a hint delivered during the current session, if
+    /// running
+    ///
+    pub async fn add_hint(&mut self) {
+        // Increment the hint count of the current session
+        if let Some(ref mut record) = self.current {
+            record.hint_count += 1;
+            self.save_current().await;
+        }
+    }
+
+    /// A method to return the id of the current session, if running
+    ///
+    pub fn current_id(&self) -> Option<String> {
+        self.current.as_ref().map(|record| record.id.clone())
+    }
+
+    /// A method to return a summary of every saved session, from oldest to
+    /// newest
+    ///
+    pub async fn list(&self) -> Vec<SessionSummary> {
+        self.records()
+            .await
+            .iter()
+            .map(|record| record.into())
+            .collect()
+    }
+
+    /// A method to return every saved session record, from oldest to newest
+    ///
+    pub async fn records(&self) -> Vec<SessionRecord> {
+        SessionHandler::load_records(&self.folder).await
+    }
+
+    /// A helper method to save the current session to its record
+    ///
+    async fn save_current(&self) {
+        // Save the record, if there is one
+        if let Some(ref record) = self.current
+            && let Err(error) = self.write_record(record).await
+        {
+            error!("Unable to save game session: {}.", error);
+        }
+    }
+
+    /// A helper method to compose a unique id for a session from its start
+    /// time, adding a counter if a record with the same id already exists
+    ///
+    async fn new_id(&self, start: &NaiveDateTime) -> String {
+        // Use the start time, to the millisecond
+        let base = start.format("%Y%m%d-%H%M%S-%3f").to_string();
+        let mut id = base.clone();
+
+        // Add a counter until the id is unused
+        let mut count = 1;
+        while fs::try_exists(self.folder.join(format!("{}.json", id)))
+            .await
+            .unwrap_or(false)
+        {
+            id = format!("{}-{}", base, count);
+            count += 1;
+        }
+        id
+    }
+
+    /// A helper method to write a session record to the session folder
+    ///
+    async fn write_record(&self, record: &SessionRecord) -> Result<()> {
+        fs::create_dir_all(&self.folder).await?;
+        let path = self.folder.join(format!("{}.json", record.id));
+        fs::write(path, serde_json::to_string_pretty(record)?).await?;
+        Ok(())
+    }
+
+    /// A helper function to load every session record from the session
+    /// folder, sorted by start time
+    ///
+    async fn load_records(folder: &PathBuf) -> Vec<SessionRecord> {
+        // Try to open the folder (it will not exist before the first session)
+        let mut records = Vec::new();
+        let mut entries = match fs::read_dir(folder).await {
+            Ok(entries) => entries,
+            Err(_) => return records,
+        };
+
+        // Load each record
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            // Ignore other files
+            let path = entry.path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+
+            // Try to read the record
+            match fs::read_to_string(&path).await {
+                Ok(text) => match serde_json::from_str::<SessionRecord>(&text) {
+                    Ok(record) => records.push(record),
+                    Err(error) => warn!("Invalid game session {:?}: {}.", path, error),
+                },
+                Err(error) => warn!("Unable to read game session {:?}: {}.", path, error),
+            }
+        }
+
+        // Sort and return the records
+        records.sort_by_key(|record| record.start);
+        records
+    }
+}
+
+// Tests of the session handler module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test starting, recording, ending, and listing sessions
+    #[tokio::test]
+    async fn game_sessions() {
+        // Create a session handler in the temporary directory
+        let folder = std::env::temp_dir().join("minerva_session_test");
+        fs::remove_dir_all(&folder).await.unwrap_or(());
+        let mut sessions = SessionHandler::new(folder.clone(), false).await;
+
+        // Start a session with details and record some data
+        sessions.set_details(Some("Owls".into()), Some(4)).await;
+        sessions.start().await;
+        sessions.record("Time 10:00".into()).await;
+        sessions.add_hint().await;
+        let id = sessions.current_id().unwrap();
+
+        // Check that an unfinished session is resumed
+        let mut resumed = SessionHandler::new(folder.clone(), true).await;
+        assert_eq!(Some(id.clone()), resumed.current_id());
+
+        // End the session and check the summary
+        resumed.end("Escaped".into()).await;
+        let summaries = resumed.list().await;
+        assert_eq!(1, summaries.len());
+        assert_eq!(id, summaries[0].id);
+        assert_eq!(Some("Owls".to_string()), summaries[0].team_name);
+        assert_eq!(Some(4), summaries[0].player_count);
+        assert_eq!(Some("Escaped".to_string()), summaries[0].outcome);
+        assert!(summaries[0].duration.is_some());
+        assert_eq!(1, summaries[0].hint_count);
+
+        // Check that sessions started together have different records
+        resumed.start().await;
+        let first_id = resumed.current_id().unwrap();
+        resumed.start().await;
+        assert_ne!(Some(first_id), resumed.current_id());
+        assert_eq!(3, resumed.list().await.len());
+
+        // Clean up the folder
+        fs::remove_dir_all(&folder).await.unwrap_or(());
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to compute the room statistics from the saved game sessions and
+//! the records of the game log.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import other definitions
+use super::session_handler::ABANDONED;
+
+// Import FNV HashMap
+use fnv::FnvHashMap;
+
+/// The number of events to include in the most triggered events
+const TOP_EVENTS: usize = 10;
+
+/// A function to compute the room statistics for the provided sessions
+///
+/// # Notes
+///
+/// The game records are matched to each session by the session id. Records
+/// without a session are only included in the most triggered events.
+///
+pub fn compute_statistics(
+    sessions: &[SessionRecord],
+    records: &[GameRecord],
+    milestones: &[ItemPair],
+    hints: &[ItemId],
+) -> RoomStatistics {
+    // Sort the events by session and count every event
+    let mut session_events: FnvHashMap<&str, Vec<&GameRecord>> = FnvHashMap::default();
+    let mut event_counts: FnvHashMap<ItemPair, u32> = FnvHashMap::default();
+    for record in records.iter() {
+        if let Some(ref event) = record.event {
+            *event_counts.entry(event.clone()).or_insert(0) += 1;
+            if let Some(ref session) = record.session {
+                session_events.entry(session).or_default().push(record);
+            }
+        }
+    }
+
+    // Compute the statistics for each session
+    let mut session_statistics = Vec::new();
+    for session in sessions.iter() {
+        // Find the events for this session
+        let events = session_events
+            .get(session.id.as_str())
+            .cloned()
+            .unwrap_or_default();
+
+        // Count the hints from the hint events and the hints delivered directly
+        let hint_count = session.hint_count
+            + events
+                .iter()
+                .filter(|record| {
+                    record
+                        .event
+                        .as_ref()
+                        .is_some_and(|event| hints.contains(&event.get_id()))
+                })
+                .count() as u32;
+
+        // Find the first time each milestone was reached
+        let milestone_times = milestones
+            .iter()
+            .map(|milestone| MilestoneTime {
+                milestone: milestone.clone(),
+                seconds: events
+                    .iter()
+                    .filter(|record| record.event.as_ref() == Some(milestone))
+                    .map(|record| (record.time - session.start).num_seconds().max(0) as u64)
+                    .min(),
+            })
+            .collect();
+
+        // Add the session statistics
+        let summary = SessionSummary::from(session);
+        session_statistics.push(SessionStatistics {
+            id: summary.id,
+            team_name: summary.team_name,
+            player_count: summary.player_count,
+            duration: summary.duration,
+            outcome: summary.outcome,
+            hint_count,
+            milestones: milestone_times,
+        });
+    }
+
+    // Compute the completion rate and average duration
+    let completed: Vec<&SessionStatistics> = session_statistics
+        .iter()
+        .filter(|session| {
+            session.duration.is_some()
+                && session
+                    .outcome
+                    .as_ref()
+                    .is_none_or(|outcome| outcome != ABANDONED)
+        })
+        .collect();
+    let session_count = session_statistics.len() as u32;
+    let completed_count = completed.len() as u32;
+    let average_duration = average(completed.iter().filter_map(|session| session.duration));
+
+    // Compute the average number of hints
+    let mut average_hints = 0.0;
+    let mut completion_rate = 0.0;
+    if session_count > 0 {
+        let total_hints: u32 = session_statistics
+            .iter()
+            .map(|session| session.hint_count)
+            .sum();
+        average_hints = total_hints as f64 / session_count as f64;
+        completion_rate = completed_count as f64 / session_count as f64;
+    }
+
+    // Summarize each milestone
+    let milestone_summaries = milestones
+        .iter()
+        .enumerate()
+        .map(|(index, milestone)| {
+            let times: Vec<u64> = session_statistics
+                .iter()
+                .filter_map(|session| session.milestones[index].seconds)
+                .collect();
+            MilestoneSummary {
+                milestone: milestone.clone(),
+                reached_count: times.len() as u32,
+                average_seconds: average(times.iter().copied()),
+                fastest_seconds: times.iter().copied().min(),
+            }
+        })
+        .collect();
+
+    // Find the most triggered events
+    let mut top_events: Vec<EventCount> = event_counts
+        .into_iter()
+        .map(|(event, count)| EventCount { event, count })
+        .collect();
+    top_events.sort_by(|first, second| {
+        second
+            .count
+            .cmp(&first.count)
+            .then(first.event.cmp(&second.event))
+    });
+    top_events.truncate(TOP_EVENTS);
+
+    // Return the statistics
+    RoomStatistics {
+        session_count,
+        completed_count,
+        completion_rate,
+        average_duration,
+        average_hints,
+        milestones: milestone_summaries,
+        top_events,
+        sessions: session_statistics,
+    }
+}
+
+/// A helper function to find the average of the values, if there are any
+///
+fn average<I: Iterator<Item = u64>>(values: I) -> Option<u64> {
+    let (total, count) = values.fold((0, 0), |(total, count), value| (total + value, count + 1));
+    total.checked_div(count)
+}
+
+// Tests of the statistics module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test computing statistics for a completed and an abandoned session
+    #[test]
+    fn room_statistics() {
+        // Create the two sessions
+        let start = chrono::NaiveDate::from_ymd_opt(2026, 3, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let minutes = |count| start + chrono::Duration::minutes(count);
+        let session = |id: &str, end: i64, outcome: &str| SessionRecord {
+            id: id.into(),
+            team_name: None,
+            player_count: None,
+            start,
+            end: Some(minutes(end)),
+            outcome: Some(outcome.into()),
+            hint_count: 0,
+            data: Vec::new(),
+        };
+        let sessions = vec![
+            session("first", 50, "Escaped"),
+            session("second", 20, ABANDONED),
+        ];
+
+        // Create the events for each session
+        let milestone = ItemPair::new_unchecked(10, "Safe Opened");
+        let hint = ItemPair::new_unchecked(20, "Hint");
+        let record = |session: &str, time, event: &ItemPair| GameRecord {
+            time: minutes(time),
+            session: Some(session.into()),
+            scene: ItemPair::new_unchecked(100, "Scene"),
+            event: Some(event.clone()),
+            status: None,
+            state: None,
+            data: None,
+        };
+        let records = vec![
+            record("first", 5, &hint),
+            record("first", 10, &milestone),
+            record("first", 30, &milestone),
+            record("second", 5, &hint),
+            record("second", 6, &hint),
+        ];
+
+        // Compute and check the statistics
+        let statistics =
+            compute_statistics(&sessions, &records, &[milestone.clone()], &[hint.get_id()]);
+        assert_eq!(2, statistics.session_count);
+        assert_eq!(1, statistics.completed_count);
+        assert_eq!(0.5, statistics.completion_rate);
+        assert_eq!(Some(3000), statistics.average_duration);
+        assert_eq!(1.5, statistics.average_hints);
+        assert_eq!(1, statistics.milestones[0].reached_count);
+        assert_eq!(Some(600), statistics.milestones[0].fastest_seconds);
+        assert_eq!(hint, statistics.top_events[0].event);
+        assert_eq!(3, statistics.top_events[0].count);
+
+        // Check the exported rows
+        let csv = statistics.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            "Session,Team Name,Player Count,Duration (s),Outcome,Hints,Safe Opened (s)",
+            lines[0]
+        );
+        assert_eq!("first,,,3000,Escaped,1,600", lines[1]);
+        assert_eq!("second,,,1200,Abandoned,2,", lines[2]);
+    }
+}
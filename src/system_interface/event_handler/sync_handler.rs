@@ -0,0 +1,453 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to share selected statuses and scenes with other instances of
+//! Minerva over UDP.
+//!
+//! Each shared value is a last-writer-wins register. Every change carries a
+//! version made of a logical clock and the identifier of the instance that
+//! made it, and every instance keeps the value with the highest version. The
+//! instances periodically resend everything they know, so all instances
+//! settle on the same values regardless of the order (or loss) of messages.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import standard library features
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// Import Tokio features
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+
+// Import FNV HashMap
+use fnv::FnvHashMap;
+
+// Import tracing features
+use tracing::{error, info, warn};
+
+/// The time between resending all known values to the other instances
+const RESEND_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The largest message that can be received from another instance
+const MAX_MESSAGE: usize = 65507;
+
+/// A structure to share statuses and scenes with other instances
+///
+/// # Notes
+///
+/// When the sync handler is dropped, the background thread is closed. Use
+/// close() instead to wait until the socket has been released.
+///
+pub struct SyncHandler {
+    identifier: u32,    // the identifier of this instance
+    params: SyncParams, // the values to share and the other instances
+    clock: u64,         // the logical clock for new changes
+    entries: Arc<Mutex<FnvHashMap<SyncKey, SyncEntry>>>, // the latest value of each shared scene or status
+    socket: Arc<UdpSocket>,                              // the socket to send and receive updates
+    thread: JoinHandle<()>,                              // the background thread
+    _close_sender: mpsc::Sender<()>, // a line to notify the background thread to close
+                                     // the line is never used, but is poisoned when dropped
+}
+
+// Implement key functionality for the sync handler
+impl SyncHandler {
+    /// A function to create a new sync handler and start listening for
+    /// updates from the other instances.
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error and return None if the listening
+    /// address could not be bound.
+    ///
+    pub async fn new(
+        identifier: Identifier,
+        params: SyncParams,
+        internal_send: InternalSend,
+    ) -> Option<Self> {
+        // Warn if the instance does not have an identifier
+        if identifier.id.is_none() {
+            warn!("Sharing statuses without an identifier. Other instances may conflict.");
+        }
+        let identifier = identifier.id.unwrap_or(UNIVERSAL_IDENTIFIER);
+
+        // Try to create the socket
+        let socket = match UdpSocket::bind(&params.address).await {
+            Ok(socket) => Arc::new(socket),
+            Err(error) => {
+                error!("Unable to listen for shared statuses: {}.", error);
+                return None;
+            }
+        };
+        info!("Sharing statuses on {}.", params.address);
+
+        // Spawn the background thread
+        let (_close_sender, close_receiver) = mpsc::channel(1); // don't need space for any messages
+        let entries = Arc::new(Mutex::new(FnvHashMap::default()));
+        let thread = tokio::spawn(SyncHandler::run_loop(
+            identifier,
+            params.peers.clone(),
+            socket.clone(),
+            entries.clone(),
+            internal_send,
+            close_receiver,
+        ));
+
+        // Return the new sync handler
+        Some(Self {
+            identifier,
+            params,
+            clock: 0,
+            entries,
+            socket,
+            thread,
+            _close_sender,
+        })
+    }
+
+    /// A method to close the background thread and wait until the socket
+    /// has been released, so that the address can be bound again
+    ///
+    pub async fn close(self) {
+        // Poison the close line and release the local copy of the socket
+        drop(self._close_sender);
+        drop(self.socket);
+
+        // Wait for the background thread to finish
+        self.thread.await.unwrap_or(());
+    }
+
+    /// A method to share a change to a status, if the status is shared
+    ///
+    pub async fn share_status(&mut self, status: ItemPair, state: ItemPair) {
+        // Ignore statuses that aren't shared
+        if !self.params.statuses.contains(&status.get_id()) {
+            return;
+        }
+
+        // Share the new state
+        let key = SyncKey::Status {
+            status_id: status.get_id(),
+        };
+        self.share(key, status.description, state).await;
+    }
+
+    /// A method to share a change to the current scene, if the scene is shared
+    ///
+    pub async fn share_scene(&mut self, scene: ItemPair) {
+        // Ignore the scene if it isn't shared
+        if !self.params.share_scene {
+            return;
+        }
+
+        // Share the new scene
+        let key = SyncKey::Scene {
+            identifier: self.identifier,
+        };
+        self.share(key, "Current Scene".into(), scene).await;
+    }
+
+    /// A method to merge the values received from another instance. The
+    /// method returns the entries that changed.
+    ///
+    pub fn merge(&mut self, message: SyncMessage) -> Vec<SyncEntry> {
+        // Try to access the entries
+        let mut entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        // Keep the newer version of each entry
+        let mut changes = Vec::new();
+        for entry in message.entries {
+            // Advance the clock past any change that has been seen
+            self.clock = self.clock.max(entry.version.clock);
+
+            // Replace the entry if it is newer
+            if entries
+                .get(&entry.key)
+                .is_none_or(|existing| existing.version < entry.version)
+            {
+                entries.insert(entry.key, entry.clone());
+                changes.push(entry);
+            }
+        }
+
+        // Return the changes
+        changes
+    }
+
+    /// A method to check if the status is shared with other instances
+    ///
+    pub fn is_shared(&self, status_id: &ItemId) -> bool {
+        self.params.statuses.contains(status_id)
+    }
+
+    /// A method to return the local events to trigger for a changed entry
+    ///
+    pub fn get_reactions(&self, entry: &SyncEntry) -> Vec<ItemId> {
+        // Find the instance that reported the change
+        let identifier = match entry.key {
+            SyncKey::Scene { identifier } => identifier,
+            SyncKey::Status { .. } => entry.version.identifier,
+        };
+
+        // Collect the matching reactions
+        self.params
+            .reactions
+            .iter()
+            .filter(|reaction| {
+                reaction.item_id == entry.value.get_id()
+                    && reaction.identifier.is_none_or(|id| id == identifier)
+            })
+            .map(|reaction| reaction.event_id)
+            .collect()
+    }
+
+    /// A method to return the latest value of every shared scene and status,
+    /// sorted by key
+    ///
+    pub fn get_entries(&self) -> Vec<SyncEntry> {
+        // Copy the entries
+        let mut entries: Vec<SyncEntry> = match self.entries.lock() {
+            Ok(entries) => entries.values().cloned().collect(),
+            Err(_) => Vec::new(),
+        };
+
+        // Sort and return the entries
+        entries.sort_by_key(|entry| entry.key);
+        entries
+    }
+
+    /// A helper method to record a local change and send it to the other
+    /// instances
+    ///
+    async fn share(&mut self, key: SyncKey, description: String, value: ItemPair) {
+        // Record the change, unless it matches the current value
+        let entry = {
+            // Try to access the entries
+            let mut entries = match self.entries.lock() {
+                Ok(entries) => entries,
+                Err(_) => return,
+            };
+
+            // Ignore changes that match the current value (e.g. a change received from another instance)
+            if entries
+                .get(&key)
+                .is_some_and(|existing| existing.value == value)
+            {
+                return;
+            }
+
+            // Create the new version of the entry
+            self.clock += 1;
+            let entry = SyncEntry {
+                key,
+                description,
+                value,
+                version: SyncVersion {
+                    clock: self.clock,
+                    identifier: self.identifier,
+                },
+            };
+            entries.insert(key, entry.clone());
+            entry
+        };
+
+        // Send the change to the other instances
+        SyncHandler::send_entries(
+            &self.socket,
+            self.identifier,
+            &self.params.peers,
+            vec![entry],
+        )
+        .await;
+    }
+
+    /// A helper function to send entries to each of the other instances
+    ///
+    async fn send_entries(
+        socket: &UdpSocket,
+        identifier: u32,
+        peers: &[String],
+        entries: Vec<SyncEntry>,
+    ) {
+        // Ignore an empty list
+        if entries.is_empty() {
+            return;
+        }
+
+        // Try to compose the message
+        let message = match serde_json::to_vec(&SyncMessage {
+            identifier,
+            entries,
+        }) {
+            Ok(message) => message,
+            Err(error) => {
+                error!("Unable to compose shared statuses: {}.", error);
+                return;
+            }
+        };
+
+        // Send the message to each instance
+        for peer in peers {
+            if let Err(error) = socket.send_to(&message, peer).await {
+                warn!("Unable to share statuses with {}: {}.", peer, error);
+            }
+        }
+    }
+
+    /// A helper function to receive updates from other instances and to
+    /// periodically resend all known values
+    ///
+    async fn run_loop(
+        identifier: u32,
+        peers: Vec<String>,
+        socket: Arc<UdpSocket>,
+        entries: Arc<Mutex<FnvHashMap<SyncKey, SyncEntry>>>,
+        internal_send: InternalSend,
+        mut close_receiver: mpsc::Receiver<()>,
+    ) {
+        // Run until the handler is dropped
+        let mut buffer = vec![0; MAX_MESSAGE];
+        let mut resend = interval(RESEND_INTERVAL);
+        loop {
+            tokio::select! {
+                // Resend all known values
+                _ = resend.tick() => {
+                    let all_entries = match entries.lock() {
+                        Ok(entries) => entries.values().cloned().collect(),
+                        Err(_) => Vec::new(),
+                    };
+                    SyncHandler::send_entries(&socket, identifier, &peers, all_entries).await;
+                }
+
+                // Pass along updates from other instances
+                result = socket.recv(&mut buffer) => {
+                    match result {
+                        // Try to read the message
+                        Ok(length) => match serde_json::from_slice::<SyncMessage>(&buffer[..length]) {
+                            // Ignore messages from this instance
+                            Ok(message) if message.identifier == identifier => (),
+
+                            // Pass the message to the system interface
+                            Ok(message) => internal_send.send_sync_update(message).await,
+
+                            // Warn of an invalid message
+                            Err(error) => warn!("Invalid shared status message: {}.", error),
+                        },
+
+                        // Warn of an error with the socket
+                        Err(error) => error!("Unable to read shared statuses: {}.", error),
+                    }
+                }
+
+                // Close the thread when the handler is dropped
+                _ = close_receiver.recv() => return,
+            }
+        }
+    }
+}
+
+// Tests of the sync handler module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that shared statuses reach other instances and converge
+    #[tokio::test]
+    async fn share_statuses() {
+        // Create two instances that share a status, on unused ports (only the
+        // first instance sends to the other)
+        let status = ItemPair::new_unchecked(10, "Puzzle");
+        let params = SyncParams {
+            address: "127.0.0.1:0".into(),
+            peers: Vec::new(),
+            statuses: vec![status.get_id()],
+            share_scene: true,
+            reactions: vec![SyncReaction {
+                item_id: ItemId::new_unchecked(12),
+                event_id: ItemId::new_unchecked(20),
+                identifier: Some(1),
+            }],
+        };
+        let (first_send, _first_receive) = InternalSend::new();
+        let (second_send, mut second_receive) = InternalSend::new();
+        let mut second = SyncHandler::new(Identifier { id: Some(2) }, params.clone(), second_send)
+            .await
+            .unwrap();
+        let mut first = SyncHandler::new(
+            Identifier { id: Some(1) },
+            SyncParams {
+                peers: vec![second.socket.local_addr().unwrap().to_string()],
+                ..params
+            },
+            first_send,
+        )
+        .await
+        .unwrap();
+
+        // Change the status on the first instance and check the second receives it
+        first
+            .share_status(status.clone(), ItemPair::new_unchecked(11, "Unsolved"))
+            .await;
+        let message = match second_receive.recv().await {
+            Some(InternalUpdate::SyncUpdate(message)) => message,
+            _ => panic!("Expected a sync update."),
+        };
+        assert_eq!(1, second.merge(message).len());
+
+        // Make conflicting changes and merge them in opposite orders
+        first
+            .share_status(status.clone(), ItemPair::new_unchecked(12, "Solved"))
+            .await;
+        second
+            .share_status(status.clone(), ItemPair::new_unchecked(13, "Skipped"))
+            .await;
+        let first_entries = first.get_entries();
+        let second_entries = second.get_entries();
+        second.merge(SyncMessage {
+            identifier: 1,
+            entries: first_entries,
+        });
+        first.merge(SyncMessage {
+            identifier: 2,
+            entries: second_entries,
+        });
+
+        // Check that both instances settled on the same state
+        assert_eq!(first.get_entries(), second.get_entries());
+        assert_eq!(
+            ItemId::new_unchecked(13),
+            first.get_entries()[0].value.get_id()
+        );
+
+        // Check the reactions to the solved state
+        let mut solved = first.get_entries()[0].clone();
+        solved.value = ItemPair::new_unchecked(12, "Solved");
+        solved.version.identifier = 1;
+        assert_eq!(
+            vec![ItemId::new_unchecked(20)],
+            second.get_reactions(&solved)
+        );
+        solved.version.identifier = 2;
+        assert!(second.get_reactions(&solved).is_empty());
+    }
+}
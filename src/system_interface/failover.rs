@@ -0,0 +1,542 @@
+// Copyright (c) 2026 Decode Detroit
+// Author: Patton Doyle
+// Licence: GNU GPLv3
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module to run two instances of Minerva as a primary and a hot standby.
+//! The primary sends a heartbeat to the standby over UDP. The standby waits
+//! without loading its configuration and takes over if the heartbeats stop.
+//! The standby only takes over once it has received a heartbeat, so a standby
+//! started before the primary (or without one) waits until the primary
+//! appears.
+//!
+//! The standby follows the state of the primary through the shared backup
+//! server: both instances must use the same configuration (with the same
+//! identifier and server location). When the standby takes over, it loads
+//! the configuration and reloads the backup left by the primary, restoring
+//! the current scene, statuses, event queue, and media, and then connects to
+//! the system connections.
+//!
+//! Each message is signed with a secret shared by both instances, so that
+//! the standby ignores messages from any other source. If the backup server
+//! is a Redis server, the instance in control also holds a lease in the
+//! backup server. The standby only takes over once it has taken the lease,
+//! so a primary that is still running (but can't reach the standby) is never
+//! joined by a second instance in control.
+
+// Import crate definitions
+use crate::definitions::*;
+
+// Import other definitions
+use super::event_handler::BackupLease;
+
+// Import standard library features
+use std::path::PathBuf;
+use std::time::Duration;
+
+// Import Tokio features
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use tokio::time::{interval, timeout};
+
+// Import tracing features
+use tracing::{error, info, warn};
+
+// Import JWT features
+use jsonwebtoken as jwt;
+
+// Import anyhow features
+use anyhow::Result;
+
+/// The time between heartbeats from the primary
+const HEARTBEAT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The time that each message remains valid (limits the replay of messages)
+const MESSAGE_LIFETIME: Duration = Duration::from_secs(5);
+
+/// The time that the lease remains held without being renewed
+const LEASE_DURATION: Duration = Duration::from_secs(2);
+
+/// The issuer of the messages sent by the primary as a heartbeat
+const HEARTBEAT: &str = "Minerva-Failover-Heartbeat";
+
+/// The issuer of the message sent by the primary when it is closed intentionally
+const CLOSING: &str = "Minerva-Failover-Closing";
+
+/// An enum to select the role of this instance in a failover pair
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FailoverMode {
+    /// A variant to run as the primary, sending heartbeats to the standby at
+    /// the provided address (signed with the shared secret)
+    Primary {
+        standby_address: String,
+        secret: String,
+    },
+
+    /// A variant to run as the standby, listening for heartbeats at the
+    /// provided address and taking over after the timeout has passed without
+    /// a heartbeat (once the first heartbeat has been received). Messages
+    /// that are not signed with the shared secret are ignored.
+    Standby {
+        listen_address: String,
+        timeout: Duration,
+        secret: String,
+    },
+}
+
+/// A structure for the claims of each failover message
+///
+#[derive(Debug, Serialize, Deserialize)]
+struct FailoverClaims {
+    iss: String, // the kind of message
+    exp: u64,    // the time the message expires, in seconds since the epoch
+}
+
+/// A structure to hold the background thread for the primary or standby
+///
+/// # Notes
+///
+/// When the primary is dropped, it notifies the standby that it was closed
+/// intentionally. The standby will then remain on standby rather than take
+/// over.
+///
+pub struct Failover {
+    _close_sender: mpsc::Sender<()>, // a line to notify the background thread to close
+                                     // the line is never used, but is poisoned when dropped
+}
+
+// Implement key functionality for the failover structure
+impl Failover {
+    /// A function to spawn the background thread for the primary or standby.
+    /// The lease (if any) is found from the backup server in the provided
+    /// configuration file.
+    ///
+    pub async fn start(
+        mode: FailoverMode,
+        config_file: Option<PathBuf>,
+        internal_send: InternalSend,
+    ) -> Self {
+        // Create a channel to notify the background thread to close
+        let (_close_sender, close_receiver) = mpsc::channel(1); // don't need space for any messages
+
+        // Spawn the thread for the selected role
+        match mode {
+            FailoverMode::Primary {
+                standby_address,
+                secret,
+            } => {
+                tokio::spawn(Failover::run_primary(
+                    standby_address,
+                    jwt::EncodingKey::from_secret(secret.as_bytes()),
+                    config_file,
+                    close_receiver,
+                ));
+            }
+            FailoverMode::Standby {
+                listen_address,
+                timeout,
+                secret,
+            } => {
+                // Try to create the socket
+                match UdpSocket::bind(&listen_address).await {
+                    Ok(socket) => {
+                        info!("Listening for failover heartbeat on {}.", listen_address);
+                        tokio::spawn(Failover::run_standby(
+                            socket,
+                            timeout,
+                            jwt::DecodingKey::from_secret(secret.as_bytes()),
+                            config_file,
+                            internal_send,
+                            close_receiver,
+                        ));
+                    }
+                    Err(error) => error!("Unable to listen for failover heartbeat: {}.", error),
+                }
+            }
+        }
+
+        // Return the new failover structure
+        Self { _close_sender }
+    }
+
+    /// A helper function to send heartbeats to the standby (and to renew the
+    /// lease) until closed
+    ///
+    async fn run_primary(
+        standby_address: String,
+        key: jwt::EncodingKey,
+        config_file: Option<PathBuf>,
+        close_receiver: mpsc::Receiver<()>,
+    ) {
+        // Try to create the socket
+        let socket = match UdpSocket::bind("0.0.0.0:0").await {
+            Ok(socket) => socket,
+            Err(error) => {
+                error!("Unable to create failover heartbeat: {}.", error);
+                return;
+            }
+        };
+        info!("Sending failover heartbeat to {}.", standby_address);
+
+        // Connect to the lease, if available
+        let lease = match Failover::open_lease(config_file).await {
+            Ok(lease) => lease,
+            Err(error) => {
+                error!("Unable to connect to the failover lease: {}.", error);
+                None
+            }
+        };
+
+        // Send heartbeats while holding the lease
+        Failover::hold_lease(lease, Some((socket, standby_address, key)), close_receiver).await;
+    }
+
+    /// A helper function to renew the lease at each interval until closed,
+    /// and to send a heartbeat to the standby at each interval, if provided
+    ///
+    async fn hold_lease(
+        mut lease: Option<BackupLease>,
+        heartbeat: Option<(UdpSocket, String, jwt::EncodingKey)>,
+        mut close_receiver: mpsc::Receiver<()>,
+    ) {
+        // Renew the lease and send a heartbeat at each interval
+        let mut ticks = interval(HEARTBEAT_INTERVAL);
+        let mut is_warned = false;
+        let mut is_held = true;
+        loop {
+            tokio::select! {
+                // Renew the lease and send the next heartbeat
+                _ = ticks.tick() => {
+                    // Warn if another instance holds the lease
+                    let was_held = is_held;
+                    is_held = Failover::renew_lease(&mut lease).await;
+                    if was_held && !is_held {
+                        error!("Unable to renew the failover lease. Another instance may be in control.");
+                    } else if !was_held && is_held {
+                        info!("Renewed the failover lease.");
+                    }
+
+                    // Warn once if the heartbeat cannot be sent
+                    if let Some((ref socket, ref standby_address, ref key)) = heartbeat
+                        && let Err(error) = Failover::send_message(socket, standby_address, HEARTBEAT, key).await
+                        && !is_warned
+                    {
+                        warn!("Unable to send failover heartbeat: {}.", error);
+                        is_warned = true;
+                    }
+                }
+
+                // Notify the standby when closed
+                _ = close_receiver.recv() => {
+                    if let Some((ref socket, ref standby_address, ref key)) = heartbeat {
+                        Failover::send_message(socket, standby_address, CLOSING, key).await.unwrap_or(());
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
+    /// A helper function to listen for heartbeats from the primary on the
+    /// provided socket and take over if the heartbeats stop (and the lease
+    /// can be taken)
+    ///
+    async fn run_standby(
+        socket: UdpSocket,
+        wait: Duration,
+        key: jwt::DecodingKey,
+        config_file: Option<PathBuf>,
+        internal_send: InternalSend,
+        mut close_receiver: mpsc::Receiver<()>,
+    ) {
+        // Wait for heartbeats from the primary
+        let mut buffer = [0; 512];
+        let mut is_following = false;
+        let mut is_armed = false; // armed by a heartbeat, disarmed when the primary closes intentionally
+        loop {
+            tokio::select! {
+                // Wait for the next message from the primary
+                result = timeout(wait, socket.recv(&mut buffer)) => {
+                    match result {
+                        // Check that the message is signed by the primary
+                        Ok(Ok(length)) => match Failover::read_message(&buffer[..length], &key).as_deref() {
+                            // A heartbeat was received
+                            Some(HEARTBEAT) => {
+                                // Notify the first time the primary is found
+                                if !is_following {
+                                    info!("Following the primary.");
+                                    is_following = true;
+                                }
+                                is_armed = true;
+                            }
+
+                            // The primary was closed intentionally
+                            Some(CLOSING) => {
+                                warn!("The primary was closed. Remaining on standby.");
+                                is_following = false;
+                                is_armed = false;
+                            }
+
+                            // Ignore any other messages
+                            _ => (),
+                        },
+
+                        // Warn of an error with the socket
+                        Ok(Err(error)) => {
+                            error!("Unable to read failover heartbeat: {}.", error);
+                        }
+
+                        // The heartbeat has stopped
+                        Err(_) => {
+                            // Ignore if not armed
+                            if !is_armed {
+                                continue;
+                            }
+
+                            // Try to take the lease, if available
+                            let mut lease = match Failover::open_lease(config_file.clone()).await {
+                                Ok(lease) => lease,
+                                Err(error) => {
+                                    error!("Unable to connect to the failover lease: {}. Remaining on standby.", error);
+                                    continue;
+                                }
+                            };
+                            if !Failover::renew_lease(&mut lease).await {
+                                warn!("The primary still holds the failover lease. Remaining on standby.");
+                                continue;
+                            }
+
+                            // Take over and keep the lease until closed
+                            internal_send.send_failover_takeover().await;
+                            Failover::hold_lease(lease, None, close_receiver).await;
+                            return;
+                        }
+                    }
+                }
+
+                // Close the thread if the standby is closed
+                _ = close_receiver.recv() => return,
+            }
+        }
+    }
+
+    /// A helper function to connect to the lease in the backup server of the
+    /// configuration file. Returns None if the configuration does not use a
+    /// Redis server (the failover pair is not protected by a lease).
+    ///
+    /// # Errors
+    ///
+    /// This function will raise an error if the configuration uses a Redis
+    /// server but the function is unable to connect to it.
+    ///
+    async fn open_lease(config_file: Option<PathBuf>) -> Result<Option<BackupLease>> {
+        /// The parts of the configuration that locate the lease
+        #[derive(Deserialize)]
+        struct LeaseConfig {
+            identifier: Option<Identifier>,
+            server_location: Option<String>,
+        }
+
+        // Connect on a blocking thread, as the connection may be slow
+        tokio::task::spawn_blocking(move || {
+            // Read the location of the backup server, if any
+            let Some(path) = config_file else {
+                return Ok(None);
+            };
+            let config: LeaseConfig = serde_yaml::from_str(&std::fs::read_to_string(path)?)?;
+            let identifier = config.identifier.unwrap_or(Identifier { id: None });
+            match config.server_location {
+                Some(location) if !location.starts_with("file://") => {
+                    Ok(Some(BackupLease::new(identifier, &location)?))
+                }
+                _ => {
+                    warn!(
+                        "Failover is not protected by a lease: the backup is not on a Redis server."
+                    );
+                    Ok(None)
+                }
+            }
+        })
+        .await?
+    }
+
+    /// A helper function to take or renew the lease, if there is one.
+    /// Returns true if the lease is held (or if there is no lease).
+    ///
+    async fn renew_lease(lease: &mut Option<BackupLease>) -> bool {
+        // Ignore a missing lease
+        let Some(mut held) = lease.take() else {
+            return true;
+        };
+
+        // Renew on a blocking thread, as the server may be slow
+        match tokio::task::spawn_blocking(move || {
+            let is_held = held.acquire(LEASE_DURATION);
+            (held, is_held)
+        })
+        .await
+        {
+            Ok((held, is_held)) => {
+                *lease = Some(held);
+                is_held
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// A helper function to sign and send a message to the standby
+    ///
+    async fn send_message(
+        socket: &UdpSocket,
+        address: &str,
+        kind: &str,
+        key: &jwt::EncodingKey,
+    ) -> Result<()> {
+        let claims = FailoverClaims {
+            iss: kind.into(),
+            exp: jwt::get_current_timestamp() + MESSAGE_LIFETIME.as_secs(),
+        };
+        let message = jwt::encode(&jwt::Header::default(), &claims, key)?;
+        socket.send_to(message.as_bytes(), address).await?;
+        Ok(())
+    }
+
+    /// A helper function to check the signature of a message from the
+    /// primary. Returns the kind of message, or None if the message is not
+    /// valid.
+    ///
+    fn read_message(message: &[u8], key: &jwt::DecodingKey) -> Option<String> {
+        let mut validation = jwt::Validation::default();
+        validation.set_issuer(&[HEARTBEAT, CLOSING]);
+        validation.leeway = 1;
+        let message = std::str::from_utf8(message).ok()?;
+        jwt::decode::<FailoverClaims>(message, key, &validation)
+            .ok()
+            .map(|data| data.claims.iss)
+    }
+}
+
+// Tests of the failover module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The shared secret for the tests
+    const SECRET: &[u8] = b"failover secret";
+
+    // A helper function to start a standby on an unused port, returning the
+    // address of the standby and the line to close it
+    async fn start_standby(
+        wait: Duration,
+        internal_send: InternalSend,
+    ) -> (String, mpsc::Sender<()>) {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let address = socket.local_addr().unwrap().to_string();
+        let (close_sender, close_receiver) = mpsc::channel(1);
+        tokio::spawn(Failover::run_standby(
+            socket,
+            wait,
+            jwt::DecodingKey::from_secret(SECRET),
+            None,
+            internal_send,
+            close_receiver,
+        ));
+        (address, close_sender)
+    }
+
+    // Test that the standby waits while the primary runs and takes over without it
+    #[tokio::test]
+    async fn take_over() {
+        // Start a standby and primary pair
+        let (internal_send, mut internal_receive) = InternalSend::new();
+        let (standby_address, _standby) =
+            start_standby(Duration::from_millis(1000), internal_send.clone()).await;
+        let primary = Failover::start(
+            FailoverMode::Primary {
+                standby_address,
+                secret: String::from_utf8(SECRET.to_vec()).unwrap(),
+            },
+            None,
+            internal_send.clone(),
+        )
+        .await;
+
+        // Make sure the standby does not take over while the primary runs
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(internal_receive.try_recv().is_err());
+
+        // Close the primary and make sure the standby remains on standby
+        drop(primary);
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(internal_receive.try_recv().is_err());
+
+        // Start a standby without a primary and make sure it waits for the first heartbeat
+        let (lonely_address, _lonely) =
+            start_standby(Duration::from_millis(100), internal_send).await;
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(internal_receive.try_recv().is_err());
+
+        // Send a single heartbeat and check for the takeover once it stops
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        Failover::send_message(
+            &socket,
+            &lonely_address,
+            HEARTBEAT,
+            &jwt::EncodingKey::from_secret(SECRET),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            Some(InternalUpdate::FailoverTakeover),
+            internal_receive.recv().await
+        );
+    }
+
+    // Test that the standby ignores messages that are not signed by the primary
+    #[tokio::test]
+    async fn ignore_unsigned() {
+        // Start a standby and sign messages with the wrong secret
+        let (internal_send, mut internal_receive) = InternalSend::new();
+        let (standby_address, _standby) =
+            start_standby(Duration::from_millis(100), internal_send).await;
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let wrong_key = jwt::EncodingKey::from_secret(b"wrong secret");
+
+        // Make sure an unsigned or wrongly signed heartbeat doesn't arm the standby
+        socket
+            .send_to(b"minerva:heartbeat", &standby_address)
+            .await
+            .unwrap();
+        Failover::send_message(&socket, &standby_address, HEARTBEAT, &wrong_key)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(internal_receive.try_recv().is_err());
+
+        // Make sure a wrongly signed closing message doesn't disarm the standby
+        let key = jwt::EncodingKey::from_secret(SECRET);
+        Failover::send_message(&socket, &standby_address, HEARTBEAT, &key)
+            .await
+            .unwrap();
+        Failover::send_message(&socket, &standby_address, CLOSING, &wrong_key)
+            .await
+            .unwrap();
+        assert_eq!(
+            Some(InternalUpdate::FailoverTakeover),
+            internal_receive.recv().await
+        );
+    }
+}
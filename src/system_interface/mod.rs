@@ -20,15 +20,30 @@
 //! to the application window.
 
 // Define private submodules
+mod autosave;
+mod config_watcher;
+mod edit_history;
 #[macro_use]
 mod event_handler;
+mod failover;
 mod system_connection;
 
+// Reexport the autosave settings, failover mode, and the validation, diff, and merge functions
+pub use self::autosave::AutosaveSettings;
+pub use self::event_handler::{
+    diff_files, export_cues_file, import_cues_file, merge_files, validate_config,
+};
+pub use self::failover::FailoverMode;
+
 // Import crate definitions
 use crate::definitions::*;
 
 // Import other definitions
+use self::autosave::Autosave;
+use self::config_watcher::ConfigWatcher;
+use self::edit_history::{EditHistory, inverse_of};
 use self::event_handler::EventHandler;
+use self::failover::Failover;
 use self::system_connection::SystemConnection;
 
 // Import standard library features
@@ -63,18 +78,42 @@ pub struct SystemInterface {
     web_receive: mpsc::Receiver<WebRequest>, // the receiving line for web requests
     internal_receive: mpsc::Receiver<InternalUpdate>, // a receiving line to receive internal updates
     internal_send: InternalSend,                      // a sending line to pass internal updates
+    failover: Option<Failover>, // the primary or standby for failover, if specified
+    standby_config: Option<String>, // the configuration to load when taking over from the primary, if on standby
+    watch_config: bool,             // a flag to reload the configuration file when it changes
+    config_watcher: Option<ConfigWatcher>, // the watcher for the loaded configuration file, if watching
+    edit_history: EditHistory, // the changes to the configuration that can be undone and redone
+    autosave: Option<Autosave>, // the autosave for changes to the configuration, if enabled
+    run_address: String,       // the address of the run interface (for media completion callbacks)
 }
 
 // Implement key SystemInterface functionality
 impl SystemInterface {
     /// A function to create a new, blank instance of the system interface.
     ///
+    /// If a failover mode is provided, this instance runs as the primary or
+    /// the standby of a failover pair. On standby, the configuration is not
+    /// loaded until the primary is lost.
+    ///
+    /// If the watch config flag is set, the loaded configuration file is
+    /// watched and reloaded when it changes.
+    ///
+    /// If autosave settings are provided, changes to the configuration are
+    /// periodically saved to the autosave directory.
+    ///
+    /// The run address is passed to the media players so that they can report
+    /// when media has finished playing.
+    ///
     pub async fn new(
         index_access: IndexAccess,
         style_access: StyleAccess,
         interface_send: InterfaceSend,
         limited_send: LimitedSend,
         config_file: String,
+        failover_mode: Option<FailoverMode>,
+        watch_config: bool,
+        autosave_settings: Option<AutosaveSettings>,
+        run_address: String,
     ) -> (Self, WebSend) {
         // Create the new general update structure and receive channel
         let (internal_send, internal_receive) = InternalSend::new();
@@ -95,9 +134,46 @@ impl SystemInterface {
             limited_send,
             web_receive,
             internal_receive,
-            internal_send,
+            internal_send: internal_send.clone(),
+            failover: None,
+            standby_config: None,
+            watch_config,
+            config_watcher: None,
+            edit_history: EditHistory::new(),
+            autosave: autosave_settings
+                .map(|settings| Autosave::new(settings, internal_send.clone())),
+            run_address,
         };
 
+        // Start the failover primary or standby, if specified
+        if let Some(mode) = failover_mode {
+            // Start the failover thread with the location of the configuration file
+            let is_standby = matches!(mode, FailoverMode::Standby { .. });
+            let config_path = env::current_dir()
+                .map(|directory| directory.join(&config_file))
+                .ok()
+                .filter(|path| path.exists());
+            sys_interface.failover = Some(Failover::start(mode, config_path, internal_send).await);
+
+            // If on standby, wait to load the configuration
+            if is_standby {
+                warn!("Running on standby. Waiting for the primary to fail.");
+                sys_interface.standby_config = Some(config_file);
+                return (sys_interface, web_send);
+            }
+        }
+
+        // Load the default configuration
+        sys_interface.load_default_config(config_file).await;
+
+        // Return the new SystemInterface and general send line
+        (sys_interface, web_send)
+    }
+
+    /// A helper method to load the provided configuration file from the
+    /// current directory, if it exists, or to create an empty configuration.
+    ///
+    async fn load_default_config(&mut self, config_file: String) {
         // Try to load a default configuration, if it exists
         if let Ok(mut path) = env::current_dir() {
             // Add the default filename FIXME only allows for relative filepaths
@@ -105,16 +181,23 @@ impl SystemInterface {
 
             // Try the file, if it exists
             if path.exists() {
-                sys_interface.load_config(Some(path), false).await;
+                self.load_config(Some(path), false).await;
 
             // Otherwise, create an empty config
             } else {
-                sys_interface.load_config(None, false).await;
+                self.load_config(None, false).await;
             }
         }
 
-        // Regardless, return the new SystemInterface and general send line
-        (sys_interface, web_send)
+        // Offer to recover a newer autosave, if one exists
+        if let (Some(autosave), Some(handler)) = (&self.autosave, &self.event_handler)
+            && let Some(path) = autosave.find_recovery(&handler.get_config_path())
+        {
+            warn!(
+                "Found an autosave newer than the configuration: {}. Recover it from the edit interface.",
+                path.display()
+            );
+        }
     }
 
     /// A method to run one iteration of the system interface to update the user
@@ -141,6 +224,16 @@ impl SystemInterface {
                         request.reply_to.send(WebReply::success()).unwrap_or(());
                     }
 
+                    // The unpacking yielded backup metrics
+                    UnpackResult::SuccessWithBackupMetrics(metrics) => {
+                        request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::BackupMetrics(metrics) }).unwrap_or(());
+                    }
+
+                    // The unpacking yielded the differences between two configurations
+                    UnpackResult::SuccessWithConfigDiff(diff) => {
+                        request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::ConfigDiff(diff) }).unwrap_or(());
+                    }
+
                     // The unpacking yielded a current scene and status
                     UnpackResult::SuccessWithCurrentSceneAndStatus((scene_id, status)) => {
                         request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::CurrentSceneAndStatus((scene_id, status)) } ).unwrap_or(());
@@ -166,11 +259,41 @@ impl SystemInterface {
                         request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::Message(message) }).unwrap_or(());
                     }
 
+                    // The unpacking yielded a media report
+                    UnpackResult::SuccessWithMediaReport(report) => {
+                        request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::MediaReport(report) }).unwrap_or(());
+                    }
+
+                    // The unpacking yielded snapshots
+                    UnpackResult::SuccessWithSnapshots(snapshots) => {
+                        request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::Snapshots(snapshots) }).unwrap_or(());
+                    }
+
+                    // The unpacking yielded records from the game log
+                    UnpackResult::SuccessWithGameLog(records) => {
+                        request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::GameLog(records) }).unwrap_or(());
+                    }
+
+                    // The unpacking yielded a summary of the game sessions
+                    UnpackResult::SuccessWithSessions(sessions) => {
+                        request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::Sessions(sessions) }).unwrap_or(());
+                    }
+
+                    // The unpacking yielded the room statistics
+                    UnpackResult::SuccessWithStatistics(statistics) => {
+                        request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::Statistics(statistics) }).unwrap_or(());
+                    }
+
                     // The unpacking yielded parameters
                     UnpackResult::SuccessWithParameters(parameters) => {
                         request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::Parameters(parameters) }).unwrap_or(());
                     }
 
+                    // The unpacking yielded settings
+                    UnpackResult::SuccessWithSettings(settings) => {
+                        request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::Settings(settings) }).unwrap_or(());
+                    }
+
                     // The unpacking yielded a path
                     UnpackResult::SuccessWithPath(path) => {
                         request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::Path {
@@ -184,6 +307,11 @@ impl SystemInterface {
                         request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::Status(Some(status)) }).unwrap_or(());
                     }
 
+                    // The unpacking yielded the shared statuses and scenes
+                    UnpackResult::SuccessWithSyncState(entries) => {
+                        request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::SyncState(entries) }).unwrap_or(());
+                    }
+
                     // The unpacking yielded a scene
                     UnpackResult::SuccessWithScene(scene) => {
                         request.reply_to.send(WebReply { is_valid: true, data: WebReplyData::Scene(Some(scene)) }).unwrap_or(());
@@ -194,6 +322,11 @@ impl SystemInterface {
                         request.reply_to.send(WebReply::failure(&reason)).unwrap_or(());
                     }
 
+                    // The unpacking was a failure with the problems with the modifications
+                    UnpackResult::FailureWithModificationErrors(errors) => {
+                        request.reply_to.send(WebReply { is_valid: false, data: WebReplyData::ModificationErrors(errors) }).unwrap_or(());
+                    }
+
                     // The unpacking indicated the program should close
                     UnpackResult::Close => {
                         request.reply_to.send(WebReply::success()).unwrap_or(());
@@ -319,6 +452,52 @@ impl SystemInterface {
                 // Echo events to the system connections
                 self.system_connection.echo(event_id, data1, data2).await;
             }
+
+            // Pass a media completion to the event handler
+            InternalUpdate::MediaComplete { channel, uri } => {
+                // If the event handler exists
+                if let Some(ref mut handler) = self.event_handler {
+                    // Cue the completion event, if any
+                    handler.media_complete(channel, uri).await;
+                }
+            }
+
+            // Merge the shared statuses and scenes from another instance
+            InternalUpdate::SyncUpdate(message) => {
+                // If the event handler exists
+                if let Some(ref mut handler) = self.event_handler {
+                    handler.sync_update(message).await;
+                }
+            }
+
+            // Take over from the failed primary
+            InternalUpdate::FailoverTakeover => {
+                // If still on standby
+                if let Some(config_file) = self.standby_config.take() {
+                    // Announce the takeover
+                    error!("Lost the primary. Taking over ...");
+                    self.interface_send
+                        .send(InterfaceUpdate::Notify {
+                            message: "Lost the primary. This instance has taken over.".into(),
+                        })
+                        .await;
+
+                    // Load the configuration (reloads the backup from the primary), unless one was loaded manually
+                    if self.event_handler.is_none() {
+                        self.load_default_config(config_file).await;
+                    }
+                }
+            }
+
+            // Reload the configuration file when it changes
+            InternalUpdate::ConfigChanged(path) => {
+                self.reload_config(path).await;
+            }
+
+            // Save any changes to the configuration to a new autosave
+            InternalUpdate::Autosave => {
+                self.autosave_config().await;
+            }
         }
     }
 
@@ -422,6 +601,18 @@ impl SystemInterface {
                 }
             }
 
+            // Return the other settings of the current configuration, if available
+            UserRequest::ConfigSettings => {
+                // Collect the configuration settings
+                if let Some(ref handler) = self.event_handler {
+                    return UnpackResult::SuccessWithSettings(handler.get_settings().await);
+
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
+
             // Return the name of the current configuration file, if available
             UserRequest::ConfigPath => {
                 // Try to get the config name
@@ -504,6 +695,14 @@ impl SystemInterface {
                             result = UnpackResult::SuccessWithItems(handler.get_scenes());
                         }
 
+                        // Reply to a request for the backup metrics
+                        DetailType::BackupMetrics => {
+                            // Get the metrics, if the backup is connected
+                            result = UnpackResult::SuccessWithBackupMetrics(
+                                handler.get_backup_metrics(),
+                            );
+                        }
+
                         // Reply to a request for the event
                         DetailType::Event { item_id } => {
                             // Try to get the event
@@ -524,6 +723,13 @@ impl SystemInterface {
                             }
                         }
 
+                        // Reply to a request for the media report
+                        DetailType::MediaReport => {
+                            // Check the media and return the report
+                            result =
+                                UnpackResult::SuccessWithMediaReport(handler.get_media_report());
+                        }
+
                         // Reply to a request for the status
                         DetailType::Status { item_id } => {
                             // Try to get the scene
@@ -544,6 +750,11 @@ impl SystemInterface {
                             }
                         }
 
+                        // Reply to a request for the shared statuses and scenes
+                        DetailType::SyncState => {
+                            result = UnpackResult::SuccessWithSyncState(handler.get_sync_state());
+                        }
+
                         // Reply to a request for item type
                         DetailType::Type { item_id } => {
                             // Check to see if there is a scene
@@ -576,115 +787,68 @@ impl SystemInterface {
             }
 
             // Modify the underlying configuration
-            UserRequest::Edit { mut modifications } => {
-                // Check to see if there is an active configuration
-                if let Some(ref mut handler) = self.event_handler {
-                    // Process each modification in order
-                    for modification in modifications.drain(..) {
-                        // Match the specified moficiation
-                        match modification {
-                            // Add or modify the item
-                            Modification::ModifyItem { item_pair } => {
-                                // Pass the update and see if it's a new item
-                                if self
-                                    .index_access
-                                    .update_description(
-                                        item_pair.get_id(),
-                                        item_pair.get_description(),
-                                    )
-                                    .await
-                                {
-                                    info!("Item description added: {}.", item_pair.description());
-
-                                // If not, notify that the item was updated
-                                } else {
-                                    info!("Item description updated: {}.", item_pair.description());
-                                }
-                            }
+            UserRequest::Edit { modifications } => return self.edit_config(modifications).await,
 
-                            // Add or modify the event
-                            Modification::ModifyEvent { item_id, event } => {
-                                handler.edit_event(item_id, event.map(|e| e.into())).await;
-                            }
-
-                            // Add or modify the group
-                            Modification::ModifyGroup { item_id, group } => {
-                                // Recompose the web group into a group
-                                let new_group = group.map(|group| group.into());
-                                handler.edit_group(item_id, new_group).await;
-                            }
-
-                            // Update the configuration parameters
-                            Modification::ModifyParameters { parameters } => {
-                                // Save the configuration parameters
-                                handler.save_parameters(parameters).await;
-                            }
+            // Reply with the differences between the current configuration and the file
+            UserRequest::DiffConfig { filepath } => {
+                // If the event handler exists
+                if let Some(ref handler) = self.event_handler {
+                    match handler.diff_config(&filepath).await {
+                        Ok(diff) => return UnpackResult::SuccessWithConfigDiff(diff),
+                        Err(error) => {
+                            error!("Unable to compare configurations: {}.", error);
+                            return UnpackResult::Failure(error.to_string());
+                        }
+                    }
 
-                            // Add or modify the status
-                            Modification::ModifyStatus { item_id, status } => {
-                                handler.edit_status(item_id, status).await;
-                            }
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
 
-                            // Add or modify the scene
-                            Modification::ModifyScene { item_id, scene } => {
-                                // Recompose the web scene into a scene
-                                let new_scene = match scene {
-                                    Some(scene) => {
-                                        // Extract the groups from the item list
-                                        let mut items = FnvHashSet::default();
-                                        let mut groups = FnvHashSet::default();
-                                        for item_id in scene.items.iter() {
-                                            // If it's a group, add it to the group list
-                                            if handler.get_group(item_id).is_some() {
-                                                groups.insert(item_id.clone());
-
-                                            // Otherwise, save it to the item list
-                                            } else {
-                                                items.insert(item_id.clone());
-                                            }
-                                        }
-
-                                        // Return the new scene
-                                        Some(Scene {
-                                            items,
-                                            groups,
-                                            key_map: scene.key_map,
-                                        })
-                                    }
-
-                                    // Do nothing if empty
-                                    None => None,
-                                };
-
-                                // Update the scene
-                                handler.edit_scene(item_id, new_scene).await;
-                            }
+            // Delete a snapshot of the game state
+            UserRequest::DeleteSnapshot { name } => {
+                // If the event handler exists
+                if let Some(ref mut handler) = self.event_handler {
+                    // Try to delete the snapshot
+                    if let Err(error) = handler.delete_snapshot(&name) {
+                        error!("Unable to delete snapshot: {}", error);
+                        return UnpackResult::Failure(error.to_string());
+                    }
 
-                            // Remove an item and its event, status, or scene
-                            Modification::RemoveItem { item_id } => {
-                                // Remove any event, status, group, or scene
-                                handler.edit_event(item_id, None).await;
-                                handler.edit_status(item_id, None).await;
-                                handler.edit_group(item_id, None).await;
-                                handler.edit_scene(item_id, None).await;
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
 
-                                // Remove the item from any scene, group, or status it's a part of
-                                handler.remove_item(item_id).await;
+            // End the current game session
+            UserRequest::EndSession { outcome } => {
+                // If the event handler exists
+                if let Some(ref mut handler) = self.event_handler {
+                    handler.end_session(outcome).await;
 
-                                // Get the description
-                                let description = self.index_access.get_description(&item_id).await;
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
 
-                                // Remove the entry in the item index
-                                if self.index_access.remove_item(item_id).await {
-                                    info!("Item deleted: {}.", description);
-                                } // ignore errors
-                            }
+            // Export the items and events to a cue list
+            UserRequest::ExportCues { filepath } => {
+                // If the event handler exists
+                if let Some(ref handler) = self.event_handler {
+                    match handler.export_cues(&filepath).await {
+                        Ok(count) => info!("Exported {} cue(s) to {}.", count, filepath.display()),
+                        Err(error) => {
+                            error!("Unable to export cues: {}.", error);
+                            return UnpackResult::Failure(error.to_string());
                         }
                     }
 
-                // Raise a warning that there is no active configuration
+                // Otherwise, return a failure
                 } else {
-                    warn!("Change not saved: There is no active configuration.");
                     return UnpackResult::Failure("No active configuration.".into());
                 }
             }
@@ -706,6 +870,165 @@ impl SystemInterface {
                 }
             }
 
+            // Reply with the matching records of the game log
+            UserRequest::GameLog { query } => {
+                // If the event handler exists
+                if let Some(ref handler) = self.event_handler {
+                    return UnpackResult::SuccessWithGameLog(handler.query_game_log(query).await);
+
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
+
+            // Reply with a summary of the game sessions
+            UserRequest::ListSessions => {
+                // If the event handler exists
+                if let Some(ref handler) = self.event_handler {
+                    return UnpackResult::SuccessWithSessions(handler.list_sessions().await);
+
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
+
+            // Reply with a list of the snapshots of the game state
+            UserRequest::ListSnapshots => {
+                // If the event handler exists
+                if let Some(ref handler) = self.event_handler {
+                    return UnpackResult::SuccessWithSnapshots(handler.list_snapshots());
+
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
+
+            // Process a notification that media has completed
+            UserRequest::MediaComplete { channel, uri } => {
+                // If the event handler exists
+                if let Some(ref mut handler) = self.event_handler {
+                    // Cue the completion event, if any
+                    handler.media_complete(channel, uri).await;
+
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
+
+            // Import the cue list into the current configuration
+            UserRequest::ImportCues { filepath } => {
+                // Find the modifications to import the cues
+                let (count, modifications) = match self.event_handler {
+                    Some(ref handler) => match handler.import_cues(&filepath).await {
+                        Ok(result) => result,
+                        Err(error) => {
+                            error!("Unable to import cues: {}.", error);
+                            return UnpackResult::Failure(error.to_string());
+                        }
+                    },
+                    None => return UnpackResult::Failure("No active configuration.".into()),
+                };
+
+                // Apply the modifications as a single change
+                let result = self.edit_config(modifications).await;
+                if let UnpackResult::Success = result {
+                    info!("Imported {} cue(s) from {}.", count, filepath.display());
+                    self.interface_send.send(InterfaceUpdate::RefreshAll).await;
+                }
+                return result;
+            }
+
+            // Merge the selected differences from the file into the current configuration
+            UserRequest::MergeConfig { filepath, changes } => {
+                // Find the modifications to merge the changes
+                let modifications = match self.event_handler {
+                    Some(ref handler) => match handler.merge_config(&filepath, &changes) {
+                        Ok(modifications) => modifications,
+                        Err(error) => {
+                            error!("Unable to merge configurations: {}.", error);
+                            return UnpackResult::Failure(error.to_string());
+                        }
+                    },
+                    None => return UnpackResult::Failure("No active configuration.".into()),
+                };
+
+                // Apply the modifications as a single change
+                let result = self.edit_config(modifications).await;
+                if let UnpackResult::Success = result {
+                    info!(
+                        "Merged {} change(s) from {}.",
+                        changes.len(),
+                        filepath.display()
+                    );
+                    self.interface_send.send(InterfaceUpdate::RefreshAll).await;
+                }
+                return result;
+            }
+
+            // Replace the current configuration with the most recent autosave
+            UserRequest::RecoverAutosave => {
+                // Find the most recent autosave of the current configuration
+                let (Some(autosave), Some(handler)) = (&self.autosave, &self.event_handler) else {
+                    return UnpackResult::Failure("No autosave available.".into());
+                };
+                let config_path = handler.get_config_path();
+                let Some(path) = autosave.find_recovery(&config_path) else {
+                    return UnpackResult::Failure("No autosave available.".into());
+                };
+
+                // Load the autosave, but save any further changes to the current configuration
+                // file and keep the items of any included files in those files
+                info!("Recovering autosave: {}.", path.display());
+                let includes = handler.get_includes();
+                self.load_config(Some(path), true).await;
+                if let Some(ref mut handler) = self.event_handler {
+                    handler.set_config_path(config_path);
+                    handler.set_includes(includes);
+                }
+                self.watch_config_file().await;
+            }
+
+            // Repeat the most recently undone change to the configuration
+            UserRequest::Redo => {
+                // Check that there is a change to redo
+                let Some(repeat) = self.edit_history.take_redo() else {
+                    return UnpackResult::Failure("Nothing to redo.".into());
+                };
+
+                // Apply the change again and record how to reverse it
+                match self.apply_modifications(repeat).await {
+                    Some(inverse) => {
+                        info!("Change redone.");
+                        self.edit_history.record_redone(inverse);
+                        self.interface_send.send(InterfaceUpdate::RefreshAll).await;
+                    }
+                    None => return UnpackResult::Failure("No active configuration.".into()),
+                }
+            }
+
+            // Restore the game state from a snapshot
+            UserRequest::RestoreSnapshot { name } => {
+                // If the event handler exists
+                if let Some(ref mut handler) = self.event_handler {
+                    // Try to restore the snapshot
+                    if let Err(error) = handler.restore_snapshot(&name).await {
+                        error!("Unable to restore snapshot: {}", error);
+                        return UnpackResult::Failure(error.to_string());
+                    }
+
+                    // Trigger a redraw of the system
+                    self.interface_send.send(InterfaceUpdate::RefreshAll).await;
+
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
+
             // Save the current configuration to the provided file
             UserRequest::SaveConfig { filepath } => {
                 // If the event handler exists
@@ -713,6 +1036,70 @@ impl SystemInterface {
                     // Save the current configuration
                     handler.save_config(filepath).await;
 
+                    // Forget the changes before the save
+                    self.edit_history.clear();
+                    if let Some(ref mut autosave) = self.autosave {
+                        autosave.mark_saved();
+                    }
+
+                    // Restart the watcher so the saved file isn't reloaded
+                    self.watch_config_file().await;
+
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
+
+            // Save the current game state as a snapshot
+            UserRequest::SaveSnapshot { name } => {
+                // If the event handler exists
+                if let Some(ref mut handler) = self.event_handler {
+                    // Try to save the snapshot
+                    if let Err(error) = handler.save_snapshot(name) {
+                        error!("Unable to save snapshot: {}", error);
+                        return UnpackResult::Failure(error.to_string());
+                    }
+
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
+
+            // Set the details of the current or next game session
+            UserRequest::SessionDetails {
+                team_name,
+                player_count,
+            } => {
+                // If the event handler exists
+                if let Some(ref mut handler) = self.event_handler {
+                    handler.set_session_details(team_name, player_count).await;
+
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
+
+            // Deliver the next hint for the current scene
+            UserRequest::DeliverHint => {
+                // If the event handler exists
+                if let Some(ref mut handler) = self.event_handler {
+                    handler.deliver_hint().await;
+
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
+
+            // Start a new game session
+            UserRequest::StartSession => {
+                // If the event handler exists
+                if let Some(ref mut handler) = self.event_handler {
+                    handler.start_session().await;
+
                 // Otherwise, return a failure
                 } else {
                     return UnpackResult::Failure("No active configuration.".into());
@@ -735,6 +1122,20 @@ impl SystemInterface {
             // Close the system interface thread and attempt to shut down the computer
             UserRequest::Shutdown => return UnpackResult::Shutdown,
 
+            // Reply with the room statistics for the date range
+            UserRequest::Statistics { query } => {
+                // If the event handler exists
+                if let Some(ref handler) = self.event_handler {
+                    return UnpackResult::SuccessWithStatistics(
+                        handler.get_statistics(query).await,
+                    );
+
+                // Otherwise, return a failure
+                } else {
+                    return UnpackResult::Failure("No active configuration.".into());
+                }
+            }
+
             // Change the state of a particular status
             UserRequest::StatusChange { status, state } => {
                 // If the event handler exists
@@ -747,10 +1148,200 @@ impl SystemInterface {
                     return UnpackResult::Failure("No active configuration.".into());
                 }
             }
+
+            // Reverse the most recent change to the configuration
+            UserRequest::Undo => {
+                // Check that there is a change to undo
+                let Some(inverse) = self.edit_history.take_undo() else {
+                    return UnpackResult::Failure("Nothing to undo.".into());
+                };
+
+                // Reverse the change and record how to repeat it
+                match self.apply_modifications(inverse).await {
+                    Some(repeat) => {
+                        info!("Change undone.");
+                        self.edit_history.record_undone(repeat);
+                        self.interface_send.send(InterfaceUpdate::RefreshAll).await;
+                    }
+                    None => return UnpackResult::Failure("No active configuration.".into()),
+                }
+            }
         }
         UnpackResult::Success // indicate to continue and no errors
     }
 
+    /// An internal method to check a batch of modifications as a whole and,
+    /// if it is valid, to apply it and record how to reverse it.
+    ///
+    async fn edit_config(&mut self, modifications: Vec<Modification>) -> UnpackResult {
+        // Check the whole batch before applying any of it
+        if let Some(ref handler) = self.event_handler {
+            let errors = handler.check_modifications(&modifications).await;
+            if !errors.is_empty() {
+                warn!(
+                    "Change not saved: {} problem(s) with the modifications.",
+                    errors.len()
+                );
+                return UnpackResult::FailureWithModificationErrors(errors);
+            }
+        }
+
+        // Apply the modifications and record how to reverse them
+        match self.apply_modifications(modifications).await {
+            Some(inverse) => self.edit_history.record(inverse),
+
+            // Raise a warning that there is no active configuration
+            None => {
+                warn!("Change not saved: There is no active configuration.");
+                return UnpackResult::Failure("No active configuration.".into());
+            }
+        }
+        UnpackResult::Success
+    }
+
+    /// An internal method to apply the provided modifications to the current
+    /// configuration, in order. The method returns the modifications that
+    /// reverse the change, or None if there is no active configuration.
+    ///
+    async fn apply_modifications(
+        &mut self,
+        mut modifications: Vec<Modification>,
+    ) -> Option<Vec<Modification>> {
+        // Check to see if there is an active configuration
+        let handler = self.event_handler.as_mut()?;
+
+        // Process each modification in order
+        let mut inverse = Vec::new();
+        for modification in modifications.drain(..) {
+            // Record how to reverse the modification (most recent first)
+            let mut reverse = inverse_of(handler, &self.index_access, &modification).await;
+            reverse.append(&mut inverse);
+            inverse = reverse;
+
+            // Match the specified moficiation
+            match modification {
+                // Add or modify the item
+                Modification::ModifyItem { item_pair } => {
+                    // Pass the update and see if it's a new item
+                    if self
+                        .index_access
+                        .update_description(item_pair.get_id(), item_pair.get_description())
+                        .await
+                    {
+                        info!("Item description added: {}.", item_pair.description());
+
+                    // If not, notify that the item was updated
+                    } else {
+                        info!("Item description updated: {}.", item_pair.description());
+                    }
+                }
+
+                // Add or modify the event
+                Modification::ModifyEvent { item_id, event } => {
+                    handler.edit_event(item_id, event.map(|e| e.into())).await;
+                }
+
+                // Add or modify the group
+                Modification::ModifyGroup { item_id, group } => {
+                    // Recompose the web group into a group
+                    let new_group = group.map(|group| group.into());
+                    handler.edit_group(item_id, new_group).await;
+                }
+
+                // Update the configuration parameters
+                Modification::ModifyParameters { parameters } => {
+                    // Save the configuration parameters
+                    handler.save_parameters(parameters).await;
+                }
+
+                // Update the other settings
+                Modification::ModifySettings { settings } => {
+                    handler
+                        .save_settings(settings, self.internal_send.clone())
+                        .await;
+                }
+
+                // Add or modify the status
+                Modification::ModifyStatus { item_id, status } => {
+                    handler.edit_status(item_id, status).await;
+                }
+
+                // Add or modify the scene
+                Modification::ModifyScene { item_id, scene } => {
+                    // Recompose the web scene into a scene
+                    let new_scene = match scene {
+                        Some(scene) => {
+                            // Extract the groups from the item list
+                            let mut items = FnvHashSet::default();
+                            let mut groups = FnvHashSet::default();
+                            for item_id in scene.items.iter() {
+                                // If it's a group, add it to the group list
+                                if handler.get_group(item_id).is_some() {
+                                    groups.insert(*item_id);
+
+                                // Otherwise, save it to the item list
+                                } else {
+                                    items.insert(*item_id);
+                                }
+                            }
+
+                            // Keep the existing hints, if none were provided
+                            let hints = match scene.hints {
+                                Some(hints) => hints,
+                                None => handler
+                                    .get_scene(&item_id)
+                                    .map(|scene| scene.hints)
+                                    .unwrap_or_default(),
+                            };
+
+                            // Return the new scene
+                            Some(Scene {
+                                items,
+                                groups,
+                                key_map: scene.key_map,
+                                hints,
+                            })
+                        }
+
+                        // Do nothing if empty
+                        None => None,
+                    };
+
+                    // Update the scene
+                    handler.edit_scene(item_id, new_scene).await;
+                }
+
+                // Remove an item and its event, status, or scene
+                Modification::RemoveItem { item_id } => {
+                    // Remove any event, status, group, or scene
+                    handler.edit_event(item_id, None).await;
+                    handler.edit_status(item_id, None).await;
+                    handler.edit_group(item_id, None).await;
+                    handler.edit_scene(item_id, None).await;
+
+                    // Remove the item from any scene, group, or status it's a part of
+                    handler.remove_item(item_id).await;
+
+                    // Get the description
+                    let description = self.index_access.get_description(&item_id).await;
+
+                    // Remove the entry in the item index
+                    if self.index_access.remove_item(item_id).await {
+                        info!("Item deleted: {}.", description);
+                    } // ignore errors
+                }
+            }
+        }
+
+        // Note the change for the next autosave
+        if let Some(ref mut autosave) = self.autosave {
+            autosave.mark_changed();
+        }
+
+        // Return the modifications to reverse the change
+        Some(inverse)
+    }
+
     /// An internal method to try to load the provided configuration into the
     /// system interface. If no filepath is provided, the function will create
     /// a new empty configuration.
@@ -762,6 +1353,13 @@ impl SystemInterface {
     /// all other types of errors will be logged.
     ///
     async fn load_config(&mut self, filepath: Option<PathBuf>, log_failure: bool) {
+        // Stop watching the previous configuration file and release its sync address
+        self.config_watcher = None;
+        let is_file = filepath.is_some();
+        if let Some(ref mut handler) = self.event_handler {
+            handler.stop_sync().await;
+        }
+
         // Create a new event handler
         let event_handler = match EventHandler::new(
             filepath,
@@ -770,14 +1368,130 @@ impl SystemInterface {
             self.internal_send.clone(),
             self.interface_send.clone(),
             self.limited_send.clone(),
+            &self.run_address,
             log_failure,
+            None,
         )
         .await
         {
             Ok(evnt_hdlr) => evnt_hdlr,
-            Err(_) => return, // errors will be logged separately if log_failure is true
+
+            // Keep sharing the previous configuration (errors will be logged separately if log_failure is true)
+            Err(_) => {
+                if let Some(ref mut handler) = self.event_handler {
+                    handler.restart_sync(self.internal_send.clone()).await;
+                }
+                return;
+            }
         };
 
+        // Start using the new event handler
+        self.install_handler(event_handler).await;
+
+        // Watch the new configuration file, if loaded from a file
+        if is_file {
+            self.watch_config_file().await;
+        }
+    }
+
+    /// An internal method to reload the configuration file after it has
+    /// changed, preserving the current scene, statuses, and queued events
+    /// where they still exist in the new configuration.
+    ///
+    /// # Errors
+    ///
+    /// If the new configuration fails validation or cannot be loaded, the
+    /// running configuration is kept and the reasons are logged as errors.
+    /// The running configuration is also kept if it has unsaved edits.
+    ///
+    async fn reload_config(&mut self, path: PathBuf) {
+        // Ignore changes to any file other than the current configuration
+        let Some(ref mut handler) = self.event_handler else {
+            return;
+        };
+        if handler.get_config_path() != path {
+            return;
+        }
+
+        // Keep the running configuration if it has unsaved edits
+        if self.edit_history.has_unsaved_changes() {
+            error!(
+                "Configuration file changed, but the running configuration has unsaved edits. Save or reload the configuration manually."
+            );
+            return;
+        }
+        info!("Configuration file changed. Reloading ...");
+
+        // Read and validate the new configuration
+        let config_string = match tokio::fs::read_to_string(&path).await {
+            Ok(config_string) => config_string,
+            Err(error) => {
+                error!("Configuration reload failed: {}.", error);
+                return;
+            }
+        };
+        let report = validate_config(&path.to_string_lossy(), &config_string);
+        if report.has_errors() {
+            error!(
+                "Configuration reload failed. Keeping the running configuration:\n{}",
+                report.to_text()
+            );
+            return;
+        }
+
+        // Capture the current game state, stop the current event queue, and
+        // release the sync address and the backup for the new event handler
+        // (the backup is kept when the current event handler is dropped)
+        let state = handler.capture_state();
+        handler.clear_events().await;
+        handler.stop_sync().await;
+        handler.close_backup();
+
+        // Create the new event handler from the previous game state
+        let event_handler = match EventHandler::new(
+            Some(path),
+            self.index_access.clone(),
+            self.style_access.clone(),
+            self.internal_send.clone(),
+            self.interface_send.clone(),
+            self.limited_send.clone(),
+            &self.run_address,
+            true,
+            state.clone(),
+        )
+        .await
+        {
+            Ok(evnt_hdlr) => evnt_hdlr,
+
+            // Restore the event queue and keep the running configuration
+            Err(error) => {
+                error!(
+                    "Configuration reload failed. Keeping the running configuration: {}.",
+                    error
+                );
+                handler.reopen_backup();
+                handler.restart_sync(self.internal_send.clone()).await;
+                if let Some(state) = state {
+                    for event in state.queued_events {
+                        handler
+                            .add_event(EventDelay::new(Some(event.remaining), event.event_id))
+                            .await;
+                    }
+                }
+                return;
+            }
+        };
+
+        // Start using the new event handler and watch any new included files
+        self.install_handler(event_handler).await;
+        self.watch_config_file().await;
+        info!("Configuration reloaded.");
+    }
+
+    /// An internal method to start using the provided event handler and to
+    /// notify the user interface of the change.
+    ///
+    async fn install_handler(&mut self, event_handler: EventHandler) {
         // Create a new connection to the hardware system
         self.system_connection
             .update_system_connections(Some((
@@ -789,8 +1503,68 @@ impl SystemInterface {
         // Trigger a redraw of the system
         self.interface_send.send(InterfaceUpdate::RefreshAll).await;
 
-        // Update the event handler
+        // Update the event handler and forget the changes to the previous configuration
         self.event_handler = Some(event_handler);
+        self.edit_history.clear();
+        if let Some(ref mut autosave) = self.autosave {
+            autosave.mark_saved();
+        }
+    }
+
+    /// An internal method to write the current configuration to a new
+    /// autosave file, if it has changed since it was last saved, and to
+    /// remove the oldest autosaves.
+    ///
+    async fn autosave_config(&mut self) {
+        // Check that there are changes to save
+        let (Some(autosave), Some(handler)) = (&mut self.autosave, &self.event_handler) else {
+            return;
+        };
+        if !autosave.take_changed() {
+            return;
+        }
+
+        // Write the configuration to a new autosave file
+        let config_path = handler.get_config_path();
+        match autosave.next_path(&config_path) {
+            Ok(path) => {
+                handler.autosave_config(&path).await;
+                info!("Configuration autosaved: {}.", path.display());
+            }
+            Err(error) => {
+                error!("Unable to create autosave directory: {}.", error);
+                return;
+            }
+        }
+
+        // Remove the oldest autosaves
+        autosave.rotate(&config_path);
+    }
+
+    /// An internal method to start watching the current configuration file,
+    /// replacing any existing watcher, if the watch config flag is set.
+    ///
+    async fn watch_config_file(&mut self) {
+        // Only watch the file if the flag is set
+        if !self.watch_config {
+            return;
+        }
+
+        // Watch the configuration files of the current event handler, if they exist
+        self.config_watcher = None;
+        if let Some(ref handler) = self.event_handler {
+            let path = handler.get_config_path();
+            if path.exists() {
+                self.config_watcher = Some(
+                    ConfigWatcher::new(
+                        path,
+                        handler.get_include_paths(),
+                        self.internal_send.clone(),
+                    )
+                    .await,
+                );
+            }
+        }
     }
 }
 
@@ -799,6 +1573,12 @@ enum UnpackResult {
     // A variant for successful unpacking
     Success,
 
+    // A variant for successful unpacking with backup metrics
+    SuccessWithBackupMetrics(Option<BackupMetrics>),
+
+    // A variant for successful unpacking with the differences between two configurations
+    SuccessWithConfigDiff(ConfigDiff),
+
     // A variant for successful unpacking with current scene and status
     SuccessWithCurrentSceneAndStatus((ItemId, CurrentStatus)),
 
@@ -814,9 +1594,27 @@ enum UnpackResult {
     // A variant for successful unpacking with message
     SuccessWithMessage(String),
 
+    // A variant for successful unpacking with a media report
+    SuccessWithMediaReport(MediaReport),
+
+    // A variant for successful unpacking with snapshots
+    SuccessWithSnapshots(Vec<SnapshotInfo>),
+
+    // A variant for successful unpacking with records from the game log
+    SuccessWithGameLog(Vec<GameRecord>),
+
+    // A variant for successful unpacking with a summary of the game sessions
+    SuccessWithSessions(Vec<SessionSummary>),
+
+    // A variant for successful unpacking with the room statistics
+    SuccessWithStatistics(RoomStatistics),
+
     // A variant for successful unpacking with system parameters
     SuccessWithParameters(ConfigParameters),
 
+    // A variant for successful unpacking with the other configuration settings
+    SuccessWithSettings(ConfigSettings),
+
     // A variant for successful unpacking with config path
     SuccessWithPath(PathBuf),
 
@@ -826,9 +1624,15 @@ enum UnpackResult {
     // A variant for successful unpacking with a status
     SuccessWithStatus(Status),
 
+    // A variant for successful unpacking with the shared statuses and scenes
+    SuccessWithSyncState(Vec<SyncEntry>),
+
     // A variant for unsuccessful unpacking
     Failure(String),
 
+    // A variant for unsuccessful unpacking with the problems with a batch of modifications
+    FailureWithModificationErrors(Vec<ModificationError>),
+
     // A variant to indicate the program should close
     Close,
 
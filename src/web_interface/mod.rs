@@ -21,6 +21,9 @@
 // Import crate definitions
 use crate::definitions::*;
 
+// Import other structures
+use crate::notification_log::NotificationLog;
+
 // Define private submodules
 mod web_definitions;
 
@@ -72,6 +75,7 @@ impl WebInterface {
     pub async fn launch(
         index_access: IndexAccess,
         style_access: StyleAccess,
+        notification_log: NotificationLog,
         web_send: WebSend,
         interface_receive: mpsc::Receiver<InterfaceUpdate>,
         limited_receive: mpsc::Receiver<LimitedUpdate>,
@@ -315,6 +319,14 @@ impl WebInterface {
                         ws.on_upgrade(move |socket| WebInterface::add_listener(sender, socket))
                     });
 
+                // Create the acknowledge notifications filter
+                let acknowledge_notifications = warp::post()
+                    .and(warp::path("acknowledgeNotifications"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(notification_log.clone()))
+                    .and(WebInterface::with_json::<AcknowledgeNotifications>())
+                    .and_then(WebInterface::handle_acknowledge_notifications);
+
                 // Create the all current items filter
                 let all_current_items = warp::get()
                     .and(warp::path("allCurrentItems"))
@@ -393,6 +405,22 @@ impl WebInterface {
                     .and(WebInterface::with_json::<FullCueEvent>())
                     .and_then(WebInterface::handle_request);
 
+                // Create the delete snapshot filter
+                let delete_snapshot = warp::post()
+                    .and(warp::path("deleteSnapshot"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(clone_send.clone()))
+                    .and(WebInterface::with_json::<DeleteSnapshot>())
+                    .and_then(WebInterface::handle_request);
+
+                // Create the deliver hint filter
+                let deliver_hint = warp::post()
+                    .and(warp::path("deliverHint"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(clone_send.clone()))
+                    .and(WebInterface::with_clone(UserRequest::DeliverHint))
+                    .and_then(WebInterface::handle_request);
+
                 // Create the event change filter
                 let event_change = warp::post()
                     .and(warp::path("eventChange"))
@@ -431,6 +459,61 @@ impl WebInterface {
                     .and(warp::path::end())
                     .and_then(WebInterface::handle_request);
 
+                // Create the list snapshots filter
+                let list_snapshots = warp::get()
+                    .and(warp::path("listSnapshots"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(clone_send.clone()))
+                    .and(WebInterface::with_clone(UserRequest::ListSnapshots))
+                    .and_then(WebInterface::handle_request);
+
+                // Create the media complete filter
+                let media_complete = warp::post()
+                    .and(warp::path("mediaComplete"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(clone_send.clone()))
+                    .and(WebInterface::with_json::<MediaComplete>())
+                    .and_then(WebInterface::handle_request);
+
+                // Create the end session filter
+                let end_session = warp::post()
+                    .and(warp::path("endSession"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(clone_send.clone()))
+                    .and(WebInterface::with_json::<web_definitions::EndSession>())
+                    .and_then(WebInterface::handle_request);
+
+                // Create the list sessions filter
+                let list_sessions = warp::get()
+                    .and(warp::path("listSessions"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(clone_send.clone()))
+                    .and(WebInterface::with_clone(UserRequest::ListSessions))
+                    .and_then(WebInterface::handle_request);
+
+                // Create the notifications filter
+                let notifications = warp::get()
+                    .and(warp::path("notifications"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(notification_log.clone()))
+                    .and_then(WebInterface::handle_notifications);
+
+                // Create the restore snapshot filter
+                let restore_snapshot = warp::post()
+                    .and(warp::path("restoreSnapshot"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(clone_send.clone()))
+                    .and(WebInterface::with_json::<RestoreSnapshot>())
+                    .and_then(WebInterface::handle_request);
+
+                // Create the save snapshot filter
+                let save_snapshot = warp::post()
+                    .and(warp::path("saveSnapshot"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(clone_send.clone()))
+                    .and(WebInterface::with_json::<SaveSnapshot>())
+                    .and_then(WebInterface::handle_request);
+
                 // Create the scene change filter
                 let scene_change = warp::post()
                     .and(warp::path("sceneChange"))
@@ -439,6 +522,22 @@ impl WebInterface {
                     .and(WebInterface::with_json::<SceneChange>())
                     .and_then(WebInterface::handle_request);
 
+                // Create the session details filter
+                let session_details = warp::post()
+                    .and(warp::path("sessionDetails"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(clone_send.clone()))
+                    .and(WebInterface::with_json::<SessionDetails>())
+                    .and_then(WebInterface::handle_request);
+
+                // Create the start session filter
+                let start_session = warp::post()
+                    .and(warp::path("startSession"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(clone_send.clone()))
+                    .and(WebInterface::with_clone(UserRequest::StartSession))
+                    .and_then(WebInterface::handle_request);
+
                 // Create the shutdown filter
                 let shutdown = warp::post()
                     .and(warp::path("shutdown"))
@@ -455,11 +554,22 @@ impl WebInterface {
                     .and(WebInterface::with_json::<StatusChange>())
                     .and_then(WebInterface::handle_request);
 
+                // Create the sync state filter
+                let sync_state = warp::get()
+                    .and(warp::path("syncState"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(clone_send.clone()))
+                    .and(WebInterface::with_clone(UserRequest::Detail {
+                        detail_type: DetailType::SyncState,
+                    }))
+                    .and_then(WebInterface::handle_request);
+
                 // Create the main page filter
                 let run_page = warp::get().and(embed(&RunWebsite));
 
                 // Combine the filters
                 let run_routes = listen
+                    .or(acknowledge_notifications)
                     .or(all_current_items)
                     .or(all_event_change)
                     .or(all_groups)
@@ -469,14 +579,26 @@ impl WebInterface {
                     .or(close)
                     .or(config_file)
                     .or(cue_event)
+                    .or(delete_snapshot)
+                    .or(deliver_hint)
+                    .or(end_session)
                     .or(event_change)
                     .or(get_item)
                     .or(get_group)
                     .or(get_styles)
                     .or(get_type)
+                    .or(list_sessions)
+                    .or(list_snapshots)
+                    .or(media_complete)
+                    .or(notifications)
+                    .or(restore_snapshot)
+                    .or(save_snapshot)
                     .or(scene_change)
+                    .or(session_details)
                     .or(shutdown)
+                    .or(start_session)
                     .or(status_change)
+                    .or(sync_state)
                     .or(run_page);
 
                 // Serve this route on a separate port
@@ -523,6 +645,16 @@ impl WebInterface {
                     }))
                     .and_then(WebInterface::handle_request);
 
+                // Create the backup metrics filter
+                let backup_metrics = warp::get()
+                    .and(warp::path("backupMetrics"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_clone(UserRequest::Detail {
+                        detail_type: DetailType::BackupMetrics,
+                    }))
+                    .and_then(WebInterface::handle_request);
+
                 // Create the close filter
                 let close = warp::post()
                     .and(warp::path("close"))
@@ -539,6 +671,14 @@ impl WebInterface {
                     .and(WebInterface::with_json::<ConfigFile>())
                     .and_then(WebInterface::handle_request);
 
+                // Create the diff config filter
+                let diff_config = warp::post()
+                    .and(warp::path("diffConfig"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_json::<DiffConfig>())
+                    .and_then(WebInterface::handle_request);
+
                 // Create the edit filter
                 let edit = warp::post()
                     .and(warp::path("edit"))
@@ -547,6 +687,22 @@ impl WebInterface {
                     .and(WebInterface::with_json::<Edit>())
                     .and_then(WebInterface::handle_request);
 
+                // Create the export cues filter
+                let export_cues = warp::post()
+                    .and(warp::path("exportCues"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_json::<ExportCues>())
+                    .and_then(WebInterface::handle_request);
+
+                // Create the game log filter
+                let game_log = warp::post()
+                    .and(warp::path("gameLog"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_json::<GameLog>())
+                    .and_then(WebInterface::handle_request);
+
                 // Create the get config parameters filter
                 let get_config_param = warp::get()
                     .and(warp::path("getConfigParam"))
@@ -555,6 +711,14 @@ impl WebInterface {
                     .and(WebInterface::with_clone(UserRequest::ConfigParameters))
                     .and_then(WebInterface::handle_request);
 
+                // Create the get config settings filter
+                let get_config_settings = warp::get()
+                    .and(warp::path("getConfigSettings"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_clone(UserRequest::ConfigSettings))
+                    .and_then(WebInterface::handle_request);
+
                 // Create the get config path filter
                 let get_config_path = warp::get()
                     .and(warp::path("getConfigPath"))
@@ -587,6 +751,16 @@ impl WebInterface {
                     .and(warp::path::end())
                     .and_then(WebInterface::handle_request);
 
+                // Create the get media report filter
+                let get_media_report = warp::get()
+                    .and(warp::path("getMediaReport"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_clone(UserRequest::Detail {
+                        detail_type: DetailType::MediaReport,
+                    }))
+                    .and_then(WebInterface::handle_request);
+
                 // Create the get scene filter
                 let get_scene = warp::get()
                     .and(warp::path("getScene"))
@@ -617,6 +791,38 @@ impl WebInterface {
                     .and(warp::path::end())
                     .and_then(WebInterface::handle_request);
 
+                // Create the import cues filter
+                let import_cues = warp::post()
+                    .and(warp::path("importCues"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_json::<ImportCues>())
+                    .and_then(WebInterface::handle_request);
+
+                // Create the merge config filter
+                let merge_config = warp::post()
+                    .and(warp::path("mergeConfig"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_json::<MergeConfig>())
+                    .and_then(WebInterface::handle_request);
+
+                // Create the recover autosave filter
+                let recover_autosave = warp::post()
+                    .and(warp::path("recoverAutosave"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_clone(UserRequest::RecoverAutosave))
+                    .and_then(WebInterface::handle_request);
+
+                // Create the redo filter
+                let redo = warp::post()
+                    .and(warp::path("redo"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_clone(UserRequest::Redo))
+                    .and_then(WebInterface::handle_request);
+
                 // Create the save config filter FIXME verify filenames
                 let save_config = warp::post()
                     .and(warp::path("saveConfig"))
@@ -641,6 +847,30 @@ impl WebInterface {
                     .and(WebInterface::with_clone(UserRequest::Shutdown))
                     .and_then(WebInterface::handle_request);
 
+                // Create the statistics filter
+                let statistics = warp::post()
+                    .and(warp::path("statistics"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_json::<Statistics>())
+                    .and_then(WebInterface::handle_request);
+
+                // Create the statistics export filter
+                let statistics_csv = warp::post()
+                    .and(warp::path("statisticsCsv"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_json::<Statistics>())
+                    .and_then(WebInterface::handle_statistics_csv);
+
+                // Create the undo filter
+                let undo = warp::post()
+                    .and(warp::path("undo"))
+                    .and(warp::path::end())
+                    .and(WebInterface::with_clone(web_send.clone()))
+                    .and(WebInterface::with_clone(UserRequest::Undo))
+                    .and_then(WebInterface::handle_request);
+
                 // Create the main page filter
                 let edit_page = warp::get().and(embed(&EditWebsite));
 
@@ -649,21 +879,34 @@ impl WebInterface {
                     .or(all_items)
                     .or(all_groups)
                     .or(all_scenes)
+                    .or(backup_metrics)
                     .or(close)
                     .or(config_file)
+                    .or(diff_config)
                     .or(edit)
+                    .or(export_cues)
+                    .or(game_log)
                     .or(get_config_param)
+                    .or(get_config_settings)
                     .or(get_config_path)
                     .or(get_event)
                     .or(get_item)
                     .or(get_group)
+                    .or(get_media_report)
                     .or(get_scene)
                     .or(get_status)
                     .or(get_styles)
                     .or(get_type)
+                    .or(import_cues)
+                    .or(merge_config)
+                    .or(recover_autosave)
+                    .or(redo)
                     .or(save_config)
                     .or(save_style)
                     .or(shutdown)
+                    .or(statistics)
+                    .or(statistics_csv)
+                    .or(undo)
                     .or(edit_page);
 
                 // Handle incoming requests on the edit port
@@ -1115,6 +1358,40 @@ impl WebInterface {
         }
     }
 
+    /// A function to handle requests to export the room statistics as
+    /// comma-separated values
+    ///
+    async fn handle_statistics_csv(
+        web_send: WebSend,
+        statistics: Statistics,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        // Send the message and wait for the reply
+        let (reply_to, rx) = oneshot::channel();
+        web_send.send(reply_to, statistics.into()).await;
+
+        // If the reply contains statistics, return them as a csv file
+        if let Ok(WebReply {
+            data: WebReplyData::Statistics(statistics),
+            ..
+        }) = rx.await
+        {
+            return Ok(warp::reply::with_status(
+                warp::reply::with_header(statistics.to_csv(), "content-type", "text/csv"),
+                http::StatusCode::OK,
+            ));
+        }
+
+        // Otherwise, note the error
+        Ok(warp::reply::with_status(
+            warp::reply::with_header(
+                "Unable to compute statistics.".to_string(),
+                "content-type",
+                "text/plain",
+            ),
+            http::StatusCode::BAD_REQUEST,
+        ))
+    }
+
     /// A function to handle all item requests (processed by the index)
     ///
     async fn handle_all_items(
@@ -1154,6 +1431,37 @@ impl WebInterface {
         ));
     }
 
+    /// A function to handle requests for the notification history
+    ///
+    async fn handle_notifications(
+        notification_log: NotificationLog,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        // Return every notification in the log
+        Ok(warp::reply::with_status(
+            warp::reply::json(&WebReply {
+                is_valid: true,
+                data: WebReplyData::Notifications(notification_log.get_all()),
+            }),
+            http::StatusCode::OK,
+        ))
+    }
+
+    /// A function to handle acknowledging notifications
+    ///
+    async fn handle_acknowledge_notifications(
+        notification_log: NotificationLog,
+        acknowledge: AcknowledgeNotifications,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        // Acknowledge the notifications (automatically updates the interface)
+        notification_log.acknowledge(acknowledge.ids);
+
+        // Return success
+        Ok(warp::reply::with_status(
+            warp::reply::json(&WebReply::success()),
+            http::StatusCode::OK,
+        ))
+    }
+
     /// A function to handle getting the current stylesheet
     async fn handle_get_styles(
         style_access: StyleAccess,
@@ -28,7 +28,7 @@ use std::str::FromStr;
 use std::time::Duration;
 
 // Import Chrono features
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 
 // Import Tokio and warp features
 use tokio::sync::mpsc;
@@ -54,6 +54,11 @@ pub struct ListenerWithExpiration {
 ///
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct AcknowledgeNotifications {
+    pub ids: Option<Vec<u64>>,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AllEventChange {
     adjustment_secs: u64,
     adjustment_nanos: u64,
@@ -81,6 +86,16 @@ pub struct FullCueEvent {
 pub struct DebugMode {
     is_debug: bool,
 }
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteSnapshot {
+    name: String,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffConfig {
+    filename: String,
+}
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Edit {
@@ -88,6 +103,11 @@ pub struct Edit {
 }
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct EndSession {
+    outcome: String,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ErrorLog {
     filename: String,
 }
@@ -100,11 +120,19 @@ pub struct EventChange {
 }
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct GameLog {
+pub struct ExportCues {
     filename: String,
 }
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct GameLog {
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    event_id: Option<u32>,
+    session: Option<String>,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetEvent {
     id: u32,
 }
@@ -135,6 +163,23 @@ pub struct GetType {
 }
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct ImportCues {
+    filename: String,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaComplete {
+    channel: u32,
+    uri: String,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeConfig {
+    filename: String,
+    changes: Vec<ConfigChange>,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ProcessEvent {
     event_id: u32,
     check_scene: bool,
@@ -142,11 +187,21 @@ pub struct ProcessEvent {
 }
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct RestoreSnapshot {
+    name: String,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SaveConfig {
     filename: String,
 }
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct SaveSnapshot {
+    name: String,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SaveStyles {
     pub new_styles: StyleMap,
 }
@@ -157,6 +212,18 @@ pub struct SceneChange {
 }
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct SessionDetails {
+    team_name: Option<String>,
+    player_count: Option<u32>,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Statistics {
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct StatusChange {
     status_id: u32,
     state_id: u32,
@@ -286,6 +353,20 @@ impl From<FullCueEvent> for UserRequest {
         }
     }
 }
+impl From<DeleteSnapshot> for UserRequest {
+    fn from(delete_snapshot: DeleteSnapshot) -> Self {
+        UserRequest::DeleteSnapshot {
+            name: delete_snapshot.name,
+        }
+    }
+}
+impl From<DiffConfig> for UserRequest {
+    fn from(diff_config: DiffConfig) -> Self {
+        UserRequest::DiffConfig {
+            filepath: PathBuf::from(diff_config.filename),
+        }
+    }
+}
 impl From<Edit> for UserRequest {
     fn from(edit: Edit) -> Self {
         UserRequest::Edit {
@@ -293,6 +374,13 @@ impl From<Edit> for UserRequest {
         }
     }
 }
+impl From<EndSession> for UserRequest {
+    fn from(end_session: EndSession) -> Self {
+        UserRequest::EndSession {
+            outcome: end_session.outcome,
+        }
+    }
+}
 impl From<EventChange> for UserRequest {
     fn from(event_change: EventChange) -> Self {
         UserRequest::EventChange {
@@ -302,6 +390,25 @@ impl From<EventChange> for UserRequest {
         }
     }
 }
+impl From<ExportCues> for UserRequest {
+    fn from(export_cues: ExportCues) -> Self {
+        UserRequest::ExportCues {
+            filepath: PathBuf::from(export_cues.filename),
+        }
+    }
+}
+impl From<GameLog> for UserRequest {
+    fn from(game_log: GameLog) -> Self {
+        UserRequest::GameLog {
+            query: GameLogQuery {
+                start_date: game_log.start_date,
+                end_date: game_log.end_date,
+                event_id: game_log.event_id.map(ItemId::new_unchecked),
+                session: game_log.session,
+            },
+        }
+    }
+}
 impl From<GetEvent> for UserRequest {
     fn from(get_event: GetEvent) -> Self {
         UserRequest::Detail {
@@ -347,6 +454,36 @@ impl From<GetType> for UserRequest {
         }
     }
 }
+impl From<ImportCues> for UserRequest {
+    fn from(import_cues: ImportCues) -> Self {
+        UserRequest::ImportCues {
+            filepath: PathBuf::from(import_cues.filename),
+        }
+    }
+}
+impl From<MediaComplete> for UserRequest {
+    fn from(media_complete: MediaComplete) -> Self {
+        UserRequest::MediaComplete {
+            channel: media_complete.channel,
+            uri: media_complete.uri,
+        }
+    }
+}
+impl From<MergeConfig> for UserRequest {
+    fn from(merge_config: MergeConfig) -> Self {
+        UserRequest::MergeConfig {
+            filepath: PathBuf::from(merge_config.filename),
+            changes: merge_config.changes,
+        }
+    }
+}
+impl From<RestoreSnapshot> for UserRequest {
+    fn from(restore_snapshot: RestoreSnapshot) -> Self {
+        UserRequest::RestoreSnapshot {
+            name: restore_snapshot.name,
+        }
+    }
+}
 impl From<SaveConfig> for UserRequest {
     fn from(save_config: SaveConfig) -> Self {
         UserRequest::SaveConfig {
@@ -354,6 +491,13 @@ impl From<SaveConfig> for UserRequest {
         }
     }
 }
+impl From<SaveSnapshot> for UserRequest {
+    fn from(save_snapshot: SaveSnapshot) -> Self {
+        UserRequest::SaveSnapshot {
+            name: save_snapshot.name,
+        }
+    }
+}
 impl From<SceneChange> for UserRequest {
     fn from(scene_change: SceneChange) -> Self {
         UserRequest::SceneChange {
@@ -361,6 +505,25 @@ impl From<SceneChange> for UserRequest {
         }
     }
 }
+impl From<SessionDetails> for UserRequest {
+    fn from(session_details: SessionDetails) -> Self {
+        UserRequest::SessionDetails {
+            team_name: session_details.team_name,
+            player_count: session_details.player_count,
+        }
+    }
+}
+impl From<Statistics> for UserRequest {
+    fn from(statistics: Statistics) -> Self {
+        UserRequest::Statistics {
+            query: GameLogQuery {
+                start_date: statistics.start_date,
+                end_date: statistics.end_date,
+                ..Default::default()
+            },
+        }
+    }
+}
 impl From<StatusChange> for UserRequest {
     fn from(status_change: StatusChange) -> Self {
         UserRequest::StatusChange {
@@ -9,63 +9,87 @@ export class Action extends React.PureComponent {
   // Render the event action
   render() {
     // Switch based on the props
-    if (this.props.action.hasOwnProperty(`NewScene`)) {
+    if (this.props.action.hasOwnProperty(`newScene`)) {
       return (
-        <NewScene newScene={this.props.action.NewScene} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
+        <NewScene newScene={this.props.action.newScene} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
       );
     
     // Modify Status
-    } else if (this.props.action.hasOwnProperty(`ModifyStatus`)) {
+    } else if (this.props.action.hasOwnProperty(`modifyStatus`)) {
       return (
-        <ModifyStatus modifyStatus={this.props.action.ModifyStatus} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
+        <ModifyStatus modifyStatus={this.props.action.modifyStatus} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
       );
 
     // Cue Dmx
-    } else if (this.props.action.hasOwnProperty(`CueDmx`)) {
+    } else if (this.props.action.hasOwnProperty(`cueDmx`)) {
       return (
-        <CueDmx cueDmx={this.props.action.CueDmx} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
+        <CueDmx cueDmx={this.props.action.cueDmx} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
       );
     
     // Cue Event
-    } else if (this.props.action.hasOwnProperty(`CueEvent`)) {
+    } else if (this.props.action.hasOwnProperty(`cueEvent`)) {
       return (
-        <CueEvent cueEvent={this.props.action.CueEvent} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
+        <CueEvent cueEvent={this.props.action.cueEvent} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
       );
     
     // Cue Media
-    } else if (this.props.action.hasOwnProperty(`CueMedia`)) {
+    } else if (this.props.action.hasOwnProperty(`cueMedia`)) {
       return (
-        <CueMedia cueMedia={this.props.action.CueMedia} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
+        <CueMedia cueMedia={this.props.action.cueMedia} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
+      );
+
+    // Cue Media Playlist
+    } else if (this.props.action.hasOwnProperty(`cueMediaPlaylist`)) {
+      return (
+        <CueMediaPlaylist cueMediaPlaylist={this.props.action.cueMediaPlaylist} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
       );
 
     // Adjust Media
-    } else if (this.props.action.hasOwnProperty(`AdjustMedia`)) {
+    } else if (this.props.action.hasOwnProperty(`adjustMedia`)) {
       return (
-        <AdjustMedia adjustMedia={this.props.action.AdjustMedia} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
+        <AdjustMedia adjustMedia={this.props.action.adjustMedia} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
       );
 
     // Cancel Event
-    } else if (this.props.action.hasOwnProperty(`CancelEvent`)) {
+    } else if (this.props.action.hasOwnProperty(`cancelEvent`)) {
       return (
-        <CancelEvent cancelEvent={this.props.action.CancelEvent} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
+        <CancelEvent cancelEvent={this.props.action.cancelEvent} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
       );
     
     // Save Data
-    } else if (this.props.action.hasOwnProperty(`SaveData`)) {
+    } else if (this.props.action.hasOwnProperty(`saveData`)) {
       return (
-        <SaveData saveData={this.props.action.SaveData} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
+        <SaveData saveData={this.props.action.saveData} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
       );
     
     // Send Data
-    } else if (this.props.action.hasOwnProperty(`SendData`)) {
+    } else if (this.props.action.hasOwnProperty(`sendData`)) {
       return (
-        <SendData sendData={this.props.action.SendData} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
+        <SendData sendData={this.props.action.sendData} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
       );
 
     // Select Event
-    } else if (this.props.action.hasOwnProperty(`SelectEvent`)) {
+    } else if (this.props.action.hasOwnProperty(`selectEvent`)) {
+      return (
+        <SelectEvent selectEvent={this.props.action.selectEvent} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
+      );
+
+    // Start Session
+    } else if (this.props.action.hasOwnProperty(`startSession`)) {
+      return (
+        <StartSession changeAction={this.props.changeAction} />
+      );
+
+    // End Session
+    } else if (this.props.action.hasOwnProperty(`endSession`)) {
+      return (
+        <EndSession endSession={this.props.action.endSession} changeAction={this.props.changeAction} />
+      );
+
+    // Deliver Hint
+    } else if (this.props.action.hasOwnProperty(`deliverHint`)) {
       return (
-        <SelectEvent selectEvent={this.props.action.SelectEvent} grabFocus={this.props.grabFocus} changeAction={this.props.changeAction} selectMenu={this.props.selectMenu} />
+        <DeliverHint changeAction={this.props.changeAction} />
       );
     }
     
@@ -98,7 +122,7 @@ export class NewScene extends React.PureComponent {
   async updateItem() {
     try {
       // Fetch the description of the status
-      let response = await fetch(`getItem/${this.props.newScene.new_scene.id}`);
+      let response = await fetch(`getItem/${this.props.newScene.newScene.id}`);
       const json = await response.json();
 
       // If valid, save the result to the state
@@ -120,8 +144,8 @@ export class NewScene extends React.PureComponent {
     if (!this.state.isMenuVisible) {
       // Try to claim the select menu, return on failure
       if (!this.props.selectMenu(<SelectMenu type="scene" closeMenu={this.toggleMenu} addItem={(id) => {this.toggleMenu(); this.props.changeAction({
-        NewScene: {
-          new_scene: {
+        newScene: {
+          newScene: {
             id: id
           }
         }
@@ -154,7 +178,7 @@ export class NewScene extends React.PureComponent {
   render() {
     return (
       <>
-        <ActionFragment title="New Scene" nodeType="scene" focusOn={() => this.props.grabFocus(this.props.newScene.new_scene.id)} changeAction={this.props.changeAction} content={
+        <ActionFragment title="New Scene" nodeType="scene" focusOn={() => this.props.grabFocus(this.props.newScene.newScene.id)} changeAction={this.props.changeAction} content={
           <div className="actionDetail" onClick={(e) => {stopPropogation(e); this.toggleMenu()}}>
             <div className={this.state.isMenuVisible && "isEditing"}>{this.state.description}</div>
             <div className="editNote">Click To Change</div>
@@ -190,21 +214,21 @@ export class ModifyStatus extends React.PureComponent {
   // Helper function to update the item information
   async updateItems() {
     // Ignore invalid status numbers
-    if (this.props.modifyStatus.status_id.id === 0) {
+    if (this.props.modifyStatus.statusId.id === 0) {
       return;
     }
 
     try {
       // Fetch the description of the status
-      let response = await fetch(`getItem/${this.props.modifyStatus.status_id.id}`);
+      let response = await fetch(`getItem/${this.props.modifyStatus.statusId.id}`);
       const json1 = await response.json();
 
       // Fetch the description of the state
-      response = await fetch(`getItem/${this.props.modifyStatus.new_state.id}`);
+      response = await fetch(`getItem/${this.props.modifyStatus.newState.id}`);
       const json2 = await response.json();
 
       // Fetch the valid states for the status
-      response = await fetch(`getStatus/${this.props.modifyStatus.status_id.id}`);
+      response = await fetch(`getStatus/${this.props.modifyStatus.statusId.id}`);
       const json3 = await response.json();
 
       // If all three are valid, save the result to the state
@@ -228,11 +252,11 @@ export class ModifyStatus extends React.PureComponent {
     if (!this.state.isStatusMenuVisible) {
       // Try to claim the select menu, return on failure
       if (!this.props.selectMenu(<SelectMenu type="status" closeMenu={this.toggleStatusMenu} addItem={(id) => {this.toggleStatusMenu(); this.props.changeAction({
-        ModifyStatus: {
-          status_id: {
+        modifyStatus: {
+          statusId: {
             id: id,
           },
-          new_state: this.props.modifyStatus.new_state,
+          newState: this.props.modifyStatus.newState,
         }
       })}}/>)) {
         return;
@@ -255,9 +279,9 @@ export class ModifyStatus extends React.PureComponent {
     if (!this.state.isStateMenuVisible) {
       // Try to claim the select menu, return on failure
       if (!this.props.selectMenu(<SelectMenu type="event" items={this.state.validStates} closeMenu={this.toggleStateMenu} addItem={(id) => {this.toggleStateMenu(); this.props.changeAction({
-        ModifyStatus: {
-          status_id: this.props.modifyStatus.status_id,
-          new_state: {
+        modifyStatus: {
+          statusId: this.props.modifyStatus.statusId,
+          newState: {
             id: id,
           },
         }
@@ -284,7 +308,7 @@ export class ModifyStatus extends React.PureComponent {
   // On change of item id, pull the description of the scene
   componentDidUpdate(prevProps, prevState) {
     // Update the item descriptions, if either changed
-    if ((this.props.modifyStatus.status_id.id !== prevProps.modifyStatus.status_id.id) || (this.props.modifyStatus.new_state.id !== prevProps.modifyStatus.new_state.id)) {
+    if ((this.props.modifyStatus.statusId.id !== prevProps.modifyStatus.statusId.id) || (this.props.modifyStatus.newState.id !== prevProps.modifyStatus.newState.id)) {
       this.updateItems();
     }
   }
@@ -293,13 +317,13 @@ export class ModifyStatus extends React.PureComponent {
   render() {
     return (
       <>
-        <ActionFragment title="Modify Status" nodeType="status" focusOn={() => this.props.grabFocus(this.props.modifyStatus.status_id.id)} changeAction={this.props.changeAction} content={
+        <ActionFragment title="Modify Status" nodeType="status" focusOn={() => this.props.grabFocus(this.props.modifyStatus.statusId.id)} changeAction={this.props.changeAction} content={
           <div className="actionDetail" onClick={stopPropogation}>
             <div className={this.state.isStatusMenuVisible && "isEditing"} onClick={this.toggleStatusMenu}>{this.state.description}</div>
             <div className="editNote" onClick={this.toggleStatusMenu}>Click To Change</div>
             <div className="additionalInfo">New State:
               <div className={`additionalInfoDetail ${this.state.isStateMenuVisible && "isEditing"}`} onClick={this.toggleStateMenu}>{this.state.stateDescription}</div>
-              <SendNode type="event" onPointerDown={(e) => {stopPropogation(e); this.props.grabFocus(this.props.modifyStatus.new_state.id)}}/>
+              <SendNode type="event" onPointerDown={(e) => {stopPropogation(e); this.props.grabFocus(this.props.modifyStatus.newState.id)}}/>
             </div>
           </div>
         }/>
@@ -399,7 +423,7 @@ export class CueDmx extends React.PureComponent {
     // Update the action, with or without duration
     if ((this.state.duration !== 0) && !isNaN(parseInt(this.state.duration))) {
       this.props.changeAction({
-        CueDmx: {
+        cueDmx: {
           fade: {
             channel: channel,
             value: value,
@@ -412,7 +436,7 @@ export class CueDmx extends React.PureComponent {
       })
     } else {
       this.props.changeAction({
-        CueDmx: {
+        cueDmx: {
           fade: {
             channel: channel,
             value: value,
@@ -543,7 +567,7 @@ export class CueEvent extends React.PureComponent {
     // Update the action, with or without delay
     if ((this.state.delay !== 0) && !isNaN(parseInt(this.state.delay))) {
       this.props.changeAction({
-        CueEvent: {
+        cueEvent: {
           event: {
             event_id: {
               id: new_id
@@ -557,7 +581,7 @@ export class CueEvent extends React.PureComponent {
       })
     } else {
       this.props.changeAction({
-        CueEvent: {
+        cueEvent: {
           event: {
             event_id: {
               id: new_id
@@ -643,6 +667,7 @@ export class CueMedia extends React.PureComponent {
     this.handleUriChange = this.handleUriChange.bind(this);
     this.handleChannelChange = this.handleChannelChange.bind(this);
     this.handleLoopChange = this.handleLoopChange.bind(this);
+    this.handleCompleteChange = this.handleCompleteChange.bind(this);
     this.updateAction = this.updateAction.bind(this);
   }
 
@@ -652,7 +677,7 @@ export class CueMedia extends React.PureComponent {
     let value = e.target.value;
 
     // Save the change immediately
-    this.updateAction(value, null, null);
+    this.updateAction(value, null, null, null);
   }
 
   // Function to handle new channel
@@ -666,7 +691,7 @@ export class CueMedia extends React.PureComponent {
     }
 
     // Save the change immediately
-    this.updateAction(null, channel, null);
+    this.updateAction(null, channel, null, null);
   }
 
   // Function to handle new delay in the input
@@ -675,11 +700,20 @@ export class CueMedia extends React.PureComponent {
     let value = e.target.value;
 
     // Save the change immediately
-    this.updateAction(null, null, value);
+    this.updateAction(null, null, value, null);
+  }
+
+  // Function to handle a new completion event
+  handleCompleteChange(e) {
+    // Extract the value
+    let complete = parseInt(e.target.value);
+
+    // Save the change immediately (zero or invalid removes the event)
+    this.updateAction(null, null, null, isNaN(complete) ? 0 : complete);
   }
 
   // Helper function to update the action
-  updateAction(uri, channel, loop) {
+  updateAction(uri, channel, loop, complete) {
     // If any value is null, replace it with the current value
     if (uri === null) {
       uri = this.props.cueMedia.cue.uri;
@@ -690,28 +724,32 @@ export class CueMedia extends React.PureComponent {
     if (loop === null) {
       loop = this.props.cueMedia.cue.loop_media;
     }
+    if (complete === null) {
+      complete = this.props.cueMedia.cue.on_complete ? this.props.cueMedia.cue.on_complete.id : 0;
+    }
 
-    // Update the action, with or without loop media
-    if (loop !== null && loop !== "") {
-      this.props.changeAction({
-        CueMedia: {
-          cue: {
-            uri: uri,
-            channel: channel,
-            loop_media: loop
-          }
-        }
-      })
-    } else {
-      this.props.changeAction({
-        CueMedia: {
-          cue: {
-            uri: uri,
-            channel: channel
-          }
-        }
-      })
+    // Compose the cue, with or without loop media
+    let cue = {
+      uri: uri,
+      channel: channel,
+    };
+    if (loop !== null && loop !== undefined && loop !== "") {
+      cue.loop_media = loop;
     }
+
+    // Add the completion event, if specified
+    if (complete > 0) {
+      cue.on_complete = {
+        id: complete
+      };
+    }
+
+    // Update the action
+    this.props.changeAction({
+      cueMedia: {
+        cue: cue
+      }
+    })
   }
 
   // Render the completed action
@@ -724,6 +762,7 @@ export class CueMedia extends React.PureComponent {
               <label>File Location</label><input type="text" value={this.props.cueMedia.cue.uri} onInput={this.handleUriChange}></input><br/>
               <label>Channel</label><input type="number" min="0" value={this.props.cueMedia.cue.channel} onInput={this.handleChannelChange}></input><br/>
               <label>Loop Media</label><input type="text" value={this.props.cueMedia.cue.loop_media ? this.props.cueMedia.cue.loop_media : ""} onInput={this.handleLoopChange}></input><br/>
+              <label>Complete Event</label><input type="number" min="0" value={this.props.cueMedia.cue.on_complete ? this.props.cueMedia.cue.on_complete.id : ""} onInput={this.handleCompleteChange}></input><br/>
             </div>
           </div>
         }/>
@@ -793,7 +832,7 @@ export class AdjustMedia extends React.PureComponent {
 
     // Update the action,
     this.props.changeAction({
-      AdjustMedia: {
+      adjustMedia: {
         adjustment: {
           channel: channel,
           direction: direction,
@@ -863,7 +902,7 @@ export class CancelEvent extends React.PureComponent {
     if (!this.state.isMenuVisible) {
       // Try to claim the select menu, return on failure
       if (!this.props.selectMenu(<SelectMenu type="event" closeMenu={this.toggleMenu} addItem={(id) => {this.toggleMenu(); this.props.changeAction({
-        CancelEvent: {
+        cancelEvent: {
           event: {
             id: id
           }
@@ -932,11 +971,11 @@ export class SelectEvent extends React.PureComponent {
   async updateItems() {
     try {
       // Fetch the description of the status
-      let response = await fetch(`getItem/${this.props.selectEvent.status_id.id}`);
+      let response = await fetch(`getItem/${this.props.selectEvent.statusId.id}`);
       const json1 = await response.json();
 
       // Fetch the states of the status
-      response = await fetch(`getStatus/${this.props.selectEvent.status_id.id}`);
+      response = await fetch(`getStatus/${this.props.selectEvent.statusId.id}`);
       const json2 = await response.json();
 
       // If both are valid, save the result to the state
@@ -956,12 +995,12 @@ export class SelectEvent extends React.PureComponent {
   // Helper function to change the event associated with a specific state
   changeSelectedEvent(stateId, eventId) {
     this.props.changeAction({
-      SelectEvent: {
-        status_id: {
-          id: this.props.selectEvent.status_id.id, // Keep the status id the same
+      selectEvent: {
+        statusId: {
+          id: this.props.selectEvent.statusId.id, // Keep the status id the same
         },
-        event_map: {
-          ...this.props.selectEvent.event_map, 
+        eventMap: {
+          ...this.props.selectEvent.eventMap, 
           [stateId]: {
             id: eventId,
           },
@@ -976,11 +1015,11 @@ export class SelectEvent extends React.PureComponent {
     if (!this.state.isMenuVisible) {
       // Try to claim the select menu, return on failure
       if (!this.props.selectMenu(<SelectMenu type="status" closeMenu={this.toggleMenu} addItem={(id) => {this.toggleMenu(); this.props.changeAction({
-        SelectEvent: {
-          status_id: {
+        selectEvent: {
+          statusId: {
             id: id,
           },
-          event_map: {}, // reset to empty
+          eventMap: {}, // reset to empty
         }
       })}}/>)) {
         return;
@@ -1005,7 +1044,7 @@ export class SelectEvent extends React.PureComponent {
   // On change of item id, pull the description of the scene
   componentDidUpdate(prevProps, prevState) {
     // Update the item descriptions, if either changed
-    if ((this.props.selectEvent.status_id.id !== prevProps.selectEvent.status_id.id)) {
+    if ((this.props.selectEvent.statusId.id !== prevProps.selectEvent.statusId.id)) {
       this.updateItems();
     }
   }
@@ -1015,7 +1054,7 @@ export class SelectEvent extends React.PureComponent {
     // Compose any states and matching events into a list
     let children = this.state.validStates.map((state) => {
       // Otherwise, look through the event map
-      for (const [key, value] of Object.entries(this.props.selectEvent.event_map)) {
+      for (const [key, value] of Object.entries(this.props.selectEvent.eventMap)) {
         // If there is an entry for this state
         if (parseInt(key) === state.id) {
           return (
@@ -1039,7 +1078,7 @@ export class SelectEvent extends React.PureComponent {
     // Return the completed action
     return (
       <>
-        <ActionFragment title="Select Event" nodeType="status" focusOn={() => this.props.grabFocus(this.props.selectEvent.status_id.id)} changeAction={this.props.changeAction} content={
+        <ActionFragment title="Select Event" nodeType="status" focusOn={() => this.props.grabFocus(this.props.selectEvent.statusId.id)} changeAction={this.props.changeAction} content={
           <div className="actionDetail" onClick={stopPropogation}>
             <div className={this.state.isMenuVisible && "isEditing"} onClick={this.toggleMenu}>{this.state.description}</div>
             <div className="editNote" onClick={this.toggleMenu}>Click To Change</div>
@@ -1051,6 +1090,24 @@ export class SelectEvent extends React.PureComponent {
   }
 }
 
+// A cue media playlist action
+export class CueMediaPlaylist extends React.PureComponent {  
+  // Render the completed action
+  render() {
+    return (
+      <ActionFragment title="Cue Media Playlist" changeAction={this.props.changeAction} content={
+        <div className="actionDetail" onClick={stopPropogation}>
+          <div className="additionalInfo noDivider">
+            <div>Channel {this.props.cueMediaPlaylist.playlist.channel}</div>
+            {this.props.cueMediaPlaylist.playlist.items.map((item) => <div>{item.uri}</div>)}
+            <div>Editing Not Yet Available</div>
+          </div>
+        </div>
+      }/>
+    );
+  }
+}
+
 // A save data action
 export class SaveData extends React.PureComponent {  
   // Render the completed action
@@ -1071,6 +1128,61 @@ export class SendData extends React.PureComponent {
   }
 }
 
+// A start session action
+export class StartSession extends React.PureComponent {
+  // Render the completed action
+  render() {
+    return (
+      <ActionFragment title="Start Session" changeAction={this.props.changeAction} content={<div className="actionDetail" onClick={stopPropogation}>Starts a new game session.</div>}/>
+    );
+  }
+}
+
+// A deliver hint action
+export class DeliverHint extends React.PureComponent {
+  // Render the completed action
+  render() {
+    return (
+      <ActionFragment title="Deliver Hint" changeAction={this.props.changeAction} content={<div className="actionDetail" onClick={stopPropogation}>Delivers the next hint for the current scene.</div>}/>
+    );
+  }
+}
+
+// An end session action
+export class EndSession extends React.PureComponent {
+  // Class constructor
+  constructor(props) {
+    // Collect props
+    super(props);
+
+    // Bind the various functions
+    this.handleOutcomeChange = this.handleOutcomeChange.bind(this);
+  }
+
+  // Function to handle a new outcome
+  handleOutcomeChange(e) {
+    // Save the change immediately
+    this.props.changeAction({
+      endSession: {
+        outcome: e.target.value
+      }
+    });
+  }
+
+  // Render the completed action
+  render() {
+    return (
+      <ActionFragment title="End Session" changeAction={this.props.changeAction} content={
+        <div className="actionDetail" onClick={stopPropogation}>
+          <div className="additionalInfo noDivider">
+            <label>Outcome</label><input type="text" value={this.props.endSession.outcome} onInput={this.handleOutcomeChange}></input>
+          </div>
+        </div>
+      }/>
+    );
+  }
+}
+
 // An action edit area partial
 export class ActionFragment extends React.PureComponent {  
   constructor(props) {
@@ -3,6 +3,7 @@ import { Action } from './Actions';
 import { ReceiveNode } from './Nodes';
 import { State } from './States';
 import { stopPropogation } from './Functions';
+import { TextInput } from './Buttons';
 import { AddMenu, AddActionMenu, DeleteMenu } from './Menus';
 
 // An item box to select the appropriate sub-box
@@ -226,12 +227,104 @@ export class SceneFragment extends React.PureComponent {
     return (
       <>
         <div className="divButton" onClick={() => {this.props.changeScene(this.props.id)}}>View This Scene</div>
+        <HintsFragment id={this.props.id} saveModifications={this.props.saveModifications}/>
         <EventFragment id={this.props.id} grabFocus={this.props.grabFocus} createConnector={this.props.createConnector} saveModifications={this.props.saveModifications}/>
       </>
     );
   }
 }
 
+// The list of hints for a scene, in the order they are delivered
+export class HintsFragment extends React.PureComponent {
+  // Class constructor
+  constructor(props) {
+    // Collect props
+    super(props);
+
+    // Set initial state
+    this.state = {
+      scene: null, // placeholder for the read data
+    }
+
+    // Bind the various functions
+    this.updateScene = this.updateScene.bind(this);
+    this.saveHints = this.saveHints.bind(this);
+  }
+
+  // Helper function to update the scene information
+  async updateScene() {
+    try {
+      // Fetch the detail of the scene
+      const response = await fetch(`getScene/${this.props.id}`);
+      const json = await response.json();
+
+      // If valid, save the result to the state
+      if (json.isValid) {
+        this.setState({
+          scene: json.data.scene,
+        });
+      }
+
+    // Ignore errors
+    } catch {
+      console.log("Server inaccessible.");
+    }
+  }
+
+  // Helper function to save a new list of hints
+  saveHints(hints) {
+    // Save the change to the scene
+    this.setState((prevState) => {
+      // Copy the scene with the new hints
+      let newScene = {...prevState.scene, hints: hints};
+
+      // Save the changes
+      let modifications = [{
+        modifyScene: {
+          itemId: { id: this.props.id },
+          scene: newScene,
+        },
+      }];
+      this.props.saveModifications(modifications);
+
+      // Update the local state
+      return {
+        scene: newScene,
+      };
+    });
+  }
+
+  // On initial load, pull the hints of the scene
+  componentDidMount() {
+    this.updateScene();
+  }
+
+  // Render the list of hints
+  render() {
+    // Wait for the scene to load
+    if (!this.state.scene) {
+      return null;
+    }
+
+    // Create an entry for each hint
+    const hints = this.state.scene.hints || [];
+    const entries = hints.map((hint, index) =>
+      <div key={index.toString()}>
+        Hint {index + 1}: <TextInput value={hint.message} handleInput={(e) => {let newHints = [...hints]; newHints[index] = {...hint, message: `${e.target.value}`}; this.saveHints(newHints)}} />
+        <span className="deleteHint" onClick={() => {this.saveHints(hints.filter((_, other) => other !== index))}}>X</span>
+      </div>
+    );
+
+    // Render the hints and the button to add another
+    return (
+      <div className="hints" onClick={stopPropogation}>
+        {entries}
+        <div className="divButton" onClick={() => {this.saveHints([...hints, { message: "" }])}}>Add Hint</div>
+      </div>
+    );
+  }
+}
+
 // A group box with other boxes inside
 export class GroupFragment extends React.PureComponent {
   // Class constructor
@@ -333,6 +333,7 @@ export class ConfigArea extends React.PureComponent {
         identifier: { id: 0 },
         defaultScene: { id: 0 },
       },
+      settings: null,
       isMenuVisible: false,
       isFileChanged: false,
       defaultDescription: "Loading ...",
@@ -343,6 +344,8 @@ export class ConfigArea extends React.PureComponent {
     this.updateIdentifier = this.updateIdentifier.bind(this);
     this.updateBackgroundProcess = this.updateBackgroundProcess.bind(this);
     this.updateParameters = this.updateParameters.bind(this);
+    this.updateHintPenalty = this.updateHintPenalty.bind(this);
+    this.updateSettings = this.updateSettings.bind(this);
     this.handleFileChange = this.handleFileChange.bind(this);
     this.toggleDefaultMenu = this.toggleDefaultMenu.bind(this);
   }
@@ -427,6 +430,58 @@ export class ConfigArea extends React.PureComponent {
     });
   }
 
+  // Helper function to update the hint penalty
+  updateHintPenalty(key, e) {
+    // Extract the value
+    let value = parseInt(e.target.value);
+    if (isNaN(value)) {
+      return;
+    }
+
+    // Compose the new hint penalty
+    let new_penalty = {
+      statusId: { id: 0 },
+      newState: { id: 0 },
+    };
+    if (this.state.settings.hintPenalty) {
+      new_penalty = {...this.state.settings.hintPenalty};
+    }
+    new_penalty[`${key}`] = { id: value };
+
+    // If the status is empty, use null instead
+    if (new_penalty.statusId.id === 0) {
+      new_penalty = null;
+    }
+
+    // Save the change
+    this.updateSettings("hintPenalty", new_penalty);
+  }
+
+  // Helper function to update the other settings
+  updateSettings(key, value) {
+    // Save the setting change
+    this.setState((prevState) => {
+      // Copy the current settings
+      let new_settings = {...prevState.settings};
+
+      // Update the seleted value
+      new_settings[`${key}`] = value;
+
+      // Save the changes
+      let modifications = [{
+        modifySettings: {
+          settings: new_settings,
+        },
+      }];
+      this.props.saveModifications(modifications);
+
+      // Update the local state
+      return {
+        settings: new_settings,
+      };
+    });
+  }
+
   // Helper function to handle a change in the filename
   handleFileChange(e) {
     // Note the file change locally
@@ -449,8 +504,8 @@ export class ConfigArea extends React.PureComponent {
   // On initial load, pull the description of the default scene
   async componentDidMount() {
     // Reload the configuration parameters
-    const response = await fetch(`getConfigParam`);
-    const json = await response.json();
+    let response = await fetch(`getConfigParam`);
+    let json = await response.json();
 
     // If the response is valid
     if (json.isValid) {
@@ -460,6 +515,18 @@ export class ConfigArea extends React.PureComponent {
       });
     }
 
+    // Reload the other configuration settings
+    response = await fetch(`getConfigSettings`);
+    json = await response.json();
+
+    // If the response is valid
+    if (json.isValid) {
+      // Save the settings
+      this.setState({
+        settings: json.data.settings,
+      });
+    }
+
     // Update the default scene listing
     this.updateDefaultScene();
   }
@@ -498,6 +565,10 @@ export class ConfigArea extends React.PureComponent {
             Keep Process Running? <ToggleSwitch value={this.state.parameters.backgroundProcess.keepalive} offOption="No" onOption="Yes" handleToggle={() => {this.updateBackgroundProcess("keepalive", !this.state.parameters.backgroundProcess.keepalive)}} />
           </>}
         </div>
+        {this.state.settings && <div>Hint Penalty:
+          Status <input type="number" min="0" value={this.state.settings.hintPenalty ? this.state.settings.hintPenalty.statusId.id : 0} onInput={(e) => {this.updateHintPenalty("statusId", e)}} />
+          New State <input type="number" min="0" value={this.state.settings.hintPenalty ? this.state.settings.hintPenalty.newState.id : 0} onInput={(e) => {this.updateHintPenalty("newState", e)}} />
+        </div>}
         <div>System Connections: Not Yet Implemented</div>
         <div>Media Players: Not Yet Implemented</div>
         <div>DMX Controllers: Not Yet Implemented</div>
@@ -72,7 +72,106 @@ export function saveEdits(modifications) {
         'Content-Type': 'application/json',
     },
     body: JSON.stringify(editItem),
-  }); // Ignore errors
+  })
+  .then(response => response.json())
+
+  // If the modifications were rejected, report each problem
+  .then(json => {
+    if (!json.isValid && json.data.modificationErrors) {
+      json.data.modificationErrors.forEach((error) => {
+        console.error(`Modification ${error.index + 1} was rejected: ${error.message}.`);
+      });
+    }
+  })
+  .catch(() => {}); // Ignore other errors
+}
+
+// Function to recover the most recent autosave and reload the page
+export async function recoverAutosave() {
+  fetch(`/recoverAutosave`, {
+    method: 'POST',
+    headers: {
+        'Content-Type': 'application/json',
+    },
+  })
+
+  // If the request is a success, reload
+  .then(
+    setTimeout(() => {
+      window.location.reload(false);
+    }, 500)
+  );
+}
+
+// Function to import a cue list from a CSV file and reload the page
+export async function importCues(filename) {
+  // Import the cues
+  let importCues = {
+    filename: filename,
+  };
+  fetch(`/importCues`, {
+    method: 'POST',
+    headers: {
+        'Content-Type': 'application/json',
+    },
+    body: JSON.stringify(importCues),
+  })
+
+  // If the request is a success, reload
+  .then(
+    setTimeout(() => {
+      window.location.reload(false);
+    }, 500)
+  );
+}
+
+// Function to export the items and events to a cue list in a CSV file
+export async function exportCues(filename) {
+  // Export the cues
+  let exportCues = {
+    filename: filename,
+  };
+  fetch(`/exportCues`, {
+    method: 'POST',
+    headers: {
+        'Content-Type': 'application/json',
+    },
+    body: JSON.stringify(exportCues),
+  }); // FIXME ignore errors
+}
+
+// Function to reverse the most recent modification and reload the page
+export async function undoEdit() {
+  fetch(`/undo`, {
+    method: 'POST',
+    headers: {
+        'Content-Type': 'application/json',
+    },
+  })
+
+  // If the request is a success, reload
+  .then(
+    setTimeout(() => {
+      window.location.reload(false);
+    }, 500)
+  );
+}
+
+// Function to repeat the most recently undone modification and reload the page
+export async function redoEdit() {
+  fetch(`/redo`, {
+    method: 'POST',
+    headers: {
+        'Content-Type': 'application/json',
+    },
+  })
+
+  // If the request is a success, reload
+  .then(
+    setTimeout(() => {
+      window.location.reload(false);
+    }, 500)
+  );
 }
 
 // Function to save the style change
@@ -1,7 +1,7 @@
 import React from 'react';
 import logoWide from '.././logo_wide.png';
 import { ConfirmButton } from './Buttons';
-import { asyncForEach, stopPropogation, switchPort, newConfig } from './Functions';
+import { asyncForEach, stopPropogation, switchPort, newConfig, recoverAutosave, importCues, exportCues, undoEdit, redoEdit } from './Functions';
 
 // A menu pop-up for deleting items
 export class DeleteMenu extends React.PureComponent {  
@@ -93,11 +93,16 @@ export class HeaderMenu extends React.PureComponent {
         <div className="headerLeft">
           <div className="title">Minerva | EDIT MODE</div>
           <div class={"menuButton saveButton" + (this.props.saved ? " inactive" : "")} onClick={this.props.saveFile}>Save</div>
+          <div class="menuButton" onClick={undoEdit}>Undo</div>
+          <div class="menuButton" onClick={redoEdit}>Redo</div>
           <div className={"menuButton" + (this.state.isFileVisible ? " selected" : "")} onClick={() => this.setState((prevState) => { return { isFileVisible: !prevState.isFileVisible }})}>File
             {this.state.isFileVisible &&
               <div class="headerExpansion">
                 <ConfirmButton buttonClass="expansionMenuButton" onClick={() => {switchPort(64636);}} buttonText="Normal Mode" />
                 <div class="expansionMenuButton" onClick={newConfig}>New Config</div>
+                <ConfirmButton buttonClass="expansionMenuButton" onClick={recoverAutosave} buttonText="Recover Autosave" />
+                <div class="expansionMenuButton" onClick={() => {let filename = window.prompt("Cue list to import (CSV)"); if (filename) {importCues(filename);}}}>Import Cues</div>
+                <div class="expansionMenuButton" onClick={() => {let filename = window.prompt("Cue list to export (CSV)"); if (filename) {exportCues(filename);}}}>Export Cues</div>
               </div>
             }
           </div>
@@ -553,16 +558,19 @@ export class AddActionMenu extends React.PureComponent {
   render() {
     // Compose the list of possible action types
     let actionList = [
-      <div className="addActionButton" onClick={() => {this.props.addAction({ AdjustMedia: { adjustment: { channel: 0, direction: "Up" }}})}}>Adjust Media</div>,
-      <div className="addActionButton" onClick={() => {this.props.addAction({ CancelEvent: { event: { id: 0 }}})}}>Cancel Event</div>,
-      <div className="addActionButton" onClick={() => {this.props.addAction({ CueEvent: { event: { event_id: { id: 0 }}}})}}>Cue Event</div>,
-      <div className="addActionButton" onClick={() => {this.props.addAction({ CueDmx: { fade: { channel: 1, value: 0 }}})}}>Cue Lights</div>,
-      <div className="addActionButton" onClick={() => {this.props.addAction({ CueMedia: { cue: { uri: "", channel: 0 }}})}}>Cue Media</div>,
-      <div className="addActionButton" onClick={() => {this.props.addAction({ ModifyStatus: { status_id: { id: 0 }, new_state: { id: 0 }}})}}>Modify Status</div>,
-      <div className="addActionButton" onClick={() => {this.props.addAction({ NewScene: { new_scene: { id: 0 }}})}}>New Scene</div>,
-      <div className="addActionButton" onClick={() => {this.props.addAction({ SelectEvent: { status_id: { id: 0 }, event_map: {}, }})}}>Select Event</div>,
-      <div className="addActionButton" onClick={() => {this.props.addAction({ SaveData: { data: { StaticString: { string: "" }}}})}}>Save Data</div>,
-      <div className="addActionButton" onClick={() => {this.props.addAction({ SendData: { data: { StaticString: { string: "" }}}})}}>Send Data</div>
+      <div className="addActionButton" onClick={() => {this.props.addAction({ adjustMedia: { adjustment: { channel: 0, direction: "Up" }}})}}>Adjust Media</div>,
+      <div className="addActionButton" onClick={() => {this.props.addAction({ cancelEvent: { event: { id: 0 }}})}}>Cancel Event</div>,
+      <div className="addActionButton" onClick={() => {this.props.addAction({ cueEvent: { event: { event_id: { id: 0 }}}})}}>Cue Event</div>,
+      <div className="addActionButton" onClick={() => {this.props.addAction({ cueDmx: { fade: { channel: 1, value: 0 }}})}}>Cue Lights</div>,
+      <div className="addActionButton" onClick={() => {this.props.addAction({ cueMedia: { cue: { uri: "", channel: 0 }}})}}>Cue Media</div>,
+      <div className="addActionButton" onClick={() => {this.props.addAction({ deliverHint: {}})}}>Deliver Hint</div>,
+      <div className="addActionButton" onClick={() => {this.props.addAction({ endSession: { outcome: "" }})}}>End Session</div>,
+      <div className="addActionButton" onClick={() => {this.props.addAction({ modifyStatus: { statusId: { id: 0 }, newState: { id: 0 }}})}}>Modify Status</div>,
+      <div className="addActionButton" onClick={() => {this.props.addAction({ newScene: { newScene: { id: 0 }}})}}>New Scene</div>,
+      <div className="addActionButton" onClick={() => {this.props.addAction({ selectEvent: { statusId: { id: 0 }, eventMap: {}, }})}}>Select Event</div>,
+      <div className="addActionButton" onClick={() => {this.props.addAction({ saveData: { data: { StaticString: { string: "" }}}})}}>Save Data</div>,
+      <div className="addActionButton" onClick={() => {this.props.addAction({ sendData: { data: { StaticString: { string: "" }}}})}}>Send Data</div>,
+      <div className="addActionButton" onClick={() => {this.props.addAction({ startSession: {}})}}>Start Session</div>
     ];
 
     // Return the box
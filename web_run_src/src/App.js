@@ -41,6 +41,21 @@ export class App extends React.PureComponent {
   async componentDidMount() {
     // Try to connect the socket
     this.connectSocket();
+
+    // Load the notifications that haven't been acknowledged
+    try {
+      const response = await fetch(`/notifications`);
+      const json = await response.json();
+      if (json.isValid) {
+        this.setState({
+          notifications: json.data.notifications.filter((notification) => !notification.isAcknowledged),
+        });
+      }
+
+    // Ignore errors
+    } catch {
+      console.log("Server inaccessible.");
+    }
   }
 
   // A helper function to connect the websocket
@@ -135,10 +150,6 @@ export class App extends React.PureComponent {
         notifications: data[`updateNotifications`][`notifications`],
       });
 
-      // FIXME Need a better place for this than the commandline
-      console.log(`Notifications`);
-      console.log(data[`updateNotifications`][`notifications`]);
-
     // Update the event timeline
     } else if (data.hasOwnProperty(`updateTimeline`)) {
       this.setState({
@@ -173,7 +184,7 @@ export class App extends React.PureComponent {
       <>
         <link id="userStyles" rel="stylesheet" href={`/getStyles/${this.state.randomCss}.css`} />
         <div className="app">
-          <HeaderMenu closeMinerva={this.closeMinerva} currentScene={this.state.currentScene}/>
+          <HeaderMenu closeMinerva={this.closeMinerva} currentScene={this.state.currentScene} notifications={this.state.notifications}/>
           <ViewArea currentScene={this.state.currentScene} />
           <FooterMenu notice={this.state.notice} />
         </div>
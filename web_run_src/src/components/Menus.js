@@ -14,6 +14,13 @@ export class HeaderMenu extends React.PureComponent {
     }
   }
 
+  // Function to deliver the next hint for the current scene
+  deliverHint() {
+    fetch(`/deliverHint`, {
+      method: 'POST',
+    }); // FIXME ignore errors
+  }
+
   // Render the header menu
   render() {
     return (
@@ -28,6 +35,9 @@ export class HeaderMenu extends React.PureComponent {
             }
           </div>
           <SceneMenu value={this.props.currentScene.id} />
+          <SnapshotMenu />
+          <div className="menuButton" onClick={this.deliverHint}>Deliver Hint</div>
+          <NotificationMenu notifications={this.props.notifications} />
         </div>
         <div className="headerRight">
           <ConfirmButton buttonClass="menuButton" onClick={() => {this.props.closeMinerva();}} buttonText="Quit Minerva" />
@@ -146,3 +156,140 @@ export class SceneMenu extends React.PureComponent {
   }
 }
 
+
+// A menu to save and restore snapshots of the game state
+export class SnapshotMenu extends React.PureComponent {
+  // Class constructor
+  constructor(props) {
+    // Collect props
+    super(props);
+
+    // Set initial state
+    this.state = {
+      isVisible: false,
+      snapshotList: [],
+    }
+
+    // Bind functions
+    this.loadSnapshots = this.loadSnapshots.bind(this);
+    this.saveSnapshot = this.saveSnapshot.bind(this);
+    this.sendRequest = this.sendRequest.bind(this);
+    this.toggleVisible = this.toggleVisible.bind(this);
+  }
+
+  // Function to pull the list of snapshots
+  async loadSnapshots() {
+    try {
+      // Fetch the snapshots and process the response
+      const response = await fetch(`/listSnapshots`);
+      const json = await response.json();
+
+      // If the response is valid, save the result to the state
+      if (json.isValid) {
+        this.setState({
+          snapshotList: json.data.snapshots,
+        });
+      }
+
+    // Ignore errors
+    } catch {
+      console.log("Server inaccessible.");
+    }
+  }
+
+  // Function to send a snapshot request and refresh the list
+  async sendRequest(path, name) {
+    await fetch(path, {
+      method: 'POST',
+      headers: {
+          'Content-Type': 'application/json',
+      },
+      body: JSON.stringify({ name: name }),
+    }); // FIXME ignore errors
+    this.loadSnapshots();
+  }
+
+  // Function to save a new snapshot
+  saveSnapshot() {
+    // Ask for the name of the snapshot
+    const name = window.prompt("Snapshot name:");
+    if (name) {
+      this.sendRequest(`/saveSnapshot`, name);
+    }
+  }
+
+  // Function to show or hide the menu
+  toggleVisible() {
+    // Refresh the list when the menu is opened
+    if (!this.state.isVisible) {
+      this.loadSnapshots();
+    }
+    this.setState((prevState) => { return { isVisible: !prevState.isVisible }});
+  }
+
+  // Render the snapshot menu
+  render() {
+    return (
+      <div className={"menuButton" + (this.state.isVisible ? " selected" : "")} onClick={this.toggleVisible}>Snapshots
+        {this.state.isVisible &&
+          <div className="headerExpansion">
+            <div className="expansionMenuButton" onClick={this.saveSnapshot}>Save Snapshot</div>
+            {this.state.snapshotList.map((snapshot) =>
+              <div key={snapshot.name} className="expansionMenuButton">
+                <ConfirmButton buttonClass="expansionMenuButton" onClick={() => {this.sendRequest(`/restoreSnapshot`, snapshot.name);}} buttonText={`Restore "${snapshot.name}" (${snapshot.saved})`} />
+                <ConfirmButton buttonClass="expansionMenuButton" onClick={() => {this.sendRequest(`/deleteSnapshot`, snapshot.name);}} buttonText="Delete" />
+              </div>
+            )}
+          </div>
+        }
+      </div>
+    );
+  }
+}
+
+// A menu to review and acknowledge warnings and errors
+export class NotificationMenu extends React.PureComponent {
+  // Class constructor
+  constructor(props) {
+    // Collect props
+    super(props);
+
+    // Set initial state
+    this.state = {
+      isVisible: false,
+    }
+
+    // Bind functions
+    this.acknowledge = this.acknowledge.bind(this);
+  }
+
+  // Function to acknowledge the notifications (all notifications if ids is null)
+  acknowledge(ids) {
+    fetch(`/acknowledgeNotifications`, {
+      method: 'POST',
+      headers: {
+          'Content-Type': 'application/json',
+      },
+      body: JSON.stringify({ ids: ids }),
+    }); // FIXME ignore errors
+  }
+
+  // Render the notification menu
+  render() {
+    return (
+      <div className={"menuButton" + (this.state.isVisible ? " selected" : "")} onClick={() => this.setState((prevState) => { return { isVisible: !prevState.isVisible }})}>{`Notifications (${this.props.notifications.length})`}
+        {this.state.isVisible &&
+          <div className="headerExpansion">
+            {this.props.notifications.length > 0 && <div className="expansionMenuButton" onClick={() => {this.acknowledge(null);}}>Clear All</div>}
+            {this.props.notifications.map((notification) =>
+              <div key={notification.id.toString()} className="expansionMenuButton" onClick={() => {this.acknowledge([notification.id]);}}>
+                {`${notification.isError ? "Error" : "Warning"}: ${notification.message}`}
+                <div className="footnote">{`at ${notification.time} (click to acknowledge)`}</div>
+              </div>
+            )}
+          </div>
+        }
+      </div>
+    );
+  }
+}